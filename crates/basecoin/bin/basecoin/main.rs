@@ -4,8 +4,8 @@
 #![forbid(unsafe_code)]
 
 use basecoin::{
-    app::default_app_runner,
-    cli::command::{BasecoinCli, Commands, QueryCmd, UpgradeCmd},
+    app::{default_app_runner, validate_genesis_file},
+    cli::command::{BasecoinCli, Commands, GenesisCmd, QueryCmd, UpgradeCmd},
 };
 
 use clap::Parser;
@@ -16,7 +16,7 @@ use tracing::metadata::LevelFilter;
 #[tokio::main]
 async fn main() {
     let cli = BasecoinCli::parse();
-    let cfg = load_config(cli.config.clone()).unwrap();
+    let mut cfg = load_config(cli.config.clone()).unwrap();
 
     let log_level = if cli.quiet {
         LevelFilter::OFF
@@ -29,9 +29,16 @@ async fn main() {
     tracing_subscriber::fmt().with_max_level(log_level).init();
 
     match &cli.command {
-        Commands::Start => {
+        Commands::Start(start) => {
+            if let Some(halt_height) = start.halt_height {
+                cfg.server.halt_height = halt_height;
+            }
+            if let Some(halt_time) = start.halt_time {
+                cfg.server.halt_time = halt_time;
+            }
             tracing::info!("Starting app and waiting for CometBFT to connect...");
-            default_app_runner(cfg.server).await
+            let reason = default_app_runner(cfg).await;
+            std::process::exit(reason.exit_code());
         }
         Commands::Query(q) => {
             let query_res = match q {
@@ -41,5 +48,15 @@ async fn main() {
             };
             println!("{:?}", query_res);
         }
+        Commands::Genesis(GenesisCmd::Validate(validate)) => {
+            if let Err(e) = validate_genesis_file(&cfg, &validate.file) {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+            println!(
+                "File at {} is a valid genesis file",
+                validate.file.display()
+            );
+        }
     };
 }
@@ -0,0 +1,76 @@
+//! Captures the build metadata reported by the node info query (`GetNodeInfo`).
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let workspace_dir = Path::new(&manifest_dir).join("../..");
+
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(&workspace_dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=BASECOIN_GIT_COMMIT={git_commit}");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=BASECOIN_RUSTC_VERSION={rustc_version}");
+
+    // the dependencies (name, version, checksum) the binary was built with, from the lockfile
+    let lockfile = workspace_dir.join("Cargo.lock");
+    let deps = fs::read_to_string(&lockfile)
+        .map(|lockfile| locked_packages(&lockfile))
+        .unwrap_or_default();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("build_deps.rs"),
+        format!("pub const BUILD_DEPS: &[(&str, &str, &str)] = &{deps:?};\n"),
+    )
+    .unwrap();
+
+    println!("cargo:rerun-if-changed={}", lockfile.display());
+
+    // the commit changes when HEAD is moved to another branch (or commit), as well as when the
+    // branch it points to moves, whose ref is either a file of its own or listed in `packed-refs`
+    let git_dir = workspace_dir.join(".git");
+    let head = git_dir.join("HEAD");
+    let mut watched = vec![git_dir.join("packed-refs")];
+    if let Some(branch) = fs::read_to_string(&head)
+        .ok()
+        .and_then(|head| Some(head.strip_prefix("ref:")?.trim().to_string()))
+    {
+        watched.push(git_dir.join(branch));
+    }
+    watched.push(head);
+    // a path that doesn't exist would make cargo rerun the script on every build
+    for path in watched.iter().filter(|path| path.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn locked_packages(lockfile: &str) -> Vec<(String, String, String)> {
+    let mut packages = vec![];
+    for package in lockfile.split("[[package]]").skip(1) {
+        let field = |name: &str| {
+            package
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.trim().strip_prefix('='))
+                .map(|value| value.trim().trim_matches('"').to_string())
+                .unwrap_or_default()
+        };
+        packages.push((field("name"), field("version"), field("checksum")));
+    }
+    packages
+}
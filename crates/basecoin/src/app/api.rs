@@ -0,0 +1,868 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Path, RawQuery};
+use axum::http::{header, HeaderMap, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, on, MethodFilter};
+use axum::Router;
+use cosmos_config::ApiConfig;
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, MethodDescriptor,
+    SerializeOptions, Value,
+};
+use serde_json::{json, Value as JsonValue};
+use subtle_encoding::base64;
+use tonic::{Code, Status};
+use tower_http::cors::CorsLayer;
+use tracing::debug;
+
+use super::grpc_router::{GrpcQueryRouter, GRPC_BLOCK_HEIGHT_HEADER};
+
+/// A REST endpoint answered by a gRPC method, as declared by the `google.api.http` annotations
+/// of the Go SDK's proto files.
+#[derive(Clone, Debug)]
+struct Route {
+    method: Method,
+    /// path template, with the path parameters in braces (e.g. `{address}`)
+    path: &'static str,
+    /// full gRPC method path
+    grpc_method: &'static str,
+}
+
+const fn route(method: Method, path: &'static str, grpc_method: &'static str) -> Route {
+    Route {
+        method,
+        path,
+        grpc_method,
+    }
+}
+
+const ROUTES: &[Route] = &[
+    // auth
+    route(
+        Method::GET,
+        "/cosmos/auth/v1beta1/accounts",
+        "/cosmos.auth.v1beta1.Query/Accounts",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/auth/v1beta1/accounts/{address}",
+        "/cosmos.auth.v1beta1.Query/Account",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/auth/v1beta1/params",
+        "/cosmos.auth.v1beta1.Query/Params",
+    ),
+    // bank
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/balances/{address}",
+        "/cosmos.bank.v1beta1.Query/AllBalances",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/balances/{address}/by_denom",
+        "/cosmos.bank.v1beta1.Query/Balance",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/spendable_balances/{address}",
+        "/cosmos.bank.v1beta1.Query/SpendableBalances",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/supply",
+        "/cosmos.bank.v1beta1.Query/TotalSupply",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/supply/by_denom",
+        "/cosmos.bank.v1beta1.Query/SupplyOf",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/params",
+        "/cosmos.bank.v1beta1.Query/Params",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/denoms_metadata",
+        "/cosmos.bank.v1beta1.Query/DenomsMetadata",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/bank/v1beta1/denoms_metadata/{denom}",
+        "/cosmos.bank.v1beta1.Query/DenomMetadata",
+    ),
+    // staking
+    route(
+        Method::GET,
+        "/cosmos/staking/v1beta1/validators",
+        "/cosmos.staking.v1beta1.Query/Validators",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/staking/v1beta1/validators/{validator_addr}",
+        "/cosmos.staking.v1beta1.Query/Validator",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/staking/v1beta1/params",
+        "/cosmos.staking.v1beta1.Query/Params",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/staking/v1beta1/pool",
+        "/cosmos.staking.v1beta1.Query/Pool",
+    ),
+    // tx
+    route(
+        Method::POST,
+        "/cosmos/tx/v1beta1/simulate",
+        "/cosmos.tx.v1beta1.Service/Simulate",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/tx/v1beta1/txs",
+        "/cosmos.tx.v1beta1.Service/GetTxsEvent",
+    ),
+    route(
+        Method::POST,
+        "/cosmos/tx/v1beta1/txs",
+        "/cosmos.tx.v1beta1.Service/BroadcastTx",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/tx/v1beta1/txs/{hash}",
+        "/cosmos.tx.v1beta1.Service/GetTx",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/tx/v1beta1/txs/block/{height}",
+        "/cosmos.tx.v1beta1.Service/GetBlockWithTxs",
+    ),
+    // base tendermint
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/node_info",
+        "/cosmos.base.tendermint.v1beta1.Service/GetNodeInfo",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/syncing",
+        "/cosmos.base.tendermint.v1beta1.Service/GetSyncing",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/blocks/latest",
+        "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/blocks/{height}",
+        "/cosmos.base.tendermint.v1beta1.Service/GetBlockByHeight",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/validatorsets/latest",
+        "/cosmos.base.tendermint.v1beta1.Service/GetLatestValidatorSet",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/validatorsets/{height}",
+        "/cosmos.base.tendermint.v1beta1.Service/GetValidatorSetByHeight",
+    ),
+    route(
+        Method::GET,
+        "/cosmos/base/tendermint/v1beta1/abci_query",
+        "/cosmos.base.tendermint.v1beta1.Service/ABCIQuery",
+    ),
+];
+
+/// HTTP server answering the REST endpoints of the Go SDK's gRPC-gateway (e.g.
+/// `/cosmos/bank/v1beta1/balances/{address}`), by calling the corresponding gRPC method through
+/// a [`GrpcQueryRouter`].
+///
+/// Requests are built from the path and query parameters (or from the JSON body for `POST`
+/// endpoints) and responses are encoded as proto-JSON, both using the protobuf descriptors of
+/// the messages, so that the responses are the same as the ones of the Go SDK's REST server.
+#[derive(Clone, Debug)]
+pub struct ApiServer {
+    grpc_router: GrpcQueryRouter,
+    descriptors: DescriptorPool,
+    config: ApiConfig,
+}
+
+impl ApiServer {
+    pub fn new(grpc_router: GrpcQueryRouter, config: ApiConfig) -> Self {
+        let descriptors = DescriptorPool::decode(ibc_proto::FILE_DESCRIPTOR_SET)
+            .expect("invalid protobuf file descriptor set");
+        Self {
+            grpc_router,
+            descriptors,
+            config,
+        }
+    }
+
+    /// Returns the routes of the server.
+    pub fn router(self) -> Router {
+        let server = Arc::new(self);
+        let mut router = Router::new();
+
+        for route in ROUTES {
+            let server = server.clone();
+            let handler = move |params: Option<Path<HashMap<String, String>>>,
+                                RawQuery(query): RawQuery,
+                                headers: HeaderMap,
+                                body: Bytes| async move {
+                let params = params.map(|Path(params)| params).unwrap_or_default();
+                server
+                    .handle(route, params, query, &headers, &body)
+                    .await
+                    .unwrap_or_else(error_response)
+            };
+            let filter = if route.method == Method::POST {
+                MethodFilter::POST
+            } else {
+                MethodFilter::GET
+            };
+            router = router.route(&axum_path(route.path), on(filter, handler));
+        }
+
+        if server.config.swagger {
+            let openapi = server.openapi().to_string();
+            router = router.route(
+                "/swagger/openapi.json",
+                get(|| async move { ([(header::CONTENT_TYPE, "application/json")], openapi) }),
+            );
+        }
+        if server.config.enabled_unsafe_cors {
+            router = router.layer(CorsLayer::permissive());
+        }
+        router
+    }
+
+    async fn handle(
+        &self,
+        route: &Route,
+        params: HashMap<String, String>,
+        query: Option<String>,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Response, Status> {
+        debug!("Got REST request for {}", route.grpc_method);
+
+        let method = self.method(route.grpc_method).ok_or_else(|| {
+            Status::unimplemented(format!("unknown method {}", route.grpc_method))
+        })?;
+
+        let mut request = if route.method == Method::POST && !body.is_empty() {
+            let mut deserializer = serde_json::Deserializer::from_slice(body);
+            DynamicMessage::deserialize(method.input(), &mut deserializer)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?
+        } else {
+            DynamicMessage::new(method.input())
+        };
+        let query: Vec<(String, String)> =
+            serde_urlencoded::from_str(query.as_deref().unwrap_or(""))
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        for (name, value) in params.iter().chain(query.iter().map(|(k, v)| (k, v))) {
+            set_field(&mut request, name, value)?;
+        }
+
+        let height = match headers.get(GRPC_BLOCK_HEIGHT_HEADER) {
+            Some(height) => height
+                .to_str()
+                .ok()
+                .and_then(|height| height.parse().ok())
+                .ok_or_else(|| Status::invalid_argument("invalid block height header"))?,
+            None => 0,
+        };
+
+        let response = self
+            .grpc_router
+            .call(route.grpc_method, &request.encode_to_vec(), height)
+            .await
+            .ok_or_else(|| {
+                Status::unimplemented(format!("{} is not served", route.grpc_method))
+            })??;
+        let response = DynamicMessage::decode(method.output(), response.as_slice())
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        let mut serializer = serde_json::Serializer::new(vec![]);
+        response
+            .serialize_with_options(
+                &mut serializer,
+                &SerializeOptions::new()
+                    .use_proto_field_name(true)
+                    .skip_default_fields(false),
+            )
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok((
+            [(header::CONTENT_TYPE, "application/json")],
+            serializer.into_inner(),
+        )
+            .into_response())
+    }
+
+    fn method(&self, grpc_method: &str) -> Option<MethodDescriptor> {
+        let (service, method) = grpc_method.strip_prefix('/')?.rsplit_once('/')?;
+        self.descriptors
+            .get_service_by_name(service)?
+            .methods()
+            .find(|m| m.name() == method)
+    }
+
+    /// Returns the OpenAPI (v3) document describing the REST endpoints.
+    fn openapi(&self) -> JsonValue {
+        let mut paths = BTreeMap::<&str, serde_json::Map<String, JsonValue>>::new();
+        let mut schemas = BTreeMap::new();
+
+        for route in ROUTES {
+            let Some(method) = self.method(route.grpc_method) else {
+                continue;
+            };
+            let path_params = path_params(route.path);
+            let mut parameters: Vec<_> = path_params
+                .iter()
+                .map(|name| {
+                    json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } })
+                })
+                .collect();
+
+            let mut operation = json!({
+                "operationId": method.name(),
+                "summary": method.full_name(),
+                "responses": {
+                    "200": {
+                        "description": "A successful response.",
+                        "content": { "application/json": { "schema": schema_ref(&method.output()) } }
+                    },
+                    "default": {
+                        "description": "An unexpected error response.",
+                        "content": { "application/json": { "schema": {
+                            "type": "object",
+                            "properties": {
+                                "code": { "type": "integer" },
+                                "message": { "type": "string" },
+                                "details": { "type": "array", "items": { "type": "object" } }
+                            }
+                        } } }
+                    }
+                }
+            });
+            if route.method == Method::POST {
+                operation["requestBody"] = json!({
+                    "required": true,
+                    "content": { "application/json": { "schema": schema_ref(&method.input()) } }
+                });
+            } else {
+                query_params(&method.input(), "", &path_params, &mut parameters);
+            }
+            operation["parameters"] = JsonValue::Array(parameters);
+
+            add_schema(&method.input(), &mut schemas);
+            add_schema(&method.output(), &mut schemas);
+            paths
+                .entry(route.path)
+                .or_default()
+                .insert(route.method.as_str().to_lowercase(), operation);
+        }
+
+        json!({
+            "openapi": "3.0.0",
+            "info": {
+                "title": "Basecoin REST API",
+                "description": "gRPC-gateway compatible REST endpoints of the Cosmos SDK modules",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": paths,
+            "components": { "schemas": schemas },
+        })
+    }
+}
+
+/// Converts a path template to the axum syntax, i.e. `{address}` to `:address`.
+fn axum_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{') {
+            Some(param) => format!(":{}", param.trim_end_matches('}')),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn path_params(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .collect()
+}
+
+/// Sets the (possibly nested, e.g. `pagination.limit`) field of the request from its string
+/// representation. Repeated fields are appended to.
+fn set_field(message: &mut DynamicMessage, name: &str, value: &str) -> Result<(), Status> {
+    let (name, rest) = match name.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (name, None),
+    };
+    let descriptor = message.descriptor();
+    let field = descriptor
+        .get_field_by_name(name)
+        .or_else(|| descriptor.get_field_by_json_name(name))
+        .ok_or_else(|| Status::invalid_argument(format!("unknown parameter {name}")))?;
+
+    match (rest, message.get_field_mut(&field)) {
+        (Some(rest), Value::Message(nested)) => set_field(nested, rest, value),
+        (None, Value::List(values)) => {
+            values.push(parse_value(&field, value)?);
+            Ok(())
+        }
+        (None, field_value) if !field.is_map() => {
+            *field_value = parse_value(&field, value)?;
+            Ok(())
+        }
+        _ => Err(Status::invalid_argument(format!(
+            "unsupported parameter {}",
+            field.full_name()
+        ))),
+    }
+}
+
+fn parse_value(field: &FieldDescriptor, value: &str) -> Result<Value, Status> {
+    let invalid = || Status::invalid_argument(format!("invalid value for {}", field.name()));
+    Ok(match field.kind() {
+        Kind::Double => Value::F64(value.parse().map_err(|_| invalid())?),
+        Kind::Float => Value::F32(value.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            Value::I32(value.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            Value::I64(value.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => Value::U32(value.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => Value::U64(value.parse().map_err(|_| invalid())?),
+        Kind::Bool => Value::Bool(value.parse().map_err(|_| invalid())?),
+        Kind::String => Value::String(value.to_string()),
+        Kind::Bytes => Value::Bytes(
+            base64::decode(value.replace('-', "+").replace('_', "/"))
+                .map_err(|_| invalid())?
+                .into(),
+        ),
+        Kind::Enum(descriptor) => Value::EnumNumber(match descriptor.get_value_by_name(value) {
+            Some(variant) => variant.number(),
+            None => value.parse().map_err(|_| invalid())?,
+        }),
+        Kind::Message(_) => return Err(invalid()),
+    })
+}
+
+/// Adds the (scalar) fields of the request message not bound by the path as query parameters.
+fn query_params(
+    message: &MessageDescriptor,
+    prefix: &str,
+    path_params: &[&str],
+    parameters: &mut Vec<JsonValue>,
+) {
+    for field in message.fields() {
+        let name = format!("{prefix}{}", field.name());
+        if path_params.contains(&name.as_str()) {
+            continue;
+        }
+        match field.kind() {
+            // only one level of nesting, e.g. `pagination.limit`
+            Kind::Message(nested) if prefix.is_empty() && !field.is_list() => {
+                query_params(&nested, &format!("{name}."), path_params, parameters)
+            }
+            Kind::Message(_) => {}
+            _ => parameters.push(json!({
+                "name": name,
+                "in": "query",
+                "required": false,
+                "schema": field_schema(&field),
+            })),
+        }
+    }
+}
+
+fn schema_ref(message: &MessageDescriptor) -> JsonValue {
+    json!({ "$ref": format!("#/components/schemas/{}", message.full_name()) })
+}
+
+/// Adds the schema of the message, and of all messages it references, to the schemas.
+fn add_schema(message: &MessageDescriptor, schemas: &mut BTreeMap<String, JsonValue>) {
+    if schemas.contains_key(message.full_name()) {
+        return;
+    }
+    // placeholder, so that recursive messages terminate
+    schemas.insert(message.full_name().to_string(), JsonValue::Null);
+
+    let properties: serde_json::Map<_, _> = message
+        .fields()
+        .map(|field| (field.name().to_string(), field_schema(&field)))
+        .collect();
+    schemas.insert(
+        message.full_name().to_string(),
+        json!({ "type": "object", "properties": properties }),
+    );
+
+    for field in message.fields() {
+        if let Kind::Message(nested) = field.kind() {
+            add_schema(&nested, schemas);
+        }
+    }
+}
+
+/// Returns the schema of the proto-JSON encoding of the field.
+fn field_schema(field: &FieldDescriptor) -> JsonValue {
+    let schema = match field.kind() {
+        Kind::Double | Kind::Float => json!({ "type": "number" }),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 | Kind::Uint32 | Kind::Fixed32 => {
+            json!({ "type": "integer" })
+        }
+        // 64-bit integers are encoded as strings
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            json!({ "type": "string", "format": "int64" })
+        }
+        Kind::Uint64 | Kind::Fixed64 => json!({ "type": "string", "format": "uint64" }),
+        Kind::Bool => json!({ "type": "boolean" }),
+        Kind::String => json!({ "type": "string" }),
+        Kind::Bytes => json!({ "type": "string", "format": "byte" }),
+        Kind::Enum(descriptor) => json!({
+            "type": "string",
+            "enum": descriptor.values().map(|v| v.name().to_string()).collect::<Vec<_>>(),
+        }),
+        Kind::Message(message) => schema_ref(&message),
+    };
+    if field.is_map() {
+        json!({ "type": "object" })
+    } else if field.is_list() {
+        json!({ "type": "array", "items": schema })
+    } else {
+        schema
+    }
+}
+
+/// Encodes the error the same way as the gRPC-gateway does.
+fn error_response(status: Status) -> Response {
+    let code = match status.code() {
+        Code::Ok => StatusCode::OK,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::Cancelled | Code::Unknown | Code::Internal | Code::DataLoss => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    let body = json!({
+        "code": status.code() as i32,
+        "message": status.message(),
+        "details": [],
+    });
+    (
+        code,
+        [(header::CONTENT_TYPE, "application/json")],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::interface::tendermint::tests::bank_app;
+    use axum::body::{Body, HttpBody};
+    use axum::http::Request;
+    use cosmos_x_auth::account::account_prefix;
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::AccountId;
+    use ibc_proto::cosmos::bank::v1beta1::QueryAllBalancesRequest;
+    use ibc_proto::cosmos::tx::v1beta1::GetTxsEventRequest;
+    use tonic::codegen::Service;
+
+    fn address(seed: u8) -> AccountId {
+        SigningKey::from_slice(&[seed; 32])
+            .unwrap()
+            .public_key()
+            .account_id(&account_prefix())
+            .unwrap()
+    }
+
+    /// Returns the routes of a server answering through the gRPC services of an app with the
+    /// `auth` and `bank` modules, where the account of `address(1)` has 100 basecoins.
+    fn router(config: ApiConfig) -> Router {
+        let app = bank_app(&address(1));
+        ApiServer::new((*app.grpc_router).clone(), config).router()
+    }
+
+    async fn send(router: &Router, request: Request<Body>) -> (StatusCode, HeaderMap, JsonValue) {
+        let mut router = router.clone();
+        futures::future::poll_fn(|cx| router.poll_ready(cx))
+            .await
+            .unwrap();
+        let mut response = router.call(request).await.unwrap();
+        let mut body = vec![];
+        while let Some(chunk) = response.body_mut().data().await {
+            body.extend_from_slice(&chunk.unwrap());
+        }
+        let body = serde_json::from_slice(&body).unwrap_or(JsonValue::Null);
+        (response.status(), response.headers().clone(), body)
+    }
+
+    async fn get(router: &Router, uri: &str) -> (StatusCode, JsonValue) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let (status, _, body) = send(router, request).await;
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn test_balances() {
+        let router = router(ApiConfig::default());
+        let address = address(1);
+
+        let (status, body) =
+            get(&router, &format!("/cosmos/bank/v1beta1/balances/{address}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["balances"],
+            json!([{ "denom": "basecoin", "amount": "100" }])
+        );
+
+        // query parameters are set on the request
+        let uri = format!("/cosmos/bank/v1beta1/balances/{address}/by_denom?denom=basecoin");
+        let (status, body) = get(&router, &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["balance"],
+            json!({ "denom": "basecoin", "amount": "100" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_account() {
+        let router = router(ApiConfig::default());
+        let address = address(1);
+
+        let (status, body) =
+            get(&router, &format!("/cosmos/auth/v1beta1/accounts/{address}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["account"]["@type"], "/cosmos.auth.v1beta1.BaseAccount");
+        assert_eq!(body["account"]["address"], address.to_string());
+        // 64-bit integers are encoded as strings and default values aren't skipped
+        assert_eq!(body["account"]["sequence"], "0");
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let router = router(ApiConfig::default());
+
+        let (status, body) = get(&router, "/cosmos/auth/v1beta1/accounts/invalid").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], Code::InvalidArgument as i32);
+        assert_eq!(body["message"], "invalid address: invalid");
+        assert_eq!(body["details"], json!([]));
+
+        let uri = format!("/cosmos/auth/v1beta1/accounts/{}", address(2));
+        let (status, body) = get(&router, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], Code::NotFound as i32);
+
+        let uri = format!("/cosmos/bank/v1beta1/balances/{}", address(1));
+        for query in ["?unknown=1", "?pagination.limit=ten", "?pagination=1"] {
+            let (status, _) = get(&router, &format!("{uri}{query}")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+        }
+
+        // only the latest height can be queried
+        for height in ["1000", "latest"] {
+            let request = Request::get(&uri)
+                .header(GRPC_BLOCK_HEIGHT_HEADER, height)
+                .body(Body::empty())
+                .unwrap();
+            let (status, _, _) = send(&router, request).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{height}");
+        }
+
+        // the app has no staking module
+        let (status, body) = get(&router, "/cosmos/staking/v1beta1/pool").await;
+        assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(body["code"], Code::Unimplemented as i32);
+    }
+
+    #[tokio::test]
+    async fn test_openapi() {
+        let (status, _) = get(&router(ApiConfig::default()), "/swagger/openapi.json").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let router = router(ApiConfig {
+            swagger: true,
+            ..ApiConfig::default()
+        });
+        let (status, openapi) = get(&router, "/swagger/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        for route in ROUTES {
+            let method = route.method.as_str().to_lowercase();
+            let operation = &openapi["paths"][route.path][&method];
+            assert!(operation.is_object(), "{method} {} is missing", route.path);
+            let grpc_method = route.grpc_method.trim_start_matches('/').replace('/', ".");
+            assert_eq!(operation["summary"], grpc_method);
+        }
+
+        let parameters = &openapi["paths"]["/cosmos/bank/v1beta1/balances/{address}/by_denom"]
+            ["get"]["parameters"];
+        assert_eq!(parameters[0]["name"], "address");
+        assert_eq!(parameters[0]["in"], "path");
+        assert_eq!(parameters[0]["required"], true);
+        assert_eq!(parameters[1]["name"], "denom");
+        assert_eq!(parameters[1]["in"], "query");
+        let schemas = &openapi["components"]["schemas"];
+        assert!(schemas["cosmos.bank.v1beta1.QueryBalanceResponse"].is_object());
+        assert!(schemas["cosmos.base.v1beta1.Coin"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_cors() {
+        let uri = format!("/cosmos/bank/v1beta1/balances/{}", address(1));
+        let request = || {
+            Request::get(&uri)
+                .header(header::ORIGIN, "https://example.com")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let (_, headers, _) = send(&router(ApiConfig::default()), request()).await;
+        assert!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+        let router = router(ApiConfig {
+            enabled_unsafe_cors: true,
+            ..ApiConfig::default()
+        });
+        let (_, headers, _) = send(&router, request()).await;
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+
+    #[test]
+    fn test_routes() {
+        let server = ApiServer::new(GrpcQueryRouter::default(), ApiConfig::default());
+        for (i, route) in ROUTES.iter().enumerate() {
+            let method = server.method(route.grpc_method);
+            assert!(method.is_some(), "unknown method {}", route.grpc_method);
+            assert!(
+                ROUTES[..i]
+                    .iter()
+                    .all(|r| r.path != route.path || r.method != route.method),
+                "duplicate route {} {}",
+                route.method,
+                route.path
+            );
+        }
+        assert!(server
+            .method("/cosmos.bank.v1beta1.Query/Unknown")
+            .is_none());
+        assert!(server.method("cosmos.bank.v1beta1.Query/Balance").is_none());
+    }
+
+    #[test]
+    fn test_error_response() {
+        for (code, status) in [
+            (Code::InvalidArgument, StatusCode::BAD_REQUEST),
+            (Code::FailedPrecondition, StatusCode::BAD_REQUEST),
+            (Code::NotFound, StatusCode::NOT_FOUND),
+            (Code::AlreadyExists, StatusCode::CONFLICT),
+            (Code::PermissionDenied, StatusCode::FORBIDDEN),
+            (Code::Unauthenticated, StatusCode::UNAUTHORIZED),
+            (Code::ResourceExhausted, StatusCode::TOO_MANY_REQUESTS),
+            (Code::Unimplemented, StatusCode::NOT_IMPLEMENTED),
+            (Code::Unavailable, StatusCode::SERVICE_UNAVAILABLE),
+            (Code::DeadlineExceeded, StatusCode::GATEWAY_TIMEOUT),
+            (Code::Internal, StatusCode::INTERNAL_SERVER_ERROR),
+        ] {
+            let response = error_response(Status::new(code, "error"));
+            assert_eq!(response.status(), status, "{code:?}");
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        }
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(
+            axum_path("/cosmos/bank/v1beta1/balances/{address}/by_denom"),
+            "/cosmos/bank/v1beta1/balances/:address/by_denom"
+        );
+        assert_eq!(
+            path_params("/cosmos/bank/v1beta1/balances/{address}/by_denom"),
+            ["address"]
+        );
+        assert!(path_params("/cosmos/bank/v1beta1/supply").is_empty());
+    }
+
+    #[test]
+    fn test_set_field() {
+        let server = ApiServer::new(GrpcQueryRouter::default(), ApiConfig::default());
+        let method = server
+            .method("/cosmos.bank.v1beta1.Query/AllBalances")
+            .unwrap();
+        let mut request = DynamicMessage::new(method.input());
+        set_field(&mut request, "address", "cosmos1abc").unwrap();
+        set_field(&mut request, "pagination.limit", "10").unwrap();
+        // fields can also be named by their JSON name
+        set_field(&mut request, "pagination.countTotal", "true").unwrap();
+        // bytes are (possibly URL-safe) base64 encoded
+        set_field(&mut request, "pagination.key", "_-8A").unwrap();
+
+        let request = QueryAllBalancesRequest::decode(request.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(request.address, "cosmos1abc");
+        let pagination = request.pagination.unwrap();
+        assert_eq!(pagination.limit, 10);
+        assert!(pagination.count_total);
+        assert_eq!(pagination.key, [0xff, 0xef, 0x00]);
+
+        let mut request = DynamicMessage::new(method.input());
+        for (name, value) in [
+            ("unknown", "1"),
+            ("pagination.limit", "-1"),
+            ("pagination.count_total", "yes"),
+            ("pagination", "1"),
+        ] {
+            let status = set_field(&mut request, name, value).unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_set_repeated_and_enum_fields() {
+        let server = ApiServer::new(GrpcQueryRouter::default(), ApiConfig::default());
+        let method = server
+            .method("/cosmos.tx.v1beta1.Service/GetTxsEvent")
+            .unwrap();
+        let mut request = DynamicMessage::new(method.input());
+        set_field(&mut request, "events", "tx.height>1").unwrap();
+        set_field(&mut request, "events", "message.sender='cosmos1abc'").unwrap();
+        // enums are set by name or by number
+        set_field(&mut request, "order_by", "ORDER_BY_DESC").unwrap();
+
+        let decoded = GetTxsEventRequest::decode(request.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(
+            decoded.events,
+            ["tx.height>1", "message.sender='cosmos1abc'"]
+        );
+        assert_eq!(decoded.order_by, 2);
+
+        set_field(&mut request, "order_by", "1").unwrap();
+        let decoded = GetTxsEventRequest::decode(request.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.order_by, 1);
+    }
+}
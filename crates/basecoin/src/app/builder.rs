@@ -1,23 +1,55 @@
-use std::sync::{Arc, RwLock};
-use tracing::error;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tonic::body::BoxBody;
+use tonic::codegen::{http, Service};
+use tonic::server::NamedService;
+use tonic::transport::Body;
+use tracing::{error, warn};
 
+use super::grpc_router::GrpcQueryRouter;
+use super::indexer::TxIndex;
+use super::router::MsgServiceRouter;
+use super::shutdown::{Shutdown, ShutdownReason};
 use crate::error::Error;
-use cosmrs::AccountId;
+use cosmrs::{AccountId, Tx};
 use ibc_proto::google::protobuf::Any;
+use serde_json::Value;
 use tendermint_proto::abci::Event;
+use tendermint_rpc::HttpClient;
 
 use cosmos_helper::Identifier;
+use cosmos_x_module_api::types::DeliverResult;
 use cosmos_x_module_api::types::IdentifiedModule;
 use cosmos_x_module_api::types::ModuleList;
 use cosmos_x_module_api::types::ModuleStore;
 
 use cosmos_x_module_api::module::Module;
 
-use cosmos_store::{MainStore, ProvableStore, RevertibleStore, SharedRw, SharedStore};
+use cosmos_store::{
+    GasStore, MainStore, ProvableStore, RevertibleStore, SharedRw, SharedStore, Store,
+};
+
+#[cfg(feature = "v0_38")]
+use super::interface::v0_38::AbciPlusState;
+#[cfg(feature = "v0_38")]
+use tendermint_proto_v038::v0_38::abci::{RequestExtendVote, RequestVerifyVoteExtension};
 
 pub struct Builder<S> {
     store: MainStore<S>,
     modules: SharedRw<ModuleList<S>>,
+    msg_router: MsgServiceRouter,
+    grpc_router: GrpcQueryRouter,
+    rpc_client: Option<HttpClient>,
+    tx_index: TxIndex,
+    halt_height: u64,
+    halt_time: u64,
+    order_init_genesis: Vec<Identifier>,
+    order_begin_blockers: Vec<Identifier>,
+    order_end_blockers: Vec<Identifier>,
+    #[cfg(feature = "v0_38")]
+    abci_plus: AbciPlusState,
 }
 
 impl<S: Default + ProvableStore + 'static> Builder<S> {
@@ -26,6 +58,17 @@ impl<S: Default + ProvableStore + 'static> Builder<S> {
         Self {
             store: SharedStore::new(RevertibleStore::new(store)),
             modules: Arc::new(RwLock::new(vec![])),
+            msg_router: MsgServiceRouter::default(),
+            grpc_router: GrpcQueryRouter::default(),
+            rpc_client: None,
+            tx_index: TxIndex::default(),
+            halt_height: 0,
+            halt_time: 0,
+            order_init_genesis: vec![],
+            order_begin_blockers: vec![],
+            order_end_blockers: vec![],
+            #[cfg(feature = "v0_38")]
+            abci_plus: AbciPlusState::default(),
         }
     }
 
@@ -37,7 +80,7 @@ impl<S: Default + ProvableStore + 'static> Builder<S> {
             .iter()
             .find(|m| &m.id == prefix)
             .map(|IdentifiedModule { module, .. }| module.store().share())
-            .unwrap_or_else(|| SharedStore::new(ModuleStore::new(S::default())))
+            .unwrap_or_else(|| SharedStore::new(ModuleStore::new(GasStore::new(S::default()))))
     }
 
     #[inline]
@@ -45,26 +88,202 @@ impl<S: Default + ProvableStore + 'static> Builder<S> {
         !self.modules.read().unwrap().iter().any(|m| &m.id == prefix)
     }
 
-    /// Adds a new module. Panics if a module with the specified identifier was previously added.
+    /// Adds a new module, named after its store prefix, and registers the message types it
+    /// handles with the message router.
+    /// Panics if a module with the specified identifier was previously added or if any of the
+    /// module's message types is already handled by another module.
     pub fn add_module(
         self,
         prefix: Identifier,
         module: impl Module<Store = ModuleStore<S>> + 'static,
+    ) -> Self {
+        let name = prefix.to_string();
+        self.add_named_module(name, prefix, module)
+    }
+
+    /// Same as `add_module()`, but for a module whose name (e.g. `gov`), which keys its section
+    /// of the genesis `app_state`, differs from its store prefix (e.g. `governance`).
+    pub fn add_named_module(
+        mut self,
+        name: impl Into<String>,
+        prefix: Identifier,
+        module: impl Module<Store = ModuleStore<S>> + 'static,
     ) -> Self {
         assert!(self.is_unique_id(&prefix), "module prefix must be unique");
-        self.modules.write().unwrap().push(IdentifiedModule {
+        let mut modules = self.modules.write().unwrap();
+        for type_url in module.msg_type_urls() {
+            if let Err(e) = self.msg_router.register(type_url, modules.len(), &prefix) {
+                panic!("{e}");
+            }
+        }
+        modules.push(IdentifiedModule {
             id: prefix,
+            name: name.into(),
             module: Box::new(module),
         });
+        drop(modules);
+        self
+    }
+
+    /// Registers a module's gRPC query service so that its methods can also be queried through
+    /// ABCI `Query` using the full gRPC method path (e.g. `/cosmos.bank.v1beta1.Query/Balance`).
+    /// Panics if a service with the same name was previously registered.
+    pub fn add_grpc_service<G>(mut self, service: G) -> Self
+    where
+        G: Service<http::Request<Body>, Response = http::Response<BoxBody>, Error = Infallible>
+            + NamedService
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        G::Future: Send + 'static,
+    {
+        self.grpc_router.register(service);
+        self
+    }
+
+    /// Sets the client of the CometBFT RPC that transactions received by the tx service are
+    /// broadcast through.
+    pub fn set_rpc_client(mut self, rpc_client: HttpClient) -> Self {
+        self.rpc_client = Some(rpc_client);
+        self
+    }
+
+    /// Sets the index of the delivered transactions, by default kept in memory.
+    pub fn set_tx_index(mut self, tx_index: TxIndex) -> Self {
+        self.tx_index = tx_index;
+        self
+    }
+
+    /// Sets the order in which the modules initialize their genesis state, e.g. so that `auth`
+    /// creates the genesis accounts before `bank` sets their balances.
+    /// Must list every module exactly once, defaults to the order the modules were added in.
+    pub fn set_order_init_genesis(mut self, order: impl IntoIterator<Item = Identifier>) -> Self {
+        self.order_init_genesis = order.into_iter().collect();
+        self
+    }
+
+    /// Sets the order in which the modules' `BeginBlock` hooks are called.
+    /// Must list every module exactly once, defaults to the order the modules were added in.
+    pub fn set_order_begin_blockers(mut self, order: impl IntoIterator<Item = Identifier>) -> Self {
+        self.order_begin_blockers = order.into_iter().collect();
+        self
+    }
+
+    /// Sets the order in which the modules' `EndBlock` hooks are called.
+    /// Must list every module exactly once, defaults to the order the modules were added in.
+    pub fn set_order_end_blockers(mut self, order: impl IntoIterator<Item = Identifier>) -> Self {
+        self.order_end_blockers = order.into_iter().collect();
+        self
+    }
+
+    /// Sets the height of the block after which the app requests the node to shut down, as the
+    /// Go SDK's `--halt-height`. `0` (the default) disables halting at a height.
+    pub fn set_halt_height(mut self, halt_height: u64) -> Self {
+        self.halt_height = halt_height;
         self
     }
 
+    /// Sets the UNIX time (in seconds) of the first block after which the app requests the node
+    /// to shut down, as the Go SDK's `--halt-time`. `0` (the default) disables halting at a time.
+    pub fn set_halt_time(mut self, halt_time: u64) -> Self {
+        self.halt_time = halt_time;
+        self
+    }
+
+    /// Sets the hook that produces the vote extensions of this node's precommits.
+    #[cfg(feature = "v0_38")]
+    pub fn set_extend_vote_handler(
+        mut self,
+        handler: impl Fn(&RequestExtendVote) -> Vec<u8> + Send + Sync + 'static,
+    ) -> Self {
+        self.abci_plus.extend_vote = Some(Arc::new(handler));
+        self
+    }
+
+    /// Sets the hook that validates the vote extensions of other validators' precommits.
+    #[cfg(feature = "v0_38")]
+    pub fn set_verify_vote_extension_handler(
+        mut self,
+        handler: impl Fn(&RequestVerifyVoteExtension) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.abci_plus.verify_vote_extension = Some(Arc::new(handler));
+        self
+    }
+
+    /// Builds the app.
+    /// Panics if any of the module orders that were set doesn't list every module exactly once.
     pub fn build(self) -> BaseCoinApp<S> {
+        let order = ModuleOrder {
+            init_genesis: self.module_order("init genesis", &self.order_init_genesis),
+            begin_blockers: self.module_order("begin blockers", &self.order_begin_blockers),
+            end_blockers: self.module_order("end blockers", &self.order_end_blockers),
+        };
+        let mut grpc_router = self.grpc_router;
+        let store = self.store.clone();
+        grpc_router.set_latest_height(move || store.current_height());
+
         BaseCoinApp {
             store: self.store,
             modules: self.modules,
+            msg_router: Arc::new(self.msg_router),
+            grpc_router: Arc::new(grpc_router),
+            rpc_client: self.rpc_client,
+            tx_index: Arc::new(RwLock::new(self.tx_index)),
+            order: Arc::new(order),
+            exec_lock: Arc::new(Mutex::new(())),
+            block: Arc::new(Mutex::new(None)),
+            halt_height: self.halt_height,
+            halt_time: self.halt_time,
+            shutdown: Shutdown::new(),
+            #[cfg(feature = "v0_38")]
+            abci_plus: self.abci_plus,
+            #[cfg(feature = "v0_38")]
+            abci_store: SharedStore::new(S::default()),
         }
     }
+
+    /// Resolves an order of module identifiers to the indices of the modules. The order must list
+    /// every module exactly once, as Go's module manager asserts for its `SetOrder*` calls.
+    fn module_order(&self, kind: &str, order: &[Identifier]) -> Vec<usize> {
+        let modules = self.modules.read().unwrap();
+        if order.is_empty() {
+            return (0..modules.len()).collect();
+        }
+
+        let mut indices = Vec::with_capacity(order.len());
+        for id in order {
+            let index = modules
+                .iter()
+                .position(|m| &m.id == id)
+                .unwrap_or_else(|| panic!("{kind} order lists unknown module `{id}`"));
+            assert!(
+                !indices.contains(&index),
+                "{kind} order lists module `{id}` more than once"
+            );
+            indices.push(index);
+        }
+        let missing: Vec<_> = modules
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !indices.contains(index))
+            .map(|(_, m)| m.id.to_string())
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "all modules must be defined when setting {kind} order, missing: {}",
+            missing.join(", ")
+        );
+        indices
+    }
+}
+
+/// Order in which the hooks of the modules are called, as indices into the module list.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ModuleOrder {
+    pub(crate) init_genesis: Vec<usize>,
+    pub(crate) begin_blockers: Vec<usize>,
+    pub(crate) end_blockers: Vec<usize>,
 }
 
 /// BaseCoin ABCI application.
@@ -74,43 +293,291 @@ impl<S: Default + ProvableStore + 'static> Builder<S> {
 pub struct BaseCoinApp<S> {
     pub store: MainStore<S>,
     pub modules: SharedRw<ModuleList<S>>,
+    pub msg_router: Arc<MsgServiceRouter>,
+    pub grpc_router: Arc<GrpcQueryRouter>,
+    pub(crate) rpc_client: Option<HttpClient>,
+    pub(crate) tx_index: SharedRw<TxIndex>,
+    pub(crate) order: Arc<ModuleOrder>,
+    /// serializes block execution and tx simulations, since simulations run against (and then
+    /// revert) the same stores
+    pub(crate) exec_lock: Arc<Mutex<()>>,
+    /// height and UNIX time of the block being executed, between `BeginBlock` and `Commit`
+    pub(crate) block: Arc<Mutex<Option<(u64, i64)>>>,
+    halt_height: u64,
+    halt_time: u64,
+    shutdown: Shutdown,
+    #[cfg(feature = "v0_38")]
+    pub(crate) abci_plus: AbciPlusState,
+    /// chain ID and consensus params, which the ABCI 2.0 requests lack, kept apart from the main
+    /// store so that they aren't committed to by the app hash
+    #[cfg(feature = "v0_38")]
+    pub(crate) abci_store: SharedStore<S>,
+}
+
+impl<S> BaseCoinApp<S> {
+    /// Returns the handle through which the app requests the node to shut down (once the halt
+    /// height or time is reached) and through which the node's servers wait for a shutdown.
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
+    /// Requests the node to shut down if the committed block is at the halt height or time.
+    pub(crate) fn check_halt(&self, height: u64, time: i64) {
+        if self.halt_height > 0 && height >= self.halt_height {
+            self.shutdown
+                .trigger(ShutdownReason::HaltHeight(self.halt_height));
+        } else if self.halt_time > 0 && time >= self.halt_time as i64 {
+            self.shutdown
+                .trigger(ShutdownReason::HaltTime(self.halt_time));
+        }
+    }
+
+    /// Panics if the node is shutting down, so that no block is executed past the halt height (or
+    /// time) while the node's servers are stopping.
+    pub(crate) fn ensure_running(&self, height: u64) {
+        if let Some(reason) = self.shutdown.reason() {
+            panic!("refusing to execute block {height}: the node is shutting down ({reason})");
+        }
+    }
+
+    /// Waits for the block being executed (if any) to be committed, flushes the tx index and
+    /// returns the execution lock, so that no further block or simulation is executed while it's
+    /// held. Gives up waiting for the block after `timeout`, in which case its changes are lost.
+    pub fn halt(&self, timeout: Duration) -> MutexGuard<'_, ()> {
+        let start = Instant::now();
+        let exec = loop {
+            let exec = self.exec_lock.lock().unwrap();
+            match *self.block.lock().unwrap() {
+                None => break exec,
+                Some((height, _)) if start.elapsed() >= timeout => {
+                    warn!("block {height} wasn't committed before shutting down");
+                    break exec;
+                }
+                Some(_) => {}
+            }
+            drop(exec);
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        if let Err(e) = self.tx_index.read().unwrap().flush() {
+            error!("failed to flush the tx index: {e}");
+        }
+        exec
+    }
 }
 
 impl<S: Default + ProvableStore> BaseCoinApp<S> {
-    // try to deliver the message to all registered modules
-    // if `module.deliver()` returns `Error::NotHandled`, try next module
+    // run the ante handlers of all registered modules (in order) on the transaction
     // Return:
-    // * other errors immediately OR
-    // * `Error::NotHandled` if all modules return `Error::NotHandled`
-    // * events from first successful deliver call
-    pub fn deliver_msg(&self, message: Any, signer: &AccountId) -> Result<Vec<Event>, Error> {
+    // * the first error returned by a module OR
+    // * events from all modules
+    pub fn ante_handle(
+        &self,
+        tx: &Tx,
+        signer: &AccountId,
+        simulate: bool,
+    ) -> Result<Vec<Event>, Error> {
         let mut modules = self.modules.write().unwrap();
-        let mut handled = false;
         let mut events = vec![];
 
         for IdentifiedModule { module, .. } in modules.iter_mut() {
-            match module.deliver(message.clone(), signer) {
-                Ok(mut msg_events) => {
-                    events.append(&mut msg_events);
-                    handled = true;
-                    break;
-                }
-                // todo(davirain)
-                Err(e) if e.to_string() == anyhow::anyhow!(Error::NotHandled).to_string() => {
-                    continue
-                }
-                Err(e) => {
-                    error!("deliver message ({:?}) failed with error: {:?}", message, e);
-                    return Err(Error::Custom {
-                        reason: e.to_string(),
-                    });
-                }
-            }
+            let mut ante_events = module.ante_handle(tx, signer, simulate).map_err(|e| {
+                error!("ante handler failed with error: {:?}", e);
+                Error::Module(e)
+            })?;
+            events.append(&mut ante_events);
+        }
+        Ok(events)
+    }
+
+    /// Checks the genesis `app_state`, i.e. that each module's section (keyed by the module's
+    /// name) is valid for that module. A missing section is validated as the module's default
+    /// genesis state.
+    pub fn validate_genesis(&self, app_state: &Value) -> Result<(), Error> {
+        if !app_state.is_object() && !app_state.is_null() {
+            return Err(Error::Custom {
+                reason: "genesis app state must be a JSON object".to_string(),
+            });
+        }
+        let modules = self.modules.read().unwrap();
+        for IdentifiedModule { name, module, .. } in modules.iter() {
+            let genesis = app_state.get(name).unwrap_or(&Value::Null);
+            module
+                .validate_genesis(genesis)
+                .map_err(|e| Error::InvalidGenesis {
+                    module: name.clone(),
+                    reason: e.to_string(),
+                })?;
         }
-        if handled {
-            Ok(events)
-        } else {
-            Err(Error::NotHandled)
+        Ok(())
+    }
+
+    /// Applies the state changes accumulated by all stores, so that they're no longer reverted by
+    /// `reset_changes()`.
+    pub(crate) fn apply_changes(&self) {
+        let mut modules = self.modules.write().unwrap();
+        for IdentifiedModule { module, .. } in modules.iter_mut() {
+            module
+                .store_mut()
+                .apply()
+                .expect("failed to apply state changes");
         }
+        self.store
+            .write()
+            .unwrap()
+            .apply()
+            .expect("failed to apply state changes");
+    }
+
+    /// Reverts the state changes accumulated by all stores since the last `apply_changes()`.
+    pub(crate) fn reset_changes(&self) {
+        let mut modules = self.modules.write().unwrap();
+        for IdentifiedModule { module, .. } in modules.iter_mut() {
+            module.store_mut().reset();
+        }
+        self.store.write().unwrap().reset();
+    }
+
+    // deliver the message to the module that registered its type URL
+    // Return:
+    // * `Error::UnknownMessageType` if no module handles messages of this type OR
+    // * the error returned by the module OR
+    // * events and the message response from the module
+    pub fn deliver_msg(&self, message: Any, signer: &AccountId) -> Result<DeliverResult, Error> {
+        let index = self.msg_router.route(&message.type_url)?;
+        let mut modules = self.modules.write().unwrap();
+
+        modules[index]
+            .module
+            .deliver(message.clone(), signer)
+            .map_err(|e| {
+                error!("deliver message ({:?}) failed with error: {:?}", message, e);
+                Error::Module(e)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_store::memory::InMemoryStore;
+    use cosmos_x_staking::impls::Staking;
+
+    /// Returns a builder with a (staking) module for each of the store prefixes.
+    fn builder(prefixes: &[&str]) -> Builder<InMemoryStore> {
+        prefixes
+            .iter()
+            .fold(Builder::new(InMemoryStore::default()), |builder, prefix| {
+                let prefix = Identifier::from(prefix.to_string());
+                let staking = Staking::new(builder.module_store(&prefix));
+                builder.add_module(prefix, staking)
+            })
+    }
+
+    fn ids(prefixes: &[&str]) -> Vec<Identifier> {
+        prefixes
+            .iter()
+            .map(|p| Identifier::from(p.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_module_order() {
+        let app = builder(&["auth", "bank", "gov"]).build();
+        assert_eq!(app.order.init_genesis, [0, 1, 2]);
+        assert_eq!(app.order.begin_blockers, [0, 1, 2]);
+        assert_eq!(app.order.end_blockers, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_module_order() {
+        let app = builder(&["auth", "bank", "gov"])
+            .set_order_init_genesis(ids(&["bank", "auth", "gov"]))
+            .set_order_begin_blockers(ids(&["gov", "auth", "bank"]))
+            .build();
+        assert_eq!(app.order.init_genesis, [1, 0, 2]);
+        assert_eq!(app.order.begin_blockers, [2, 0, 1]);
+        assert_eq!(app.order.end_blockers, [0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "begin blockers order lists unknown module `mint`")]
+    fn test_module_order_unknown_module() {
+        builder(&["auth", "bank"])
+            .set_order_begin_blockers(ids(&["auth", "bank", "mint"]))
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "end blockers order lists module `auth` more than once")]
+    fn test_module_order_duplicate_module() {
+        builder(&["auth", "bank"])
+            .set_order_end_blockers(ids(&["auth", "bank", "auth"]))
+            .build();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "all modules must be defined when setting init genesis order, missing: bank, gov"
+    )]
+    fn test_module_order_missing_modules() {
+        builder(&["auth", "bank", "gov"])
+            .set_order_init_genesis(ids(&["auth"]))
+            .build();
+    }
+
+    fn halting_app(halt_height: u64, halt_time: u64) -> BaseCoinApp<InMemoryStore> {
+        Builder::new(InMemoryStore::default())
+            .set_halt_height(halt_height)
+            .set_halt_time(halt_time)
+            .build()
+    }
+
+    #[test]
+    fn test_check_halt_height() {
+        let app = halting_app(10, 0);
+        app.check_halt(9, i64::MAX);
+        assert_eq!(app.shutdown().reason(), None);
+        app.check_halt(10, 0);
+        assert_eq!(
+            app.shutdown().reason(),
+            Some(ShutdownReason::HaltHeight(10))
+        );
+    }
+
+    #[test]
+    fn test_check_halt_time() {
+        let app = halting_app(0, 1_000);
+        app.check_halt(u64::MAX, 999);
+        assert_eq!(app.shutdown().reason(), None);
+        app.check_halt(1, 1_000);
+        assert_eq!(
+            app.shutdown().reason(),
+            Some(ShutdownReason::HaltTime(1_000))
+        );
+
+        // the first reason is kept once both are reached
+        let app = halting_app(10, 1_000);
+        app.check_halt(10, 1_000);
+        assert_eq!(
+            app.shutdown().reason(),
+            Some(ShutdownReason::HaltHeight(10))
+        );
+    }
+
+    #[test]
+    fn test_check_halt_disabled() {
+        let app = halting_app(0, 0);
+        app.check_halt(u64::MAX, i64::MAX);
+        assert_eq!(app.shutdown().reason(), None);
+        app.ensure_running(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "refusing to execute block 11")]
+    fn test_ensure_running_after_halt() {
+        let app = halting_app(10, 0);
+        app.check_halt(10, 0);
+        app.ensure_running(11);
     }
 }
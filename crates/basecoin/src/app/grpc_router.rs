@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use futures::FutureExt;
+use tonic::body::BoxBody;
+use tonic::codegen::{http, BoxFuture, Bytes, Service};
+use tonic::server::NamedService;
+use tonic::transport::Body;
+use tonic::Status;
+
+/// Header used by the Go SDK to pass the query height to gRPC query handlers.
+pub const GRPC_BLOCK_HEIGHT_HEADER: &str = "x-cosmos-block-height";
+
+/// Length of the gRPC message frame header (compression flag + big-endian message length).
+const GRPC_FRAME_HEADER_LEN: usize = 5;
+
+type Handler = Arc<
+    dyn Fn(http::Request<Body>) -> BoxFuture<http::Response<BoxBody>, Infallible> + Send + Sync,
+>;
+
+type LatestHeight = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// Routes ABCI queries to the gRPC query services registered by modules, based on the full
+/// gRPC method path of the query (e.g. `/cosmos.bank.v1beta1.Query/Balance`).
+/// Similar to the Go SDK's `GRPCQueryRouter`.
+///
+/// The query data is expected to be the protobuf encoded request and the returned bytes are
+/// the protobuf encoded response, i.e. a query is answered exactly like the gRPC endpoint
+/// would answer it.
+#[derive(Clone, Default)]
+pub struct GrpcQueryRouter {
+    /// gRPC service name (e.g. `cosmos.bank.v1beta1.Query`) -> handler
+    services: HashMap<String, Handler>,
+    /// returns the latest committed height, queries at which are answered with the latest state
+    latest_height: Option<LatestHeight>,
+}
+
+impl GrpcQueryRouter {
+    /// Registers all methods of a tonic generated gRPC server.
+    /// Panics if a service with the same name was previously registered.
+    pub fn register<S>(&mut self, service: S)
+    where
+        S: Service<http::Request<Body>, Response = http::Response<BoxBody>, Error = Infallible>
+            + NamedService
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Future: Send + 'static,
+    {
+        let handler: Handler = Arc::new(move |request| {
+            let mut service = service.clone();
+            Box::pin(async move {
+                futures::future::poll_fn(|cx| service.poll_ready(cx)).await?;
+                service.call(request).await
+            })
+        });
+        assert!(
+            self.services.insert(S::NAME.to_owned(), handler).is_none(),
+            "gRPC service {} is already registered",
+            S::NAME
+        );
+    }
+
+    /// Sets the source of the latest committed height, so that queries at that height (e.g. by
+    /// clients passing the height they last saw) are answered as queries at height `0` are.
+    pub fn set_latest_height(&mut self, latest_height: impl Fn() -> u64 + Send + Sync + 'static) {
+        self.latest_height = Some(Arc::new(latest_height));
+    }
+
+    /// Answers the query without blocking, for the (synchronous) ABCI `Query`, which may be
+    /// served from within an async runtime. The modules' services read their stores without
+    /// awaiting anything, so their response is ready as soon as the request is handled; a
+    /// service that isn't ready is reported as unavailable rather than blocking the caller.
+    /// Returns `None` if no handler was registered for the service of the specified method path.
+    pub fn query(&self, method: &str, data: &[u8], height: u64) -> Option<Result<Vec<u8>, Status>> {
+        self.call(method, data, height)
+            .now_or_never()
+            .unwrap_or_else(|| {
+                Some(Err(Status::unavailable(format!(
+                    "{method} can't be answered without blocking"
+                ))))
+            })
+    }
+
+    /// Same as [`query()`](Self::query), for callers that are already running on an executor.
+    ///
+    /// Only the latest state can be queried, so a query at any other height (i.e. other than `0`
+    /// or the latest committed height) is rejected rather than answered with the latest state.
+    pub async fn call(
+        &self,
+        method: &str,
+        data: &[u8],
+        height: u64,
+    ) -> Option<Result<Vec<u8>, Status>> {
+        let handler = self.handler(method)?;
+        let latest_height = self
+            .latest_height
+            .as_ref()
+            .map(|latest_height| latest_height());
+        if height != 0 && Some(height) != latest_height {
+            return Some(Err(Status::invalid_argument(format!(
+                "{method} can only be queried at the latest height, not at height {height}"
+            ))));
+        }
+        let request = match grpc_request(method, data) {
+            Ok(request) => request,
+            Err(status) => return Some(Err(status)),
+        };
+
+        let response = handler(request).await.unwrap_or_else(|e| match e {});
+        Some(grpc_response(response).await)
+    }
+
+    fn handler(&self, method: &str) -> Option<&Handler> {
+        let (service, _) = method.strip_prefix('/')?.rsplit_once('/')?;
+        self.services.get(service)
+    }
+}
+
+impl Debug for GrpcQueryRouter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcQueryRouter")
+            .field("services", &self.services.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+fn grpc_request(method: &str, data: &[u8]) -> Result<http::Request<Body>, Status> {
+    let len = u32::try_from(data.len())
+        .map_err(|_| Status::invalid_argument("query request too large"))?;
+    let mut frame = Vec::with_capacity(GRPC_FRAME_HEADER_LEN + data.len());
+    frame.push(0); // uncompressed
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(data);
+
+    http::Request::builder()
+        .method(http::Method::POST)
+        .uri(method)
+        .header(http::header::CONTENT_TYPE, "application/grpc")
+        .header("te", "trailers")
+        .body(Body::from(frame))
+        .map_err(|e| Status::invalid_argument(format!("invalid query path {method}: {e}")))
+}
+
+async fn grpc_response(response: http::Response<BoxBody>) -> Result<Vec<u8>, Status> {
+    use tonic::codegen::Body as _;
+
+    let (parts, mut body) = response.into_parts();
+    // a "trailers-only" response carries the status in the headers
+    if let Some(status) = Status::from_header_map(&parts.headers) {
+        if status.code() != tonic::Code::Ok {
+            return Err(status);
+        }
+    }
+
+    let mut buf: Vec<u8> = vec![];
+    while let Some(data) = body.data().await {
+        let data: Bytes = data?;
+        buf.extend_from_slice(&data);
+    }
+    if let Some(trailers) = body.trailers().await? {
+        if let Some(status) = Status::from_header_map(&trailers) {
+            if status.code() != tonic::Code::Ok {
+                return Err(status);
+            }
+        }
+    }
+
+    if buf.len() < GRPC_FRAME_HEADER_LEN {
+        return Err(Status::internal("missing gRPC response message"));
+    }
+    if buf[0] != 0 {
+        return Err(Status::internal("unexpected compressed gRPC response"));
+    }
+    let len = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]) as usize;
+    buf.get(GRPC_FRAME_HEADER_LEN..GRPC_FRAME_HEADER_LEN + len)
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| Status::internal("truncated gRPC response message"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_store::memory::InMemoryStore;
+    use cosmos_store::SharedStore;
+    use cosmos_x_staking::impls::Staking;
+    use ibc_proto::cosmos::staking::v1beta1::{
+        QueryParamsRequest, QueryParamsResponse, QueryValidatorsRequest,
+    };
+    use prost::Message;
+
+    const PARAMS_METHOD: &str = "/cosmos.staking.v1beta1.Query/Params";
+
+    fn router() -> GrpcQueryRouter {
+        let staking = Staking::new(SharedStore::new(InMemoryStore::default()));
+        let mut router = GrpcQueryRouter::default();
+        router.register(staking.service());
+        router
+    }
+
+    #[test]
+    fn test_query() {
+        let router = router();
+
+        let response = router
+            .query(PARAMS_METHOD, &QueryParamsRequest {}.encode_to_vec(), 0)
+            .unwrap()
+            .unwrap();
+        let params = QueryParamsResponse::decode(response.as_slice())
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(params.historical_entries, 1);
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let router = router();
+
+        // methods that aren't implemented by the service fail instead of panicking
+        let status = router
+            .query(
+                "/cosmos.staking.v1beta1.Query/Validators",
+                &QueryValidatorsRequest::default().encode_to_vec(),
+                0,
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unimplemented);
+
+        // only the latest state can be queried
+        let status = router
+            .query(PARAMS_METHOD, &QueryParamsRequest {}.encode_to_vec(), 5)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("height 5"));
+
+        // queries on services that aren't registered aren't handled
+        assert!(router
+            .query("/cosmos.bank.v1beta1.Query/Params", &[], 0)
+            .is_none());
+        assert!(router
+            .query("cosmos.staking.v1beta1.Query", &[], 0)
+            .is_none());
+        assert!(router.query("/", &[], 0).is_none());
+    }
+
+    #[test]
+    fn test_query_at_latest_height() {
+        let mut router = router();
+        router.set_latest_height(|| 7);
+        let query = |height| {
+            router.query(
+                PARAMS_METHOD,
+                &QueryParamsRequest {}.encode_to_vec(),
+                height,
+            )
+        };
+
+        assert!(query(0).unwrap().is_ok());
+        assert!(query(7).unwrap().is_ok());
+        for height in [6, 8] {
+            let status = query(height).unwrap().unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call() {
+        let response = router()
+            .call(PARAMS_METHOD, &QueryParamsRequest {}.encode_to_vec(), 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(QueryParamsResponse::decode(response.as_slice())
+            .unwrap()
+            .params
+            .is_some());
+    }
+}
@@ -0,0 +1,321 @@
+use std::path::Path;
+
+use ibc_proto::cosmos::base::abci::v1beta1::TxResponse;
+use prost::Message;
+
+use super::query::{TM_EVENT_KEY, TX_HASH_KEY, TX_HEIGHT_KEY};
+use super::{Operand, Operator, Query, TxIndexer};
+use crate::error::Error;
+
+/// Length of the primary key of a transaction, i.e. its height followed by its index in the
+/// block, both big-endian so that keys sort in delivery order.
+const PRIMARY_KEY_LEN: usize = 12;
+
+/// Default maximum number of transactions a query without an indexed equality condition may scan.
+const DEFAULT_SCAN_LIMIT: usize = 10_000;
+
+/// Indexer persisting the transactions in an embedded [sled](https://docs.rs/sled) database,
+/// similar to CometBFT's `kv` indexer.
+///
+/// The database has three trees:
+/// * `txs`: primary key -> protobuf encoded `TxResponse`
+/// * `hashes`: hash -> primary key
+/// * `events`: `{eventType}.{attributeKey}\0{value}\0{primary key}` -> (empty), for each
+///   attribute marked `index: true`
+///
+/// Queries are answered by looking up their first equality condition on the hash or an indexed
+/// attribute. Queries without one (e.g. `tx.height>5`) decode every transaction within their
+/// height range, so they fail rather than scan more than the scan limit.
+#[derive(Clone, Debug)]
+pub struct KvTxIndexer {
+    db: sled::Db,
+    txs: sled::Tree,
+    hashes: sled::Tree,
+    events: sled::Tree,
+    scan_limit: usize,
+}
+
+impl KvTxIndexer {
+    /// Opens (or creates) the database at the specified path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let db = sled::open(path).map_err(db_error)?;
+        Ok(Self {
+            txs: db.open_tree("txs").map_err(db_error)?,
+            hashes: db.open_tree("hashes").map_err(db_error)?,
+            events: db.open_tree("events").map_err(db_error)?,
+            db,
+            scan_limit: DEFAULT_SCAN_LIMIT,
+        })
+    }
+
+    /// Sets the maximum number of transactions a query without an indexed equality condition
+    /// may scan.
+    pub fn with_scan_limit(mut self, scan_limit: usize) -> Self {
+        self.scan_limit = scan_limit;
+        self
+    }
+
+    fn load(&self, primary_key: &[u8]) -> Result<Option<TxResponse>, Error> {
+        self.txs
+            .get(primary_key)
+            .map_err(db_error)?
+            .map(|bytes| TxResponse::decode(bytes.as_ref()).map_err(db_error))
+            .transpose()
+    }
+
+    /// Returns the primary keys of the transactions having an indexed attribute with the
+    /// specified composite key and value, in delivery order.
+    fn lookup_event(&self, key: &str, value: &str) -> Result<Vec<Vec<u8>>, Error> {
+        self.events
+            .scan_prefix(event_prefix(key, value))
+            .keys()
+            .map(|key| {
+                let key = key.map_err(db_error)?;
+                Ok(key[key.len() - PRIMARY_KEY_LEN..].to_vec())
+            })
+            .collect()
+    }
+}
+
+impl TxIndexer for KvTxIndexer {
+    fn index(&mut self, txs: Vec<TxResponse>) -> Result<(), Error> {
+        let mut tx_batch = sled::Batch::default();
+        let mut hash_batch = sled::Batch::default();
+        let mut event_batch = sled::Batch::default();
+
+        for (index, tx) in txs.iter().enumerate() {
+            let primary_key = primary_key(tx.height as u64, index as u32);
+            for event in &tx.events {
+                for attr in event.attributes.iter().filter(|attr| attr.index) {
+                    let key = format!("{}.{}", event.r#type, attr.key);
+                    let mut event_key = event_prefix(&key, &attr.value);
+                    event_key.extend_from_slice(&primary_key);
+                    event_batch.insert(event_key, vec![]);
+                }
+            }
+            hash_batch.insert(tx.txhash.as_bytes(), primary_key.to_vec());
+            tx_batch.insert(primary_key.to_vec(), tx.encode_to_vec());
+        }
+
+        self.txs.apply_batch(tx_batch).map_err(db_error)?;
+        self.events.apply_batch(event_batch).map_err(db_error)?;
+        // hashes last, so that a transaction can't be found before it's fully indexed
+        self.hashes.apply_batch(hash_batch).map_err(db_error)?;
+        self.flush()
+    }
+
+    fn get(&self, hash: &str) -> Result<Option<TxResponse>, Error> {
+        match self.hashes.get(hash.as_bytes()).map_err(db_error)? {
+            Some(primary_key) => self.load(&primary_key),
+            None => Ok(None),
+        }
+    }
+
+    fn search(&self, query: &Query) -> Result<Vec<TxResponse>, Error> {
+        let (min_height, max_height) = query.height_range();
+        if min_height > max_height {
+            return Ok(vec![]);
+        }
+
+        // narrow down the candidates with the first equality condition that can be looked up,
+        // otherwise scan the transactions within the height range
+        let lookup: Option<Result<Vec<TxResponse>, Error>> =
+            query.conditions().iter().find_map(|condition| {
+                match (condition.key.as_str(), &condition.op) {
+                    (TX_HASH_KEY, Operator::Eq(Operand::String(hash))) => Some(
+                        self.get(&hash.to_ascii_uppercase())
+                            .map(|tx| tx.into_iter().collect()),
+                    ),
+                    (key, Operator::Eq(Operand::String(value)))
+                        if key != TX_HEIGHT_KEY && key != TM_EVENT_KEY =>
+                    {
+                        Some(self.lookup_event(key, value).and_then(|primary_keys| {
+                            primary_keys
+                                .iter()
+                                .filter(|primary_key| {
+                                    (min_height..=max_height).contains(&key_height(primary_key))
+                                })
+                                .filter_map(|primary_key| self.load(primary_key).transpose())
+                                .collect()
+                        }))
+                    }
+                    _ => None,
+                }
+            });
+        let candidates: Vec<TxResponse> = match lookup {
+            Some(candidates) => candidates?,
+            None => {
+                let candidates: Vec<_> = self
+                    .txs
+                    .range(primary_key(min_height, 0)..)
+                    .values()
+                    .map(|bytes| {
+                        TxResponse::decode(bytes.map_err(db_error)?.as_ref()).map_err(db_error)
+                    })
+                    .take_while(|tx| {
+                        tx.as_ref()
+                            .map_or(true, |tx| tx.height as u64 <= max_height)
+                    })
+                    .take(self.scan_limit + 1)
+                    .collect::<Result<_, _>>()?;
+                if candidates.len() > self.scan_limit {
+                    return Err(Error::TxIndexer {
+                        reason: format!(
+                            "query scans more than {} transactions, narrow it down with an \
+                             indexed attribute or a smaller height range",
+                            self.scan_limit
+                        ),
+                    });
+                }
+                candidates
+            }
+        };
+
+        Ok(candidates
+            .into_iter()
+            .filter(|tx| query.matches(tx))
+            .collect())
+    }
+
+    fn flush(&self) -> Result<(), Error> {
+        self.db.flush().map_err(db_error)?;
+        Ok(())
+    }
+}
+
+fn primary_key(height: u64, index: u32) -> [u8; PRIMARY_KEY_LEN] {
+    let mut key = [0; PRIMARY_KEY_LEN];
+    key[..8].copy_from_slice(&height.to_be_bytes());
+    key[8..].copy_from_slice(&index.to_be_bytes());
+    key
+}
+
+fn key_height(primary_key: &[u8]) -> u64 {
+    let mut height = [0; 8];
+    height.copy_from_slice(&primary_key[..8]);
+    u64::from_be_bytes(height)
+}
+
+fn event_prefix(key: &str, value: &str) -> Vec<u8> {
+    [key.as_bytes(), &[0], value.as_bytes(), &[0]].concat()
+}
+
+fn db_error(e: impl ToString) -> Error {
+    Error::TxIndexer {
+        reason: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::indexer::{tx_hash, TxIndex};
+    use ibc_proto::cosmos::base::abci::v1beta1::SearchTxsResult;
+    use ibc_proto::cosmos::tx::v1beta1::OrderBy;
+    use tendermint_proto::abci::{Event, EventAttribute, ResponseDeliverTx};
+
+    fn tx_bytes(height: u64, recipient: &str) -> Vec<u8> {
+        format!("{recipient}@{height}").into_bytes()
+    }
+
+    fn deliver_result(recipient: &str) -> ResponseDeliverTx {
+        let attribute = |key: &str, value: &str, index| EventAttribute {
+            key: key.to_owned(),
+            value: value.to_owned(),
+            index,
+        };
+        ResponseDeliverTx {
+            events: vec![Event {
+                r#type: "transfer".to_owned(),
+                attributes: vec![
+                    attribute("recipient", recipient, true),
+                    attribute("memo", "unindexed", false),
+                ],
+            }],
+            ..ResponseDeliverTx::default()
+        }
+    }
+
+    /// Indexes blocks 1 to 3, each with a transaction to `alice` followed by one to `bob`.
+    fn index_blocks(tx_index: &mut TxIndex) {
+        for height in 1..=3 {
+            tx_index.begin_block(height, format!("2023-08-01T00:00:0{height}Z"));
+            for recipient in ["alice", "bob"] {
+                tx_index.record(tx_bytes(height, recipient), deliver_result(recipient));
+            }
+            tx_index.commit().unwrap();
+        }
+    }
+
+    fn txs(result: &SearchTxsResult) -> Vec<Vec<u8>> {
+        result
+            .txs
+            .iter()
+            .map(|tx| tx.tx.as_ref().unwrap().value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_kv_indexer() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tx_index = TxIndex::new(KvTxIndexer::open(dir.path()).unwrap());
+        index_blocks(&mut tx_index);
+        drop(tx_index);
+
+        // the indexed transactions survive reopening the database
+        let tx_index = TxIndex::new(KvTxIndexer::open(dir.path()).unwrap());
+        let hash = tx_hash(&tx_bytes(2, "bob"));
+        let tx = tx_index.get(&hash.to_ascii_lowercase()).unwrap().unwrap();
+        assert_eq!(tx.txhash, hash);
+        assert_eq!(tx.height, 2);
+        assert_eq!(tx.timestamp, "2023-08-01T00:00:02Z");
+        assert_eq!(tx.events, deliver_result("bob").events);
+        assert_eq!(tx_index.get(&tx_hash(b"unknown")).unwrap(), None);
+
+        // looked up by attribute, within the height range
+        let query: Query = "transfer.recipient='bob' AND tx.height>1".parse().unwrap();
+        let result = tx_index.search(&query, OrderBy::Asc, 1, 10).unwrap();
+        assert_eq!(result.total_count, 2);
+        assert_eq!(txs(&result), [tx_bytes(2, "bob"), tx_bytes(3, "bob")]);
+
+        let result = tx_index.search(&query, OrderBy::Desc, 1, 1).unwrap();
+        assert_eq!((result.total_count, result.page_total), (2, 2));
+        assert_eq!(txs(&result), [tx_bytes(3, "bob")]);
+        let result = tx_index.search(&query, OrderBy::Desc, 2, 1).unwrap();
+        assert_eq!(txs(&result), [tx_bytes(2, "bob")]);
+        assert!(tx_index.search(&query, OrderBy::Desc, 3, 1).is_err());
+
+        // scanned by height, in delivery order
+        let query: Query = "tx.height>2".parse().unwrap();
+        let result = tx_index.search(&query, OrderBy::Asc, 1, 10).unwrap();
+        assert_eq!(txs(&result), [tx_bytes(3, "alice"), tx_bytes(3, "bob")]);
+
+        // attributes that aren't marked for indexing can't be searched
+        let query: Query = "transfer.memo='unindexed'".parse().unwrap();
+        let result = tx_index.search(&query, OrderBy::Asc, 1, 10).unwrap();
+        assert_eq!(result.total_count, 0);
+        drop(tx_index);
+
+        // scans are bounded, lookups aren't
+        let indexer = KvTxIndexer::open(dir.path()).unwrap().with_scan_limit(4);
+        let tx_index = TxIndex::new(indexer);
+        let query: Query = "tx.height>1".parse().unwrap();
+        assert_eq!(
+            tx_index
+                .search(&query, OrderBy::Asc, 1, 10)
+                .unwrap()
+                .total_count,
+            4
+        );
+        let query: Query = "tx.height>0".parse().unwrap();
+        assert!(tx_index.search(&query, OrderBy::Asc, 1, 10).is_err());
+        let query: Query = "transfer.recipient='bob' AND tx.height>0".parse().unwrap();
+        assert_eq!(
+            tx_index
+                .search(&query, OrderBy::Asc, 1, 10)
+                .unwrap()
+                .total_count,
+            3
+        );
+    }
+}
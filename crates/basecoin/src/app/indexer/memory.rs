@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use ibc_proto::cosmos::base::abci::v1beta1::TxResponse;
+
+use super::{Query, TxIndexer};
+use crate::error::Error;
+
+/// Indexer keeping all transactions in memory, i.e. the index doesn't survive restarts.
+#[derive(Clone, Debug, Default)]
+pub struct MemTxIndexer {
+    txs: Vec<TxResponse>,
+    by_hash: HashMap<String, usize>,
+}
+
+impl TxIndexer for MemTxIndexer {
+    fn index(&mut self, txs: Vec<TxResponse>) -> Result<(), Error> {
+        for tx in txs {
+            self.by_hash.insert(tx.txhash.clone(), self.txs.len());
+            self.txs.push(tx);
+        }
+        Ok(())
+    }
+
+    fn get(&self, hash: &str) -> Result<Option<TxResponse>, Error> {
+        Ok(self.by_hash.get(hash).map(|&index| self.txs[index].clone()))
+    }
+
+    fn search(&self, query: &Query) -> Result<Vec<TxResponse>, Error> {
+        let (min_height, max_height) = query.height_range();
+        let first = self
+            .txs
+            .partition_point(|tx| (tx.height as u64) < min_height);
+        Ok(self.txs[first..]
+            .iter()
+            .take_while(|tx| tx.height as u64 <= max_height)
+            .filter(|tx| query.matches(tx))
+            .cloned()
+            .collect())
+    }
+}
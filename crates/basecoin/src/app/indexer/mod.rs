@@ -0,0 +1,191 @@
+//! Indexing of the transactions delivered by the app, by hash and by events, backing the tx
+//! service's `GetTx` and `GetTxsEvent` queries.
+
+mod kv;
+pub use kv::KvTxIndexer;
+
+mod memory;
+pub use memory::MemTxIndexer;
+
+mod query;
+pub use query::{Condition, Operand, Operator, Query};
+
+use std::fmt::{Debug, Formatter};
+
+use cosmos_config::{IndexerConfig, IndexerKind};
+use ibc_proto::cosmos::base::abci::v1beta1::{SearchTxsResult, TxResponse};
+use ibc_proto::cosmos::tx::v1beta1::OrderBy;
+use ibc_proto::google::protobuf::Any;
+use sha2::{Digest, Sha256};
+use tendermint_proto::abci::ResponseDeliverTx;
+
+use crate::error::Error;
+
+/// Type URL of the `Any` wrapping the transaction in a `TxResponse`.
+const TX_TYPE_URL: &str = "/cosmos.tx.v1beta1.Tx";
+
+/// Hash of a transaction, i.e. the uppercase hex encoded SHA-256 of the transaction bytes.
+pub fn tx_hash(tx: &[u8]) -> String {
+    hex::encode_upper(Sha256::digest(tx))
+}
+
+/// A backend storing the transactions of committed blocks.
+pub trait TxIndexer: Send + Sync {
+    /// Indexes the transactions of a committed block, in the order they were delivered.
+    fn index(&mut self, txs: Vec<TxResponse>) -> Result<(), Error>;
+
+    /// Returns the transaction with the specified (uppercase hex encoded) hash.
+    fn get(&self, hash: &str) -> Result<Option<TxResponse>, Error>;
+
+    /// Returns the transactions matching the query, in the order they were delivered.
+    fn search(&self, query: &Query) -> Result<Vec<TxResponse>, Error>;
+
+    /// Persists the indexed transactions, if the backend buffers writes.
+    fn flush(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Indexer that doesn't index anything, for nodes that don't serve tx queries.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullTxIndexer;
+
+impl TxIndexer for NullTxIndexer {
+    fn index(&mut self, _txs: Vec<TxResponse>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get(&self, _hash: &str) -> Result<Option<TxResponse>, Error> {
+        Ok(None)
+    }
+
+    fn search(&self, _query: &Query) -> Result<Vec<TxResponse>, Error> {
+        Ok(vec![])
+    }
+}
+
+/// Records the transactions as they are delivered and hands them over to the configured
+/// indexer once the block that includes them is committed.
+pub struct TxIndex {
+    indexer: Box<dyn TxIndexer>,
+    pending: Vec<TxResponse>,
+    height: u64,
+    /// RFC 3339 time of the current block
+    timestamp: String,
+}
+
+impl TxIndex {
+    pub fn new(indexer: impl TxIndexer + 'static) -> Self {
+        Self {
+            indexer: Box::new(indexer),
+            pending: vec![],
+            height: 0,
+            timestamp: String::new(),
+        }
+    }
+
+    /// Creates the index with the indexer selected by the config.
+    pub fn from_config(config: &IndexerConfig) -> Result<Self, Error> {
+        Ok(match config.indexer {
+            IndexerKind::Null => Self::new(NullTxIndexer),
+            IndexerKind::Memory => Self::new(MemTxIndexer::default()),
+            IndexerKind::Kv => Self::new(KvTxIndexer::open(&config.db_path)?),
+        })
+    }
+
+    /// Starts recording the transactions of a new block.
+    pub fn begin_block(&mut self, height: u64, timestamp: String) {
+        self.pending.clear();
+        self.height = height;
+        self.timestamp = timestamp;
+    }
+
+    /// Records a transaction delivered in the current block.
+    pub fn record(&mut self, tx: Vec<u8>, result: ResponseDeliverTx) {
+        self.pending.push(TxResponse {
+            height: self.height as i64,
+            txhash: tx_hash(&tx),
+            codespace: result.codespace,
+            code: result.code,
+            data: hex::encode_upper(&result.data),
+            raw_log: result.log,
+            logs: vec![],
+            info: result.info,
+            gas_wanted: result.gas_wanted,
+            gas_used: result.gas_used,
+            tx: Some(Any {
+                type_url: TX_TYPE_URL.to_string(),
+                value: tx,
+            }),
+            timestamp: self.timestamp.clone(),
+            events: result.events,
+        });
+    }
+
+    /// Indexes the transactions of the current block, making them queryable.
+    pub fn commit(&mut self) -> Result<(), Error> {
+        let txs = std::mem::take(&mut self.pending);
+        self.indexer.index(txs)
+    }
+
+    /// Persists the transactions indexed so far.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.indexer.flush()
+    }
+
+    /// Returns the transaction with the specified (hex encoded) hash.
+    pub fn get(&self, hash: &str) -> Result<Option<TxResponse>, Error> {
+        self.indexer.get(&hash.to_ascii_uppercase())
+    }
+
+    /// Returns the requested page (starting from 1) of the transactions matching the query.
+    pub fn search(
+        &self,
+        query: &Query,
+        order_by: OrderBy,
+        page: u64,
+        limit: u64,
+    ) -> Result<SearchTxsResult, Error> {
+        let mut txs = self.indexer.search(query)?;
+        if order_by == OrderBy::Desc {
+            txs.reverse();
+        }
+
+        let total_count = txs.len() as u64;
+        let page_total = total_count.div_ceil(limit).max(1);
+        if page == 0 || page > page_total {
+            return Err(Error::InvalidQuery {
+                reason: format!("page should be within [1, {page_total}] range, given {page}"),
+            });
+        }
+
+        let txs: Vec<_> = txs
+            .into_iter()
+            .skip(((page - 1) * limit) as usize)
+            .take(limit as usize)
+            .collect();
+        Ok(SearchTxsResult {
+            total_count,
+            count: txs.len() as u64,
+            page_number: page,
+            page_total,
+            limit,
+            txs,
+        })
+    }
+}
+
+impl Default for TxIndex {
+    fn default() -> Self {
+        Self::new(MemTxIndexer::default())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex")
+            .field("pending", &self.pending.len())
+            .field("height", &self.height)
+            .finish()
+    }
+}
@@ -0,0 +1,434 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use ibc_proto::cosmos::base::abci::v1beta1::TxResponse;
+
+use crate::error::Error;
+
+/// Reserved key matching the height of the block that included a transaction.
+pub const TX_HEIGHT_KEY: &str = "tx.height";
+/// Reserved key matching the (hex encoded) hash of a transaction.
+pub const TX_HASH_KEY: &str = "tx.hash";
+/// Reserved key matching the kind of event; always `Tx` for indexed transactions.
+pub const TM_EVENT_KEY: &str = "tm.event";
+
+/// A query over indexed transactions, in the CometBFT query language, i.e. conditions joined
+/// by `AND`, e.g. `transfer.recipient='cosmos1...' AND tx.height>100`.
+///
+/// A condition matches a transaction if any of its event attributes marked `index: true`
+/// (identified by the `{eventType}.{attributeKey}` composite key) matches it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    conditions: Vec<Condition>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    pub key: String,
+    pub op: Operator,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operator {
+    Eq(Operand),
+    Lt(f64),
+    Le(f64),
+    Gt(f64),
+    Ge(f64),
+    Contains(String),
+    Exists,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    String(String),
+    Number(f64),
+}
+
+impl Query {
+    /// Builds a query out of conditions that must all hold, e.g. the `events` of a
+    /// `GetTxsEventRequest`.
+    pub fn from_conditions<T: AsRef<str>>(conditions: &[T]) -> Result<Self, Error> {
+        conditions
+            .iter()
+            .map(|condition| condition.as_ref())
+            .collect::<Vec<_>>()
+            .join(" AND ")
+            .parse()
+    }
+
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// Returns the height range `[min, max]` the matching transactions must be within, as
+    /// constrained by the `tx.height` conditions.
+    pub fn height_range(&self) -> (u64, u64) {
+        self.conditions
+            .iter()
+            .filter(|condition| condition.key == TX_HEIGHT_KEY)
+            .fold((0, u64::MAX), |(min, max), condition| match condition.op {
+                Operator::Eq(Operand::Number(n)) => (min.max(n.ceil() as u64), max.min(n as u64)),
+                Operator::Lt(n) => (min, max.min((n.ceil() as u64).saturating_sub(1))),
+                Operator::Le(n) => (min, max.min(n as u64)),
+                Operator::Gt(n) => (min.max(n.floor() as u64 + 1), max),
+                Operator::Ge(n) => (min.max(n.ceil() as u64), max),
+                _ => (min, max),
+            })
+    }
+
+    pub fn matches(&self, tx: &TxResponse) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(tx))
+    }
+}
+
+impl Condition {
+    pub fn matches(&self, tx: &TxResponse) -> bool {
+        match self.key.as_str() {
+            TX_HEIGHT_KEY => self.op.matches(&tx.height.to_string()),
+            TX_HASH_KEY => match &self.op {
+                Operator::Eq(Operand::String(hash)) => hash.eq_ignore_ascii_case(&tx.txhash),
+                op => op.matches(&tx.txhash),
+            },
+            TM_EVENT_KEY => self.op.matches("Tx"),
+            key => tx
+                .events
+                .iter()
+                .flat_map(|event| {
+                    event
+                        .attributes
+                        .iter()
+                        .filter(|attr| attr.index)
+                        .map(move |attr| (&event.r#type, attr))
+                })
+                .any(|(kind, attr)| {
+                    key.strip_prefix(kind.as_str())
+                        .and_then(|key| key.strip_prefix('.'))
+                        == Some(attr.key.as_str())
+                        && self.op.matches(&attr.value)
+                }),
+        }
+    }
+}
+
+impl Operator {
+    /// Returns `true` if the specified attribute value satisfies the operator.
+    pub fn matches(&self, value: &str) -> bool {
+        let number = || value.parse::<f64>().ok();
+        match self {
+            Operator::Eq(Operand::String(s)) => value == s,
+            Operator::Eq(Operand::Number(n)) => number() == Some(*n),
+            Operator::Lt(n) => number().is_some_and(|v| v < *n),
+            Operator::Le(n) => number().is_some_and(|v| v <= *n),
+            Operator::Gt(n) => number().is_some_and(|v| v > *n),
+            Operator::Ge(n) => number().is_some_and(|v| v >= *n),
+            Operator::Contains(s) => value.contains(s.as_str()),
+            Operator::Exists => true,
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { query, rest: query };
+        let mut conditions = vec![parser.condition()?];
+        while !parser.at_end() {
+            parser.keyword("AND")?;
+            conditions.push(parser.condition()?);
+        }
+        Ok(Self { conditions })
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, condition) in self.conditions.iter().enumerate() {
+            if i > 0 {
+                write!(f, " AND ")?;
+            }
+            write!(f, "{condition}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.op {
+            Operator::Eq(Operand::String(s)) => write!(f, "{} = '{s}'", self.key),
+            Operator::Eq(Operand::Number(n)) => write!(f, "{} = {n}", self.key),
+            Operator::Lt(n) => write!(f, "{} < {n}", self.key),
+            Operator::Le(n) => write!(f, "{} <= {n}", self.key),
+            Operator::Gt(n) => write!(f, "{} > {n}", self.key),
+            Operator::Ge(n) => write!(f, "{} >= {n}", self.key),
+            Operator::Contains(s) => write!(f, "{} CONTAINS '{s}'", self.key),
+            Operator::Exists => write!(f, "{} EXISTS", self.key),
+        }
+    }
+}
+
+struct Parser<'a> {
+    query: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn at_end(&mut self) -> bool {
+        self.rest = self.rest.trim_start();
+        self.rest.is_empty()
+    }
+
+    fn error(&self, expected: &str) -> Error {
+        let position = self.query.len() - self.rest.len();
+        Error::InvalidQuery {
+            reason: format!(
+                "{}: expected {expected} at position {position}",
+                self.query.trim()
+            ),
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition, Error> {
+        let key = self.key()?;
+        let op = self.operator()?;
+        Ok(Condition { key, op })
+    }
+
+    fn key(&mut self) -> Result<String, Error> {
+        self.at_end();
+        let len = self
+            .rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '<' | '>' | '\''))
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.error("an event key"));
+        }
+        let (key, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(key.to_string())
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), Error> {
+        self.at_end();
+        let rest = self
+            .rest
+            .get(..keyword.len())
+            .filter(|word| word.eq_ignore_ascii_case(keyword))
+            .map(|_| &self.rest[keyword.len()..])
+            .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+            .ok_or_else(|| self.error(keyword))?;
+        self.rest = rest;
+        Ok(())
+    }
+
+    fn operator(&mut self) -> Result<Operator, Error> {
+        self.at_end();
+        for (symbol, op) in [
+            ("<=", "<="),
+            (">=", ">="),
+            ("=", "="),
+            ("<", "<"),
+            (">", ">"),
+        ] {
+            if let Some(rest) = self.rest.strip_prefix(symbol) {
+                self.rest = rest;
+                let operand = self.operand()?;
+                return match (op, operand) {
+                    ("=", operand) => Ok(Operator::Eq(operand)),
+                    ("<", Operand::Number(n)) => Ok(Operator::Lt(n)),
+                    ("<=", Operand::Number(n)) => Ok(Operator::Le(n)),
+                    (">", Operand::Number(n)) => Ok(Operator::Gt(n)),
+                    (">=", Operand::Number(n)) => Ok(Operator::Ge(n)),
+                    _ => Err(self.error(&format!("a numeric operand for `{op}`"))),
+                };
+            }
+        }
+        if self.keyword("CONTAINS").is_ok() {
+            return match self.operand()? {
+                Operand::String(s) => Ok(Operator::Contains(s)),
+                Operand::Number(_) => Err(self.error("a string operand for `CONTAINS`")),
+            };
+        }
+        self.keyword("EXISTS")
+            .map(|_| Operator::Exists)
+            .map_err(|_| self.error("one of `=`, `<`, `<=`, `>`, `>=`, `CONTAINS`, `EXISTS`"))
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error> {
+        self.at_end();
+        if let Some(rest) = self.rest.strip_prefix('\'') {
+            let end = rest
+                .find('\'')
+                .ok_or_else(|| self.error("a closing quote"))?;
+            self.rest = &rest[end + 1..];
+            return Ok(Operand::String(rest[..end].to_string()));
+        }
+        let len = self
+            .rest
+            .find(char::is_whitespace)
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.error("a quoted string or a number"));
+        }
+        let (operand, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        // unquoted operands that aren't numbers are leniently accepted as strings, as in
+        // `message.sender=cosmos1...`
+        Ok(match operand.parse::<f64>() {
+            Ok(number) if number.is_finite() => Operand::Number(number),
+            _ => Operand::String(operand.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_proto::abci::{Event, EventAttribute};
+
+    const RECIPIENT: &str = "cosmos1t2e0nyjhwn3revunvf2uperhftvhzu4euuzva9";
+
+    fn condition(key: &str, op: Operator) -> Condition {
+        Condition {
+            key: key.to_owned(),
+            op,
+        }
+    }
+
+    fn parse_error(query: &str) -> String {
+        query.parse::<Query>().unwrap_err().to_string()
+    }
+
+    fn tx(height: i64, events: Vec<(&str, &str, &str, bool)>) -> TxResponse {
+        TxResponse {
+            height,
+            txhash: "A1B2C3".to_owned(),
+            events: events
+                .into_iter()
+                .map(|(kind, key, value, index)| Event {
+                    r#type: kind.to_owned(),
+                    attributes: vec![EventAttribute {
+                        key: key.to_owned(),
+                        value: value.to_owned(),
+                        index,
+                    }],
+                })
+                .collect(),
+            ..TxResponse::default()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let query: Query = format!("transfer.recipient='{RECIPIENT}' AND tx.height>100")
+            .parse()
+            .unwrap();
+        assert_eq!(
+            query.conditions(),
+            [
+                condition(
+                    "transfer.recipient",
+                    Operator::Eq(Operand::String(RECIPIENT.to_owned()))
+                ),
+                condition(TX_HEIGHT_KEY, Operator::Gt(100.0)),
+            ]
+        );
+
+        let query: Query = "a.b < 1 and a.b<=2.5 AND a.b >3 AND a.b>= 4 AND a.b = 5 AND \
+                            a.b CONTAINS 'x y' AND a.b EXISTS AND message.sender=cosmos1abc"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            query.conditions(),
+            [
+                condition("a.b", Operator::Lt(1.0)),
+                condition("a.b", Operator::Le(2.5)),
+                condition("a.b", Operator::Gt(3.0)),
+                condition("a.b", Operator::Ge(4.0)),
+                condition("a.b", Operator::Eq(Operand::Number(5.0))),
+                condition("a.b", Operator::Contains("x y".to_owned())),
+                condition("a.b", Operator::Exists),
+                condition(
+                    "message.sender",
+                    Operator::Eq(Operand::String("cosmos1abc".to_owned()))
+                ),
+            ]
+        );
+
+        // displayed queries parse back to the same query
+        assert_eq!(query.to_string().parse::<Query>().unwrap(), query);
+        assert_eq!(
+            Query::from_conditions(&["tx.height=5", "tm.event='Tx'"])
+                .unwrap()
+                .to_string(),
+            "tx.height = 5 AND tm.event = 'Tx'"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_error("").contains("expected an event key at position 0"));
+        assert!(parse_error("tx.height").contains("expected one of `=`"));
+        assert!(parse_error("tx.height >").contains("expected a quoted string or a number"));
+        assert!(parse_error("a.b = 'x").contains("expected a closing quote"));
+        assert!(parse_error("a.b > 'x'").contains("expected a numeric operand for `>`"));
+        assert!(parse_error("a.b CONTAINS 1").contains("expected a string operand"));
+        assert!(parse_error("a.b = 1 a.c = 2").contains("expected AND at position 8"));
+        assert!(parse_error("a.b = 1 ANDa.c = 2").contains("expected AND"));
+        assert!(parse_error("a.b = 1 AND").contains("expected an event key"));
+    }
+
+    #[test]
+    fn test_height_range() {
+        let range = |query: &str| query.parse::<Query>().unwrap().height_range();
+
+        assert_eq!(range("a.b = 1"), (0, u64::MAX));
+        assert_eq!(range("tx.height > 100"), (101, u64::MAX));
+        assert_eq!(range("tx.height >= 100 AND tx.height < 200"), (100, 199));
+        assert_eq!(range("tx.height > 1.5 AND tx.height <= 7"), (2, 7));
+        assert_eq!(range("tx.height = 5"), (5, 5));
+        // contradicting conditions result in an empty range
+        let (min, max) = range("tx.height > 10 AND tx.height < 5");
+        assert!(min > max);
+    }
+
+    #[test]
+    fn test_matches() {
+        let tx = tx(
+            150,
+            vec![
+                ("transfer", "recipient", RECIPIENT, true),
+                ("transfer", "amount", "10stake", true),
+                ("message", "sender", "cosmos1abc", false),
+            ],
+        );
+        let matches = |query: &str| query.parse::<Query>().unwrap().matches(&tx);
+
+        assert!(matches(&format!(
+            "transfer.recipient='{RECIPIENT}' AND tx.height>100"
+        )));
+        assert!(!matches(&format!(
+            "transfer.recipient='{RECIPIENT}' AND tx.height>150"
+        )));
+        assert!(!matches("transfer.recipient='cosmos1other'"));
+        assert!(matches("transfer.amount CONTAINS 'stake'"));
+        assert!(matches("transfer.recipient EXISTS"));
+        assert!(!matches("transfer.sender EXISTS"));
+        // the key must match both the event type and the attribute key
+        assert!(!matches("transfers.recipient EXISTS"));
+        assert!(!matches("transfer.recipients EXISTS"));
+        // only indexed attributes are matched
+        assert!(!matches("message.sender='cosmos1abc'"));
+
+        assert!(matches(
+            "tx.height = 150 AND tx.height <= 150 AND tx.height >= 150"
+        ));
+        assert!(matches("tx.hash = 'a1b2c3'"));
+        assert!(matches("tm.event = 'Tx'"));
+        assert!(!matches("tm.event = 'NewBlock'"));
+    }
+}
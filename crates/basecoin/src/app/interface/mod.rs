@@ -5,3 +5,6 @@ pub mod tendermint;
 
 #[cfg(feature = "tower-abci")]
 pub mod tower_abci;
+
+#[cfg(feature = "v0_38")]
+pub mod v0_38;
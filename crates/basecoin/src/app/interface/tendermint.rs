@@ -1,24 +1,30 @@
 use prost::Message;
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::convert::TryInto;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
-use crate::error::Error;
+use crate::error::{grpc_status_registered_error, is_not_handled, Error};
 use cosmrs::tx::SignerInfo;
 use cosmrs::tx::SignerPublicKey;
-use cosmrs::Tx;
+use cosmrs::{AccountId, Tx};
 
+use ibc_proto::cosmos::base::abci::v1beta1::{GasInfo, Result as AbciResult, TxMsgData};
 use ibc_proto::google::protobuf::Any;
 
 use tendermint_abci::Application;
+use tendermint_proto::abci::Event;
 use tendermint_proto::abci::RequestBeginBlock;
+use tendermint_proto::abci::RequestCheckTx;
 use tendermint_proto::abci::RequestDeliverTx;
+use tendermint_proto::abci::RequestEndBlock;
 use tendermint_proto::abci::RequestInfo;
 use tendermint_proto::abci::RequestInitChain;
 use tendermint_proto::abci::RequestQuery;
 use tendermint_proto::abci::ResponseBeginBlock;
+use tendermint_proto::abci::ResponseCheckTx;
 use tendermint_proto::abci::ResponseCommit;
 use tendermint_proto::abci::ResponseDeliverTx;
+use tendermint_proto::abci::ResponseEndBlock;
 use tendermint_proto::abci::ResponseInfo;
 use tendermint_proto::abci::ResponseInitChain;
 use tendermint_proto::abci::ResponseQuery;
@@ -28,9 +34,11 @@ use tendermint_proto::crypto::ProofOps;
 use crate::app::BaseCoinApp;
 use cosmos_helper::macros::ResponseFromErrorExt;
 use cosmos_helper::{Height, Path};
-use cosmos_store::{ProvableStore, Store};
-use cosmos_x_auth::account::ACCOUNT_PREFIX;
-use cosmos_x_module_api::types::IdentifiedModule;
+use cosmos_store::gas::with_gas_meter;
+use cosmos_store::{GasMeter, ProvableStore, Store};
+use cosmos_x_auth::account::signer_pub_key;
+use cosmos_x_module_api::error::AbciError;
+use cosmos_x_module_api::types::{DeliverResult, EndBlockResult, IdentifiedModule};
 
 impl<S: Default + ProvableStore + 'static> Application for BaseCoinApp<S> {
     fn info(&self, request: RequestInfo) -> ResponseInfo {
@@ -60,9 +68,16 @@ impl<S: Default + ProvableStore + 'static> Application for BaseCoinApp<S> {
                 .expect("invalid genesis state"),
         )
         .expect("genesis state isn't valid JSON");
+        if let Err(e) = self.validate_genesis(&app_state) {
+            panic!("{e}");
+        }
+
+        // each module is initialized from its own section of the app state
         let mut modules = self.modules.write().unwrap();
-        for IdentifiedModule { module, .. } in modules.iter_mut() {
-            module.init(app_state.clone());
+        for &index in &self.order.init_genesis {
+            let IdentifiedModule { name, module, .. } = &mut modules[index];
+            let genesis = app_state.get(name.as_str()).cloned().unwrap_or_default();
+            module.init(genesis);
         }
 
         info!("App initialized");
@@ -77,10 +92,34 @@ impl<S: Default + ProvableStore + 'static> Application for BaseCoinApp<S> {
     fn query(&self, request: RequestQuery) -> ResponseQuery {
         debug!("Got query request: {:?}", request);
 
+        // queries on a registered gRPC method path are answered by the module's gRPC service
+        if let Some(result) =
+            self.grpc_router
+                .query(&request.path, &request.data, request.height as u64)
+        {
+            return match result {
+                Ok(value) => ResponseQuery {
+                    code: 0,
+                    key: request.data,
+                    value: value.into(),
+                    height: self.store.read().unwrap().current_height() as i64,
+                    ..Default::default()
+                },
+                Err(status) => {
+                    let error = grpc_status_registered_error(&status);
+                    ResponseQuery::from_codespace_error(
+                        error.codespace(),
+                        error.code(),
+                        format!("query error: {}: {}", status.message(), error),
+                    )
+                }
+            };
+        }
+
         let path: Option<Path> = request.path.try_into().ok();
         let modules = self.modules.read().unwrap();
         let height = Height::from(request.height as u64);
-        for IdentifiedModule { id, module } in modules.iter() {
+        for IdentifiedModule { id, module, .. } in modules.iter() {
             match module.query(&request.data, path.as_ref(), height, request.prove) {
                 // success - implies query was handled by this module, so return response
                 Ok(result) => {
@@ -116,91 +155,50 @@ impl<S: Default + ProvableStore + 'static> Application for BaseCoinApp<S> {
                 }
                 // `Error::NotHandled` - implies query isn't known or was intercepted but not
                 // responded to by this module, so try with next module
-                // todo(davirain)
-                Err(e) if e.to_string() == anyhow::anyhow!(Error::NotHandled).to_string() => {
-                    continue
-                }
+                Err(e) if is_not_handled(&e) => continue,
                 // Other error - return immediately
-                Err(e) => return ResponseQuery::from_error(1, format!("query error: {e:?}")),
+                Err(e) => return error_response(&Error::Module(e), "query error"),
             }
         }
-        ResponseQuery::from_error(1, "query msg not handled")
+        error_response(&Error::NotHandled, "unknown query path")
     }
 
-    fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
-        debug!("Got deliverTx request: {request:?}");
-
-        let tx: Tx = match request.tx.as_ref().try_into() {
-            Ok(tx) => tx,
-            Err(err) => {
-                return ResponseDeliverTx::from_error(
-                    1,
-                    format!("failed to decode incoming tx bytes: {err}"),
-                );
-            }
-        };
+    fn check_tx(&self, request: RequestCheckTx) -> ResponseCheckTx {
+        debug!("Got checkTx request: {request:?}");
 
-        // Extract `AccountId` of first signer
-        let signer = {
-            let pubkey = match tx.auth_info.signer_infos.first() {
-                Some(&SignerInfo {
-                    public_key: Some(SignerPublicKey::Single(pubkey)),
-                    ..
-                }) => pubkey,
-                _ => return ResponseDeliverTx::from_error(2, "Empty signers"),
-            };
-            if let Ok(signer) = pubkey.account_id(ACCOUNT_PREFIX) {
-                signer
-            } else {
-                return ResponseDeliverTx::from_error(2, "Invalid signer");
+        // only stateless checks are performed, since the module stores are shared with
+        // `DeliverTx` and there's no separate check state to run the ante handlers against
+        let result = decode_tx(request.tx.as_ref()).and_then(|(tx, _)| {
+            for message in &tx.body.messages {
+                self.msg_router.route(&message.type_url)?;
             }
-        };
+            Ok(tx)
+        });
 
-        if tx.body.messages.is_empty() {
-            return ResponseDeliverTx::from_error(2, "Empty Tx");
+        match result {
+            Ok(tx) => ResponseCheckTx {
+                gas_wanted: tx.auth_info.fee.gas_limit as i64,
+                ..ResponseCheckTx::default()
+            },
+            Err(e) => error_response(&e, "check failed with error"),
         }
+    }
 
-        let mut events = vec![];
-        for message in tx.body.messages {
-            let message = Any {
-                type_url: message.type_url,
-                value: message.value,
-            };
-
-            // try to deliver message to every module
-            match self.deliver_msg(message, &signer) {
-                // success - append events and continue with next message
-                Ok(mut msg_events) => {
-                    events.append(&mut msg_events);
-                }
-                // return on first error -
-                // either an error that occurred during execution of this message OR no module
-                // could handle this message
-                Err(e) => {
-                    // reset changes from other messages in this tx
-                    let mut modules = self.modules.write().unwrap();
-                    for IdentifiedModule { module, .. } in modules.iter_mut() {
-                        module.store_mut().reset();
-                    }
-                    self.store.write().unwrap().reset();
-                    return ResponseDeliverTx::from_error(
-                        2,
-                        format!("deliver failed with error: {e}"),
-                    );
-                }
-            }
-        }
+    fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
+        debug!("Got deliverTx request: {request:?}");
 
-        ResponseDeliverTx {
-            log: "success".to_owned(),
-            events,
-            ..ResponseDeliverTx::default()
-        }
+        let response = self.execute_tx(request.tx.as_ref());
+        self.tx_index
+            .write()
+            .unwrap()
+            .record(request.tx.to_vec(), response.clone());
+        response
     }
 
     fn commit(&self) -> ResponseCommit {
+        let _exec = self.exec_lock.lock().unwrap();
         let mut modules = self.modules.write().unwrap();
-        for IdentifiedModule { id, module } in modules.iter_mut() {
+        for IdentifiedModule { id, module, .. } in modules.iter_mut() {
             module
                 .store_mut()
                 .commit()
@@ -213,11 +211,21 @@ impl<S: Default + ProvableStore + 'static> Application for BaseCoinApp<S> {
 
         let mut state = self.store.write().unwrap();
         let data = state.commit().expect("failed to commit to state");
+        if let Err(e) = self.tx_index.write().unwrap().commit() {
+            error!("failed to index the transactions of the block: {e}");
+        }
         info!(
             "Committed height {} with hash({})",
             state.current_height() - 1,
             data.iter().map(|b| format!("{b:02X}")).collect::<String>()
         );
+        drop(state);
+
+        // the node halts right after committing the block at the halt height (or time), so that
+        // it can be restarted from there, e.g. with a new binary
+        if let Some((height, time)) = self.block.lock().unwrap().take() {
+            self.check_halt(height, time);
+        }
         ResponseCommit {
             data: data.into(),
             retain_height: 0,
@@ -227,13 +235,491 @@ impl<S: Default + ProvableStore + 'static> Application for BaseCoinApp<S> {
     fn begin_block(&self, request: RequestBeginBlock) -> ResponseBeginBlock {
         debug!("Got begin block request.");
 
+        let header: tendermint::block::Header = request.header.unwrap().try_into().unwrap();
+        self.ensure_running(header.height.value());
+        let _exec = self.exec_lock.lock().unwrap();
         let mut modules = self.modules.write().unwrap();
         let mut events = vec![];
-        let header = request.header.unwrap().try_into().unwrap();
-        for IdentifiedModule { module, .. } in modules.iter_mut() {
-            events.extend(module.begin_block(&header));
+        self.tx_index
+            .write()
+            .unwrap()
+            .begin_block(header.height.value(), header.time.to_rfc3339());
+        *self.block.lock().unwrap() = Some((header.height.value(), header.time.unix_timestamp()));
+        let last_commit_info = request.last_commit_info.unwrap_or_default();
+        for &index in &self.order.begin_blockers {
+            events.extend(
+                modules[index]
+                    .module
+                    .begin_block(&header, &last_commit_info),
+            );
         }
+        drop(modules);
+        self.apply_changes();
 
         ResponseBeginBlock { events }
     }
+
+    fn end_block(&self, request: RequestEndBlock) -> ResponseEndBlock {
+        debug!("Got end block request.");
+
+        let _exec = self.exec_lock.lock().unwrap();
+        let mut modules = self.modules.write().unwrap();
+        let mut response = ResponseEndBlock::default();
+        for &index in &self.order.end_blockers {
+            let IdentifiedModule { id, module, .. } = &mut modules[index];
+            let EndBlockResult {
+                events,
+                validator_updates,
+                consensus_param_updates,
+            } = module.end_block(request.height as u64);
+
+            response.events.extend(events);
+
+            // only a single module may update the validator set (or the consensus params) in a
+            // block, the Go module manager panics otherwise as well
+            if !validator_updates.is_empty() {
+                assert!(
+                    response.validator_updates.is_empty(),
+                    "validator end block updates already set by a previous module (current module: {id})"
+                );
+                response.validator_updates = validator_updates;
+            }
+            if consensus_param_updates.is_some() {
+                assert!(
+                    response.consensus_param_updates.is_none(),
+                    "consensus param updates already set by a previous module (current module: {id})"
+                );
+                response.consensus_param_updates = consensus_param_updates;
+            }
+        }
+        drop(modules);
+        self.apply_changes();
+
+        response
+    }
+}
+
+impl<S: Default + ProvableStore + 'static> BaseCoinApp<S> {
+    /// Runs the ante handlers and delivers the messages of a transaction, reverting all state
+    /// changes if any of them fails.
+    fn execute_tx(&self, tx_bytes: &[u8]) -> ResponseDeliverTx {
+        let (tx, signer) = match decode_tx(tx_bytes) {
+            Ok(decoded) => decoded,
+            Err(e) => return error_response(&e, "deliver failed with error"),
+        };
+
+        let _exec = self.exec_lock.lock().unwrap();
+        let (result, gas_meter) =
+            with_gas_meter(GasMeter::infinite(), || self.run_tx(&tx, &signer, false));
+        let gas_wanted = tx.auth_info.fee.gas_limit as i64;
+        let gas_used = gas_meter.consumed() as i64;
+
+        match result {
+            Ok((mut events, msg_results)) => {
+                self.apply_changes();
+                let data = tx_msg_data(msg_responses(&msg_results));
+                events.extend(msg_results.into_iter().flat_map(|result| result.events));
+                ResponseDeliverTx {
+                    data: data.into(),
+                    log: "success".to_owned(),
+                    events,
+                    gas_wanted,
+                    gas_used,
+                    ..ResponseDeliverTx::default()
+                }
+            }
+            Err(e) => {
+                // reset changes from other messages in this tx
+                self.reset_changes();
+                ResponseDeliverTx {
+                    gas_wanted,
+                    gas_used,
+                    ..error_response::<ResponseDeliverTx>(&e, "deliver failed with error")
+                }
+            }
+        }
+    }
+
+    /// Runs a transaction like `execute_tx()` does, but with an infinite gas meter and discarding
+    /// all state changes afterwards.
+    /// Returns the gas info along with the result of the transaction or the error it failed with.
+    #[allow(deprecated)] // `data` field of `Result`
+    pub(crate) fn simulate_tx(
+        &self,
+        tx_bytes: &[u8],
+    ) -> Result<(GasInfo, AbciResult), (Error, GasInfo)> {
+        let (tx, signer) = decode_tx(tx_bytes).map_err(|e| (e, GasInfo::default()))?;
+
+        let _exec = self.exec_lock.lock().unwrap();
+        let (result, gas_meter) =
+            with_gas_meter(GasMeter::infinite(), || self.run_tx(&tx, &signer, true));
+        self.reset_changes();
+
+        let gas_info = GasInfo {
+            gas_wanted: tx.auth_info.fee.gas_limit,
+            gas_used: gas_meter.consumed(),
+        };
+        let (mut events, msg_results) = result.map_err(|e| (e, gas_info.clone()))?;
+
+        let msg_responses = msg_responses(&msg_results);
+        let log = msg_results
+            .iter()
+            .enumerate()
+            .map(|(msg_index, result)| {
+                json!({
+                    "msg_index": msg_index,
+                    "events": result.events.iter().map(|event| json!({
+                        "type": event.r#type,
+                        "attributes": event.attributes.iter().map(|attr| json!({
+                            "key": attr.key,
+                            "value": attr.value,
+                        })).collect::<Vec<_>>(),
+                    })).collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+        events.extend(msg_results.into_iter().flat_map(|result| result.events));
+        let data = tx_msg_data(msg_responses.clone());
+
+        Ok((
+            gas_info,
+            AbciResult {
+                data,
+                log: Value::from(log).to_string(),
+                events,
+                msg_responses,
+            },
+        ))
+    }
+
+    /// Runs the ante handlers and then delivers every message of the transaction.
+    /// Returns the events emitted by the ante handlers and the result of each message.
+    fn run_tx(
+        &self,
+        tx: &Tx,
+        signer: &AccountId,
+        simulate: bool,
+    ) -> Result<(Vec<Event>, Vec<DeliverResult>), Error> {
+        let ante_events = self.ante_handle(tx, signer, simulate)?;
+
+        // deliver each message to the module that handles it - return on first error, i.e.
+        // either an error that occurred during execution of the message OR no module handles it
+        let msg_results = tx
+            .body
+            .messages
+            .iter()
+            .map(|message| {
+                let message = Any {
+                    type_url: message.type_url.clone(),
+                    value: message.value.clone(),
+                };
+                self.deliver_msg(message, signer)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((ante_events, msg_results))
+    }
+}
+
+/// Returns the responses of the messages of a transaction, in order.
+fn msg_responses(msg_results: &[DeliverResult]) -> Vec<Any> {
+    msg_results
+        .iter()
+        .filter_map(|result| result.msg_response.clone())
+        .collect()
+}
+
+/// Encodes the responses of the messages of a transaction into its `TxMsgData`, i.e. the `data`
+/// of its result.
+#[allow(deprecated)] // `data` field of `TxMsgData`
+fn tx_msg_data(msg_responses: Vec<Any>) -> Vec<u8> {
+    TxMsgData {
+        data: vec![],
+        msg_responses,
+    }
+    .encode_to_vec()
+}
+
+/// Builds the response for a failed request, reporting the error's registered codespace and code.
+fn error_response<R: ResponseFromErrorExt>(e: &Error, context: &str) -> R {
+    let registered = e.registered_error();
+    R::from_codespace_error(
+        registered.codespace(),
+        registered.code(),
+        format!("{context}: {e}"),
+    )
+}
+
+/// Decode a transaction and extract the `AccountId` of its first signer.
+fn decode_tx(bytes: &[u8]) -> Result<(Tx, AccountId), Error> {
+    let tx = Tx::from_bytes(bytes).map_err(|e| Error::TxDecode {
+        reason: e.to_string(),
+    })?;
+
+    let signer = match tx.auth_info.signer_infos.first() {
+        Some(SignerInfo {
+            public_key: Some(pubkey),
+            ..
+        }) => signer_account_id(pubkey)?,
+        _ => return Err(Error::NoSignatures),
+    };
+
+    if tx.body.messages.is_empty() {
+        return Err(Error::EmptyTx);
+    }
+
+    Ok((tx, signer))
+}
+
+/// Derive the `AccountId` of a signer from its public key, supports both single keys and
+/// (possibly nested) legacy amino multisig keys.
+fn signer_account_id(pubkey: &SignerPublicKey) -> Result<AccountId, Error> {
+    signer_pub_key(pubkey)
+        .map(|(_, address)| address)
+        .map_err(|e| Error::InvalidSigner {
+            reason: e.to_string(),
+        })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::app::{Builder, ModuleRegistry};
+    use cosmos_core::appconfig::{AppConfig, ModuleConfig};
+    use cosmos_helper::Identifier;
+    use cosmos_store::{InMemoryStore, SharedStore};
+    use cosmos_x_auth::account::account_prefix;
+    use cosmos_x_module_api::module::Module;
+    use cosmos_x_module_api::types::ModuleStore;
+    use cosmrs::bank::MsgSend;
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::tx::{Body, Fee, Msg};
+    use cosmrs::Coin;
+    use ibc_proto::cosmos::tx::v1beta1::TxRaw;
+    use std::sync::{Arc, Mutex};
+    use tendermint_proto::abci::ValidatorUpdate;
+    use tendermint_proto::types::{BlockParams, ConsensusParams};
+
+    /// Module whose `end_block` returns a fixed result, recording the order it's called in.
+    pub(crate) struct EndBlocker {
+        store: SharedStore<ModuleStore<InMemoryStore>>,
+        name: &'static str,
+        result: EndBlockResult,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Module for EndBlocker {
+        type Store = ModuleStore<InMemoryStore>;
+
+        fn end_block(&mut self, _height: u64) -> EndBlockResult {
+            self.calls.lock().unwrap().push(self.name);
+            self.result.clone()
+        }
+
+        fn store_mut(&mut self) -> &mut SharedStore<Self::Store> {
+            &mut self.store
+        }
+
+        fn store(&self) -> &SharedStore<Self::Store> {
+            &self.store
+        }
+    }
+
+    /// Returns a builder with an `EndBlocker` module for each of the names, along with the
+    /// names of the modules in the order their end blockers are called.
+    pub(crate) fn end_blockers(
+        results: Vec<(&'static str, EndBlockResult)>,
+    ) -> (Builder<InMemoryStore>, Arc<Mutex<Vec<&'static str>>>) {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let builder = results.into_iter().fold(
+            Builder::new(InMemoryStore::default()),
+            |builder, (name, result)| {
+                let prefix = Identifier::from(name.to_owned());
+                let module = EndBlocker {
+                    store: builder.module_store(&prefix),
+                    name,
+                    result,
+                    calls: calls.clone(),
+                };
+                builder.add_module(prefix, module)
+            },
+        );
+        (builder, calls)
+    }
+
+    pub(crate) fn event(kind: &str) -> Event {
+        Event {
+            r#type: kind.to_owned(),
+            attributes: vec![],
+        }
+    }
+
+    pub(crate) fn validator_update(power: i64) -> ValidatorUpdate {
+        ValidatorUpdate {
+            pub_key: None,
+            power,
+        }
+    }
+
+    pub(crate) fn block_params(max_bytes: i64, max_gas: i64) -> ConsensusParams {
+        ConsensusParams {
+            block: Some(BlockParams { max_bytes, max_gas }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_end_block() {
+        let (builder, calls) = end_blockers(vec![
+            (
+                "auth",
+                EndBlockResult {
+                    events: vec![event("auth")],
+                    ..Default::default()
+                },
+            ),
+            (
+                "staking",
+                EndBlockResult {
+                    events: vec![event("staking")],
+                    validator_updates: vec![validator_update(10), validator_update(0)],
+                    ..Default::default()
+                },
+            ),
+            (
+                "params",
+                EndBlockResult {
+                    consensus_param_updates: Some(block_params(1024, 100)),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let app = builder
+            .set_order_end_blockers(
+                ["params", "staking", "auth"].map(|id| Identifier::from(id.to_owned())),
+            )
+            .build();
+
+        let response = app.end_block(RequestEndBlock { height: 1 });
+        assert_eq!(*calls.lock().unwrap(), ["params", "staking", "auth"]);
+        assert_eq!(response.events, [event("staking"), event("auth")]);
+        assert_eq!(
+            response.validator_updates,
+            [validator_update(10), validator_update(0)]
+        );
+        assert_eq!(
+            response.consensus_param_updates,
+            Some(block_params(1024, 100))
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "validator end block updates already set by a previous module (current module: gov)"
+    )]
+    fn test_end_block_duplicate_validator_updates() {
+        let updates = || EndBlockResult {
+            validator_updates: vec![validator_update(1)],
+            ..Default::default()
+        };
+        let (builder, _) = end_blockers(vec![("staking", updates()), ("gov", updates())]);
+        builder.build().end_block(RequestEndBlock { height: 1 });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "consensus param updates already set by a previous module (current module: gov)"
+    )]
+    fn test_end_block_duplicate_consensus_param_updates() {
+        let updates = || EndBlockResult {
+            consensus_param_updates: Some(block_params(1, 1)),
+            ..Default::default()
+        };
+        let (builder, _) = end_blockers(vec![("params", updates()), ("gov", updates())]);
+        builder.build().end_block(RequestEndBlock { height: 1 });
+    }
+
+    /// Returns an app with the `auth` and `bank` modules, where the account has 100 basecoins.
+    pub(crate) fn bank_app(account: &AccountId) -> BaseCoinApp<InMemoryStore> {
+        let config = AppConfig {
+            modules: ["auth", "bank"].map(ModuleConfig::new).into(),
+            ..Default::default()
+        };
+        let (builder, _) = ModuleRegistry::default()
+            .build(&config, Builder::new(InMemoryStore::default()))
+            .unwrap();
+        let app = builder.build();
+        let app_state = json!({
+            "bank": {
+                "balances": [{
+                    "address": account.to_string(),
+                    "coins": [{ "denom": "basecoin", "amount": "100" }]
+                }]
+            }
+        });
+        app.init_chain(RequestInitChain {
+            app_state_bytes: app_state.to_string().into_bytes().into(),
+            ..Default::default()
+        });
+        app
+    }
+
+    #[test]
+    #[allow(deprecated)] // `data` field of `TxMsgData`
+    fn test_simulate_tx() {
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let sender = key.public_key().account_id(&account_prefix()).unwrap();
+        let recipient = SigningKey::from_slice(&[2; 32])
+            .unwrap()
+            .public_key()
+            .account_id(&account_prefix())
+            .unwrap();
+        let app = bank_app(&sender);
+
+        let msg = MsgSend {
+            from_address: sender.clone(),
+            to_address: recipient.clone(),
+            amount: vec![Coin::new(60, "basecoin").unwrap()],
+        };
+        let body = Body::new(vec![msg.to_any().unwrap()], "", 0u32);
+        let auth_info = SignerInfo::single_direct(Some(key.public_key()), 0).auth_info(Fee {
+            amount: vec![],
+            gas_limit: 200_000,
+            payer: None,
+            granter: None,
+        });
+        // simulated transactions don't need to be signed
+        let tx_bytes = TxRaw {
+            body_bytes: body.into_bytes().unwrap(),
+            auth_info_bytes: auth_info.into_bytes().unwrap(),
+            signatures: vec![vec![]],
+        }
+        .encode_to_vec();
+
+        let (gas_info, result) = app.simulate_tx(&tx_bytes).unwrap();
+        assert_eq!(gas_info.gas_wanted, 200_000);
+        assert!(gas_info.gas_used > 0);
+
+        let kinds: Vec<_> = result.events.iter().map(|e| e.r#type.as_str()).collect();
+        assert_eq!(
+            kinds,
+            ["coin_spent", "coin_received", "transfer", "message"]
+        );
+        let transfer = &result.events[2].attributes;
+        assert_eq!(transfer[0].value, recipient.to_string());
+        assert_eq!(transfer[2].value, "60basecoin");
+
+        let msg_send_response = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSendResponse".to_owned(),
+            value: vec![],
+        };
+        assert_eq!(result.msg_responses, [msg_send_response.clone()]);
+        let data = TxMsgData::decode(result.data.as_slice()).unwrap();
+        assert_eq!(data.msg_responses, [msg_send_response]);
+
+        // the simulation's changes are discarded, otherwise the sender couldn't afford to send
+        // the coins again
+        let (_, again) = app.simulate_tx(&tx_bytes).unwrap();
+        assert_eq!(again.events, result.events);
+    }
 }
@@ -0,0 +1,727 @@
+//! ABCI 2.0 (CometBFT v0.38) interface.
+//!
+//! `FinalizeBlock` is executed in terms of the existing `BeginBlock`, `DeliverTx` and `EndBlock`
+//! implementations, messages that are identical across ABCI versions are transcoded between the
+//! v0.37 and v0.38 protobuf definitions.
+
+use std::convert::TryInto;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use cosmrs::Tx;
+use prost::Message;
+use tendermint_abci::Application;
+use tendermint_proto::abci::{
+    CommitInfo, RequestBeginBlock, RequestDeliverTx, RequestEndBlock, ResponseEndBlock, VoteInfo,
+};
+use tendermint_proto::google::protobuf::Timestamp;
+use tendermint_proto::types::Header as RawHeader;
+use tendermint_proto::version::Consensus;
+use tendermint_proto_v038::v0_38::abci as pb;
+use tendermint_proto_v038::v0_38::abci::response_process_proposal::ProposalStatus;
+use tendermint_proto_v038::v0_38::abci::response_verify_vote_extension::VerifyStatus;
+use tendermint_proto_v038::v0_38::types::{BlockIdFlag, ConsensusParams};
+use tendermint_v038::v0_38::abci::response::Response as AbciResponse;
+use tendermint_v038::v0_38::abci::Request as AbciRequest;
+use tower::Service;
+use tower_abci_v038::BoxError;
+use tracing::debug;
+
+use crate::app::BaseCoinApp;
+use cosmos_helper::{Height, Path};
+use cosmos_store::{ProvableStore, Store};
+use cosmos_x_module_api::types::IdentifiedModule;
+
+/// Block protocol version reported in the headers handed to modules.
+const BLOCK_PROTOCOL: u64 = 11;
+
+/// We have to create this type since the compiler doesn't think that
+/// `dyn Future<Output = Result<AbciResponse, BoxError>> + Send`
+/// can be sent across threads...
+pub type SendFuture = dyn Future<Output = Result<AbciResponse, BoxError>> + Send;
+
+/// Hook called on `ExtendVote`, returns the vote extension to attach to the precommit.
+pub type ExtendVoteHandler = Arc<dyn Fn(&pb::RequestExtendVote) -> Vec<u8> + Send + Sync>;
+
+/// Hook called on `VerifyVoteExtension`, returns whether the vote extension is valid.
+pub type VerifyVoteExtensionHandler =
+    Arc<dyn Fn(&pb::RequestVerifyVoteExtension) -> bool + Send + Sync>;
+
+/// Path (in the ABCI store) of the chain ID, which isn't part of the ABCI 2.0 requests.
+const CHAIN_ID_PATH: &str = "chain_id";
+
+/// Path (in the ABCI store) of the consensus params, which aren't part of the ABCI 2.0 requests.
+const CONSENSUS_PARAMS_PATH: &str = "consensus_params";
+
+/// Hooks called by the ABCI 2.0 vote extension methods.
+#[derive(Clone, Default)]
+pub struct AbciPlusState {
+    pub(crate) extend_vote: Option<ExtendVoteHandler>,
+    pub(crate) verify_vote_extension: Option<VerifyVoteExtensionHandler>,
+}
+
+fn path(path: &str) -> Path {
+    path.to_owned().try_into().unwrap() // safety - the paths are constants
+}
+
+/// Re-encode a protobuf message into its counterpart from another ABCI version.
+fn transcode<T: Message, U: Message + Default>(message: &T) -> U {
+    // safety - only used for messages that are wire-compatible across versions
+    U::decode(message.encode_to_vec().as_slice()).expect("incompatible ABCI message")
+}
+
+/// Transcode the votes on the previous block into their v0.37 counterpart, where a validator
+/// signed the block unless its vote is absent (same as CometBFT v0.37).
+fn last_commit_info(commit: &pb::CommitInfo) -> CommitInfo {
+    CommitInfo {
+        round: commit.round,
+        votes: commit
+            .votes
+            .iter()
+            .map(|vote| VoteInfo {
+                validator: vote.validator.as_ref().map(transcode),
+                signed_last_block: vote.block_id_flag != BlockIdFlag::Absent as i32,
+            })
+            .collect(),
+    }
+}
+
+/// Gas limit requested by the fee of an encoded transaction.
+fn tx_gas_limit(tx: &[u8]) -> Option<u64> {
+    Tx::from_bytes(tx).ok().map(|tx| tx.auth_info.fee.gas_limit)
+}
+
+impl<S: Default + ProvableStore + 'static> BaseCoinApp<S> {
+    /// Chain ID set at `InitChain`, which is persisted as it's needed to build the block headers
+    /// handed to the modules.
+    fn chain_id(&self) -> String {
+        self.abci_store
+            .read()
+            .unwrap()
+            .get(Height::Pending, &path(CHAIN_ID_PATH))
+            .and_then(|chain_id| String::from_utf8(chain_id).ok())
+            .unwrap_or_default()
+    }
+
+    /// Consensus params set at `InitChain` (or updated by the modules since), which are
+    /// persisted as the block limits are checked against them.
+    fn consensus_params(&self) -> Option<ConsensusParams> {
+        self.abci_store
+            .read()
+            .unwrap()
+            .get(Height::Pending, &path(CONSENSUS_PARAMS_PATH))
+            .map(|params| {
+                ConsensusParams::decode(params.as_slice()).expect("invalid consensus params")
+            })
+    }
+
+    fn set_chain_id(&self, chain_id: &str) {
+        self.abci_store
+            .write()
+            .unwrap()
+            .set(path(CHAIN_ID_PATH), chain_id.as_bytes().to_vec())
+            .expect("failed to store the chain id");
+    }
+
+    fn set_consensus_params(&self, params: &ConsensusParams) {
+        self.abci_store
+            .write()
+            .unwrap()
+            .set(path(CONSENSUS_PARAMS_PATH), params.encode_to_vec())
+            .expect("failed to store the consensus params");
+    }
+
+    /// Maximum bytes and gas of a block, `None` if unlimited.
+    fn block_limits(&self) -> (Option<u64>, Option<u64>) {
+        let block = self
+            .consensus_params()
+            .and_then(|params| params.block)
+            .unwrap_or_default();
+        // non-positive limits (i.e. `-1`) are unlimited
+        let limit = |value: i64| u64::try_from(value).ok().filter(|&value| value > 0);
+        (limit(block.max_bytes), limit(block.max_gas))
+    }
+
+    /// Select the transactions of a block proposal in the order proposed by the mempool,
+    /// skipping those that can't be decoded or would exceed the block's byte or gas limit.
+    pub fn prepare_proposal_v038(
+        &self,
+        request: pb::RequestPrepareProposal,
+    ) -> pb::ResponsePrepareProposal {
+        debug!("Got prepare proposal request.");
+
+        let max_tx_bytes = u64::try_from(request.max_tx_bytes).unwrap_or_default();
+        let (_, max_block_gas) = self.block_limits();
+        let (mut total_bytes, mut total_gas) = (0u64, 0u64);
+        let mut txs = vec![];
+        for tx in request.txs {
+            let Some(gas_limit) = tx_gas_limit(&tx) else {
+                continue;
+            };
+            let tx_bytes = tx.len() as u64;
+            if total_bytes + tx_bytes > max_tx_bytes {
+                continue;
+            }
+            if max_block_gas.is_some_and(|max| total_gas.saturating_add(gas_limit) > max) {
+                continue;
+            }
+            total_bytes += tx_bytes;
+            total_gas = total_gas.saturating_add(gas_limit);
+            txs.push(tx);
+        }
+
+        pb::ResponsePrepareProposal { txs }
+    }
+
+    /// Reject proposals that contain transactions which can't be decoded or that exceed the
+    /// block's byte or gas limit.
+    pub fn process_proposal_v038(
+        &self,
+        request: pb::RequestProcessProposal,
+    ) -> pb::ResponseProcessProposal {
+        debug!("Got process proposal request.");
+
+        let (max_block_bytes, max_block_gas) = self.block_limits();
+        let (mut total_bytes, mut total_gas) = (0u64, 0u64);
+        let mut status = ProposalStatus::Accept;
+        for tx in &request.txs {
+            match tx_gas_limit(tx) {
+                Some(gas_limit) => total_gas = total_gas.saturating_add(gas_limit),
+                None => {
+                    status = ProposalStatus::Reject;
+                    break;
+                }
+            }
+            total_bytes = total_bytes.saturating_add(tx.len() as u64);
+            if max_block_bytes.is_some_and(|max| total_bytes > max)
+                || max_block_gas.is_some_and(|max| total_gas > max)
+            {
+                status = ProposalStatus::Reject;
+                break;
+            }
+        }
+
+        pb::ResponseProcessProposal {
+            status: status as i32,
+        }
+    }
+
+    /// Execute a decided block, this runs the `BeginBlock`, `DeliverTx` and `EndBlock` logic of
+    /// the modules and returns the app hash the block results in (without committing it).
+    pub fn finalize_block(&self, request: pb::RequestFinalizeBlock) -> pb::ResponseFinalizeBlock {
+        debug!("Got finalize block request.");
+
+        let header = RawHeader {
+            version: Some(Consensus {
+                block: BLOCK_PROTOCOL,
+                app: 1,
+            }),
+            chain_id: self.chain_id(),
+            height: request.height,
+            time: request.time.as_ref().map(|time| Timestamp {
+                seconds: time.seconds,
+                nanos: time.nanos,
+            }),
+            next_validators_hash: request.next_validators_hash.to_vec(),
+            app_hash: self.store.read().unwrap().root_hash(),
+            proposer_address: request.proposer_address.to_vec(),
+            ..Default::default()
+        };
+        let mut events: Vec<pb::Event> = self
+            .begin_block(RequestBeginBlock {
+                hash: request.hash.clone(),
+                header: Some(header),
+                last_commit_info: request.decided_last_commit.as_ref().map(last_commit_info),
+                byzantine_validators: request.misbehavior.iter().map(transcode).collect(),
+            })
+            .events
+            .iter()
+            .map(transcode)
+            .collect();
+
+        let tx_results = request
+            .txs
+            .into_iter()
+            .map(|tx| transcode(&self.deliver_tx(RequestDeliverTx { tx })))
+            .collect();
+
+        let ResponseEndBlock {
+            validator_updates,
+            consensus_param_updates,
+            events: end_block_events,
+        } = self.end_block(RequestEndBlock {
+            height: request.height,
+        });
+        events.extend(end_block_events.iter().map(transcode));
+        let consensus_param_updates: Option<ConsensusParams> =
+            consensus_param_updates.as_ref().map(transcode);
+        if let Some(updates) = &consensus_param_updates {
+            // same as CometBFT - only the params that are set are updated
+            let mut params = self.consensus_params().unwrap_or_default();
+            params.block = updates.block.clone().or(params.block);
+            params.evidence = updates.evidence.clone().or(params.evidence);
+            params.validator = updates.validator.clone().or(params.validator);
+            params.version = updates.version.clone().or(params.version);
+            params.abci = updates.abci.clone().or(params.abci);
+            self.set_consensus_params(&params);
+        }
+
+        pb::ResponseFinalizeBlock {
+            events,
+            tx_results,
+            validator_updates: validator_updates.iter().map(transcode).collect(),
+            consensus_param_updates,
+            app_hash: self.pending_app_hash().into(),
+        }
+    }
+
+    /// Return the vote extension produced by the registered hook, if any.
+    pub fn extend_vote(&self, request: pb::RequestExtendVote) -> pb::ResponseExtendVote {
+        debug!("Got extend vote request.");
+
+        let vote_extension = self
+            .abci_plus
+            .extend_vote
+            .as_ref()
+            .map(|extend_vote| extend_vote(&request))
+            .unwrap_or_default();
+
+        pb::ResponseExtendVote {
+            vote_extension: vote_extension.into(),
+        }
+    }
+
+    /// Verify a vote extension using the registered hook, accepts all extensions if no hook
+    /// was registered.
+    pub fn verify_vote_extension(
+        &self,
+        request: pb::RequestVerifyVoteExtension,
+    ) -> pb::ResponseVerifyVoteExtension {
+        debug!("Got verify vote extension request.");
+
+        let accepted = self
+            .abci_plus
+            .verify_vote_extension
+            .as_ref()
+            .map_or(true, |verify| verify(&request));
+        let status = if accepted {
+            VerifyStatus::Accept
+        } else {
+            VerifyStatus::Reject
+        };
+
+        pb::ResponseVerifyVoteExtension {
+            status: status as i32,
+        }
+    }
+
+    /// App hash that committing the pending state would result in.
+    fn pending_app_hash(&self) -> Vec<u8> {
+        let modules = self.modules.read().unwrap();
+        let mut state = self.store.write().unwrap();
+        for IdentifiedModule { id, module, .. } in modules.iter() {
+            state
+                .set(id.clone().into(), module.store().root_hash())
+                .expect("failed to update sub-store commitment");
+        }
+        state.root_hash()
+    }
+}
+
+impl<S> Service<AbciRequest> for BaseCoinApp<S>
+where
+    S: Default + ProvableStore + Send + 'static,
+{
+    type Response = AbciResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<SendFuture>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: AbciRequest) -> Self::Future {
+        let response = match req {
+            AbciRequest::Echo(domain_req) => {
+                let proto_req: pb::RequestEcho = domain_req.into();
+
+                let proto_resp: pb::ResponseEcho = transcode(&self.echo(transcode(&proto_req)));
+
+                AbciResponse::Echo(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::Flush => AbciResponse::Flush,
+            AbciRequest::Info(domain_req) => {
+                let proto_req: pb::RequestInfo = domain_req.into();
+
+                let proto_resp: pb::ResponseInfo = transcode(&self.info(transcode(&proto_req)));
+
+                AbciResponse::Info(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::InitChain(domain_req) => {
+                let proto_req: pb::RequestInitChain = domain_req.into();
+                self.set_chain_id(&proto_req.chain_id);
+                if let Some(params) = &proto_req.consensus_params {
+                    self.set_consensus_params(params);
+                }
+
+                let mut proto_resp: pb::ResponseInitChain =
+                    transcode(&self.init_chain(transcode(&proto_req)));
+                // the v0.37 consensus params lack the ABCI (vote extension) params
+                proto_resp.consensus_params = proto_req.consensus_params;
+
+                AbciResponse::InitChain(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::Query(domain_req) => {
+                let proto_req: pb::RequestQuery = domain_req.into();
+
+                let proto_resp: pb::ResponseQuery = transcode(&self.query(transcode(&proto_req)));
+
+                AbciResponse::Query(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::CheckTx(domain_req) => {
+                let proto_req: pb::RequestCheckTx = domain_req.into();
+
+                let proto_resp: pb::ResponseCheckTx =
+                    transcode(&self.check_tx(transcode(&proto_req)));
+
+                AbciResponse::CheckTx(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::Commit => {
+                let proto_resp: pb::ResponseCommit = transcode(&self.commit());
+                self.abci_store
+                    .write()
+                    .unwrap()
+                    .commit()
+                    .expect("failed to commit the ABCI store");
+
+                AbciResponse::Commit(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::ListSnapshots => {
+                let proto_resp: pb::ResponseListSnapshots = transcode(&self.list_snapshots());
+
+                AbciResponse::ListSnapshots(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::OfferSnapshot(domain_req) => {
+                let proto_req: pb::RequestOfferSnapshot = domain_req.into();
+
+                let proto_resp: pb::ResponseOfferSnapshot =
+                    transcode(&self.offer_snapshot(transcode(&proto_req)));
+
+                AbciResponse::OfferSnapshot(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::LoadSnapshotChunk(domain_req) => {
+                let proto_req: pb::RequestLoadSnapshotChunk = domain_req.into();
+
+                let proto_resp: pb::ResponseLoadSnapshotChunk =
+                    transcode(&self.load_snapshot_chunk(transcode(&proto_req)));
+
+                AbciResponse::LoadSnapshotChunk(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::ApplySnapshotChunk(domain_req) => {
+                let proto_req: pb::RequestApplySnapshotChunk = domain_req.into();
+
+                let proto_resp: pb::ResponseApplySnapshotChunk =
+                    transcode(&self.apply_snapshot_chunk(transcode(&proto_req)));
+
+                AbciResponse::ApplySnapshotChunk(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::PrepareProposal(domain_req) => {
+                let proto_resp = self.prepare_proposal_v038(domain_req.into());
+
+                AbciResponse::PrepareProposal(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::ProcessProposal(domain_req) => {
+                let proto_resp = self.process_proposal_v038(domain_req.into());
+
+                AbciResponse::ProcessProposal(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::ExtendVote(domain_req) => {
+                let proto_resp = self.extend_vote(domain_req.into());
+
+                AbciResponse::ExtendVote(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::VerifyVoteExtension(domain_req) => {
+                let proto_resp = self.verify_vote_extension(domain_req.into());
+
+                AbciResponse::VerifyVoteExtension(proto_resp.try_into().unwrap())
+            }
+            AbciRequest::FinalizeBlock(domain_req) => {
+                let proto_resp = self.finalize_block(domain_req.into());
+
+                AbciResponse::FinalizeBlock(proto_resp.try_into().unwrap())
+            }
+        };
+
+        Box::pin(future::ready(Ok(response)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::interface::tendermint::tests::{
+        block_params, end_blockers, event, validator_update,
+    };
+    use crate::app::{BaseCoinApp, Builder};
+    use bytes::Bytes;
+    use cosmos_helper::Identifier;
+    use cosmos_store::{InMemoryStore, SharedStore};
+    use cosmos_x_module_api::module::Module;
+    use cosmos_x_module_api::types::{EndBlockResult, ModuleStore};
+    use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, TxBody, TxRaw};
+    use std::sync::Mutex;
+    use tendermint::block::Header;
+    use tendermint_proto::abci::Event;
+    use tendermint_proto_v038::google::protobuf::Timestamp as Timestamp038;
+    use tendermint_proto_v038::v0_38::types::{BlockParams, EvidenceParams};
+
+    /// Module that records whether each validator signed the previous block.
+    struct VoteRecorder {
+        store: SharedStore<ModuleStore<InMemoryStore>>,
+        signed: Arc<Mutex<Vec<bool>>>,
+    }
+
+    impl Module for VoteRecorder {
+        type Store = ModuleStore<InMemoryStore>;
+
+        fn begin_block(&mut self, _header: &Header, last_commit_info: &CommitInfo) -> Vec<Event> {
+            let mut signed = self.signed.lock().unwrap();
+            signed.extend(last_commit_info.votes.iter().map(|v| v.signed_last_block));
+            vec![]
+        }
+
+        fn store_mut(&mut self) -> &mut SharedStore<Self::Store> {
+            &mut self.store
+        }
+
+        fn store(&self) -> &SharedStore<Self::Store> {
+            &self.store
+        }
+    }
+
+    /// Returns an encoded transaction (of constant size for gas limits from 128 to 16383).
+    fn tx(gas_limit: u64) -> Bytes {
+        let auth_info = AuthInfo {
+            fee: Some(Fee {
+                gas_limit,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        TxRaw {
+            body_bytes: TxBody::default().encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![],
+        }
+        .encode_to_vec()
+        .into()
+    }
+
+    fn undecodable_tx() -> Bytes {
+        Bytes::from_static(b"garbage")
+    }
+
+    fn app_with_limits(max_bytes: i64, max_gas: i64) -> BaseCoinApp<InMemoryStore> {
+        let app = Builder::new(InMemoryStore::default()).build();
+        app.set_consensus_params(&ConsensusParams {
+            block: Some(BlockParams { max_bytes, max_gas }),
+            ..Default::default()
+        });
+        app
+    }
+
+    fn prepare(app: &BaseCoinApp<InMemoryStore>, max_tx_bytes: usize, txs: &[Bytes]) -> Vec<Bytes> {
+        app.prepare_proposal_v038(pb::RequestPrepareProposal {
+            max_tx_bytes: max_tx_bytes as i64,
+            txs: txs.to_vec(),
+            ..Default::default()
+        })
+        .txs
+    }
+
+    fn process(app: &BaseCoinApp<InMemoryStore>, txs: &[Bytes]) -> i32 {
+        app.process_proposal_v038(pb::RequestProcessProposal {
+            txs: txs.to_vec(),
+            ..Default::default()
+        })
+        .status
+    }
+
+    #[test]
+    fn test_prepare_proposal_gas_limit() {
+        let app = app_with_limits(-1, 3500);
+        let txs = [tx(1000), undecodable_tx(), tx(3000), tx(2000)];
+        assert_eq!(prepare(&app, 1 << 20, &txs), [tx(1000), tx(2000)]);
+    }
+
+    #[test]
+    fn test_prepare_proposal_byte_limit() {
+        // without consensus params, the block gas is unlimited
+        let app = Builder::new(InMemoryStore::default()).build();
+        let txs = [tx(1000), tx(2000), tx(3000)];
+        let max_tx_bytes = 2 * tx(1000).len();
+        assert_eq!(prepare(&app, max_tx_bytes, &txs), [tx(1000), tx(2000)]);
+        assert_eq!(prepare(&app, max_tx_bytes - 1, &txs), [tx(1000)]);
+    }
+
+    #[test]
+    fn test_process_proposal() {
+        let max_bytes = 2 * tx(1000).len() as i64;
+        let app = app_with_limits(max_bytes, 5000);
+        assert_eq!(process(&app, &[]), ProposalStatus::Accept as i32);
+        assert_eq!(
+            process(&app, &[tx(1000), tx(2000)]),
+            ProposalStatus::Accept as i32
+        );
+        assert_eq!(
+            process(&app, &[tx(1000), tx(2000), tx(1000)]),
+            ProposalStatus::Reject as i32
+        );
+        assert_eq!(
+            process(&app, &[tx(1000), undecodable_tx()]),
+            ProposalStatus::Reject as i32
+        );
+
+        let app = app_with_limits(-1, 2500);
+        assert_eq!(
+            process(&app, &[tx(1000), tx(2000)]),
+            ProposalStatus::Reject as i32
+        );
+        assert_eq!(process(&app, &[tx(2500)]), ProposalStatus::Accept as i32);
+    }
+
+    fn vote(address: u8, block_id_flag: BlockIdFlag) -> pb::VoteInfo {
+        pb::VoteInfo {
+            validator: Some(pb::Validator {
+                address: vec![address; 20].into(),
+                power: 10,
+            }),
+            block_id_flag: block_id_flag as i32,
+        }
+    }
+
+    #[test]
+    fn test_finalize_block() {
+        let (builder, _) = end_blockers(vec![(
+            "staking",
+            EndBlockResult {
+                events: vec![event("staking")],
+                validator_updates: vec![validator_update(10)],
+                consensus_param_updates: Some(block_params(2048, 500)),
+            },
+        )]);
+        let signed = Arc::new(Mutex::new(vec![]));
+        let prefix = Identifier::from("votes".to_owned());
+        let recorder = VoteRecorder {
+            store: builder.module_store(&prefix),
+            signed: signed.clone(),
+        };
+        let app = builder.add_module(prefix, recorder).build();
+        app.set_chain_id("test-chain");
+        app.set_consensus_params(&ConsensusParams {
+            block: Some(BlockParams {
+                max_bytes: 1024,
+                max_gas: 100,
+            }),
+            evidence: Some(EvidenceParams {
+                max_age_num_blocks: 100,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let response = app.finalize_block(pb::RequestFinalizeBlock {
+            txs: vec![undecodable_tx()],
+            decided_last_commit: Some(pb::CommitInfo {
+                round: 0,
+                votes: vec![
+                    vote(1, BlockIdFlag::Commit),
+                    vote(2, BlockIdFlag::Absent),
+                    vote(3, BlockIdFlag::Nil),
+                ],
+            }),
+            height: 1,
+            time: Some(Timestamp038 {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            proposer_address: vec![1; 20].into(),
+            ..Default::default()
+        });
+
+        assert_eq!(*signed.lock().unwrap(), [true, false, true]);
+        assert_eq!(response.tx_results.len(), 1);
+        assert_ne!(response.tx_results[0].code, 0);
+        assert_eq!(
+            response.events.last().map(|event| event.r#type.as_str()),
+            Some("staking")
+        );
+        assert_eq!(response.validator_updates.len(), 1);
+        assert_eq!(response.validator_updates[0].power, 10);
+
+        let updated_block = Some(BlockParams {
+            max_bytes: 2048,
+            max_gas: 500,
+        });
+        assert_eq!(
+            response
+                .consensus_param_updates
+                .and_then(|params| params.block),
+            updated_block
+        );
+        // only the updated params are replaced
+        let params = app.consensus_params().unwrap();
+        assert_eq!(params.block, updated_block);
+        assert_eq!(params.evidence.unwrap().max_age_num_blocks, 100);
+
+        // the returned app hash is the one the block is committed with
+        assert_eq!(response.app_hash, app.commit().data);
+    }
+
+    #[test]
+    fn test_abci_store() {
+        let app = app_with_limits(1024, 100);
+        app.set_chain_id("test-chain");
+        assert_eq!(app.chain_id(), "test-chain");
+        assert_eq!(app.block_limits(), (Some(1024), Some(100)));
+
+        // the chain ID and consensus params aren't part of the app hash
+        let empty_app = Builder::new(InMemoryStore::default()).build();
+        assert_eq!(app.pending_app_hash(), empty_app.pending_app_hash());
+    }
+
+    fn verify(app: &BaseCoinApp<InMemoryStore>, vote_extension: &[u8]) -> i32 {
+        app.verify_vote_extension(pb::RequestVerifyVoteExtension {
+            vote_extension: Bytes::copy_from_slice(vote_extension),
+            ..Default::default()
+        })
+        .status
+    }
+
+    #[test]
+    fn test_vote_extensions() {
+        let app = Builder::new(InMemoryStore::default()).build();
+        let request = pb::RequestExtendVote {
+            height: 5,
+            ..Default::default()
+        };
+        assert!(app.extend_vote(request.clone()).vote_extension.is_empty());
+        assert_eq!(verify(&app, b""), VerifyStatus::Accept as i32);
+
+        let app = Builder::new(InMemoryStore::default())
+            .set_extend_vote_handler(|request| request.height.to_be_bytes().to_vec())
+            .set_verify_vote_extension_handler(|request| request.vote_extension.len() == 8)
+            .build();
+        assert_eq!(
+            app.extend_vote(request).vote_extension,
+            5i64.to_be_bytes().to_vec()
+        );
+        assert_eq!(
+            verify(&app, &5i64.to_be_bytes()),
+            VerifyStatus::Accept as i32
+        );
+        assert_eq!(verify(&app, b"short"), VerifyStatus::Reject as i32);
+    }
+}
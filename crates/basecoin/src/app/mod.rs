@@ -1,8 +1,29 @@
+pub mod indexer;
+pub use indexer::{TxIndex, TxIndexer};
+
 pub mod interface;
 pub mod service;
 
+mod api;
+pub use api::ApiServer;
+
 mod builder;
 pub use builder::{BaseCoinApp, Builder};
 
+mod grpc_router;
+pub use grpc_router::GrpcQueryRouter;
+
+mod reflection;
+pub use reflection::{reflection_service, ReflectionV1};
+
+mod registry;
+pub use registry::{default_app_config, GrpcServices, ModuleFactory, ModuleRegistry, Wiring};
+
+mod router;
+pub use router::MsgServiceRouter;
+
 mod runner;
-pub use runner::default_app_runner;
+pub use runner::{default_app_runner, validate_genesis_file};
+
+mod shutdown;
+pub use shutdown::{Shutdown, ShutdownReason};
@@ -0,0 +1,63 @@
+use std::task::{Context, Poll};
+
+use tonic::codegen::{http, Service};
+use tonic::server::NamedService;
+use tonic_reflection::server::{Builder, Error, ServerReflection, ServerReflectionServer};
+
+const V1ALPHA_SERVICE_NAME: &str = "grpc.reflection.v1alpha.ServerReflection";
+const V1_SERVICE_NAME: &str = "grpc.reflection.v1.ServerReflection";
+
+/// Builds the `grpc.reflection.v1alpha` server reflection service, advertising the specified
+/// services and describing them with the protobuf descriptors of `ibc_proto` (which include the
+/// Cosmos SDK protos) and of the gRPC health checking protocol.
+pub fn reflection_service<'a>(
+    service_names: impl IntoIterator<Item = &'a str>,
+) -> Result<ServerReflectionServer<impl ServerReflection>, Error> {
+    service_names
+        .into_iter()
+        .chain([V1ALPHA_SERVICE_NAME])
+        .fold(Builder::configure(), |builder, name| {
+            builder.with_service_name(name)
+        })
+        .register_encoded_file_descriptor_set(ibc_proto::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build()
+}
+
+/// Serves the `grpc.reflection.v1` server reflection service with a `v1alpha` one.
+///
+/// The two versions of the protocol are identical on the wire, only the service name differs,
+/// so the requests are simply forwarded to the `v1alpha` service (which is all `tonic-reflection`
+/// supports for the version of tonic we use).
+#[derive(Clone, Debug)]
+pub struct ReflectionV1<S>(pub S);
+
+impl<S> NamedService for ReflectionV1<S> {
+    const NAME: &'static str = V1_SERVICE_NAME;
+}
+
+impl<S, B> Service<http::Request<B>> for ReflectionV1<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let path = request.uri().path();
+        if let Some(method) = path
+            .strip_prefix('/')
+            .and_then(|path| path.strip_prefix(V1_SERVICE_NAME))
+        {
+            if let Ok(uri) = format!("/{V1ALPHA_SERVICE_NAME}{method}").parse() {
+                *request.uri_mut() = uri;
+            }
+        }
+        self.0.call(request)
+    }
+}
@@ -0,0 +1,435 @@
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Debug;
+
+use tonic::body::BoxBody;
+use tonic::codegen::{http, Service};
+use tonic::server::NamedService;
+use tonic::transport::server::Router;
+use tonic::transport::Body;
+
+use super::builder::Builder;
+use crate::error::Error;
+use cosmos_core::appconfig::{AppConfig, ModuleConfig};
+use cosmos_helper::Identifier;
+use cosmos_store::{ProvableStore, SharedStore};
+use cosmos_x_auth::account::{BURNER, MINTER, STAKING};
+use cosmos_x_auth::config::{Config as AuthConfig, ModuleAccountPermission};
+use cosmos_x_auth::impls::{Auth, AuthAccountKeeper, AuthAccountReader};
+use cosmos_x_bank::impls::{Bank, BankBalanceKeeper};
+use cosmos_x_gov::impls::Governance;
+use cosmos_x_ibc::impls::Ibc;
+use cosmos_x_ibc::transfer::TRANSFER_MODULE_ACCOUNT;
+use cosmos_x_module_api::module::Module;
+use cosmos_x_module_api::types::ModuleStore;
+use cosmos_x_staking::impls::Staking;
+use cosmos_x_upgrade::impls::Upgrade;
+
+/// Instantiates a module from its config and adds it (and its gRPC services) to the app, using
+/// the dependencies provided by the modules instantiated before it.
+pub type ModuleFactory<S> = Box<dyn Fn(&ModuleConfig, &mut Wiring<S>) -> Result<(), Error>>;
+
+type InstallService = Box<dyn FnOnce(Router) -> Router + Send>;
+
+/// Registry of the modules an app can be composed of, instantiating and wiring them as listed by
+/// an [`AppConfig`].
+pub struct ModuleRegistry<S> {
+    factories: HashMap<String, ModuleFactory<S>>,
+}
+
+impl<S: Default + ProvableStore + 'static> ModuleRegistry<S> {
+    /// Creates a registry without any module.
+    pub fn new() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Registers the factory of the module with the specified name, replacing any factory
+    /// previously registered under that name.
+    pub fn register(
+        mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&ModuleConfig, &mut Wiring<S>) -> Result<(), Error> + 'static,
+    ) -> Self {
+        self.factories.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    /// Instantiates the modules listed by the config, in order, and adds them to the app.
+    /// Returns the builder along with the gRPC services of the modules, to be served by the node.
+    pub fn build(
+        &self,
+        config: &AppConfig,
+        builder: Builder<S>,
+    ) -> Result<(Builder<S>, GrpcServices), Error> {
+        config.validate().map_err(|e| Error::AppConfig {
+            reason: e.to_string(),
+        })?;
+
+        let mut wiring = Wiring {
+            builder: Some(builder),
+            module: String::new(),
+            provided: HashMap::new(),
+            modules: vec![],
+            services: GrpcServices::default(),
+        };
+        for module in &config.modules {
+            let factory = self
+                .factories
+                .get(&module.name)
+                .ok_or_else(|| Error::AppConfig {
+                    reason: format!("no module registered with name `{}`", module.name),
+                })?;
+            wiring.module = module.name.clone();
+            factory(module, &mut wiring)?;
+        }
+
+        let Wiring {
+            builder,
+            modules,
+            services,
+            ..
+        } = wiring;
+        let mut builder = builder.expect("builder is always set");
+        let mut added = HashMap::new();
+        for (name, store_key, add) in modules {
+            builder = add(builder);
+            added.insert(name, store_key);
+        }
+
+        // modules that only serve gRPC services have no hooks to order
+        let order = |names: Vec<&str>| -> Vec<Identifier> {
+            names
+                .into_iter()
+                .filter_map(|name| added.get(name).cloned())
+                .collect()
+        };
+        let builder = builder
+            .set_order_init_genesis(order(config.init_genesis_order()))
+            .set_order_begin_blockers(order(config.begin_blockers_order()))
+            .set_order_end_blockers(order(config.end_blockers_order()));
+        Ok((builder, services))
+    }
+}
+
+impl<S> Default for ModuleRegistry<S>
+where
+    S: Default + ProvableStore + Debug + 'static,
+{
+    /// Creates a registry of the basecoin modules, i.e. `auth`, `bank`, `staking`, `ibc`,
+    /// `upgrade` and `gov`.
+    fn default() -> Self {
+        Self::new()
+            .register("auth", |config, wiring| {
+                let auth_config = config
+                    .params::<AuthConfig>()
+                    .map_err(|e| Error::AppConfig {
+                        reason: e.to_string(),
+                    })?;
+                let auth = Auth::new(wiring.module_store(config))
+                    .with_config(auth_config)
+                    .map_err(|e| Error::AppConfig {
+                        reason: e.to_string(),
+                    })?;
+                wiring.provide(auth.account_reader().clone());
+                wiring.provide(auth.account_keeper().clone());
+                wiring.add_grpc_service(auth.service());
+                wiring.add_module(config, auth);
+                Ok(())
+            })
+            .register("bank", |config, wiring| {
+                let bank = Bank::new(
+                    wiring.module_store(config),
+                    wiring.require::<AuthAccountReader<ModuleStore<S>>>()?,
+                    wiring.require::<AuthAccountKeeper<ModuleStore<S>>>()?,
+                );
+                wiring.provide(bank.bank_keeper().clone());
+                wiring.add_grpc_service(bank.service());
+                wiring.add_module(config, bank);
+                Ok(())
+            })
+            .register("staking", |config, wiring| {
+                let staking = Staking::new(wiring.module_store(config));
+                wiring.add_grpc_service(staking.service());
+                wiring.add_module(config, staking);
+                Ok(())
+            })
+            .register("ibc", |config, wiring| {
+                let ibc = Ibc::new(
+                    wiring.module_store(config),
+                    wiring.require::<BankBalanceKeeper<ModuleStore<S>>>()?,
+                );
+                wiring.add_grpc_service(ibc.client_service());
+                wiring.add_grpc_service(ibc.connection_service());
+                wiring.add_grpc_service(ibc.channel_service());
+                wiring.add_module(config, ibc);
+                Ok(())
+            })
+            .register("upgrade", |config, wiring| {
+                let upgrade = Upgrade::new(wiring.module_store(config));
+                wiring.provide(upgrade.clone());
+                // still answers ABCI queries on its gRPC method paths with ad-hoc responses
+                wiring.serve(upgrade.service());
+                wiring.add_module(config, upgrade);
+                Ok(())
+            })
+            .register("gov", |config, wiring| {
+                let governance = Governance::new(
+                    wiring.module_store(config),
+                    wiring.require::<Upgrade<ModuleStore<S>>>()?,
+                );
+                // still answers ABCI queries on its gRPC method paths with ad-hoc responses
+                wiring.serve(governance.service());
+                wiring.add_module(config, governance);
+                Ok(())
+            })
+    }
+}
+
+/// The app config of basecoin, i.e. the modules of the default [`ModuleRegistry`] with their
+/// default store keys, and the module accounts of the modules that hold coins.
+pub fn default_app_config() -> AppConfig {
+    let mut auth = ModuleConfig::new("auth");
+    auth.params = serde_json::to_value(AuthConfig {
+        module_account_permissions: vec![
+            ModuleAccountPermission::new("fee_collector", &[]),
+            ModuleAccountPermission::new("bonded_tokens_pool", &[BURNER, STAKING]),
+            ModuleAccountPermission::new("not_bonded_tokens_pool", &[BURNER, STAKING]),
+            ModuleAccountPermission::new("gov", &[BURNER]),
+            ModuleAccountPermission::new(TRANSFER_MODULE_ACCOUNT, &[MINTER, BURNER]),
+        ],
+    })
+    .expect("auth config is always serializable");
+
+    AppConfig {
+        modules: std::iter::once(auth)
+            .chain(
+                ["bank", "staking", "ibc", "upgrade", "gov"]
+                    .into_iter()
+                    .map(ModuleConfig::new),
+            )
+            .collect(),
+        // auth goes first so that the genesis accounts exist before bank sets their balances
+        init_genesis: ["auth", "bank", "staking", "ibc", "gov", "upgrade"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        begin_blockers: vec![],
+        end_blockers: vec![],
+    }
+}
+
+/// State shared by the module factories while an app is being wired.
+pub struct Wiring<S> {
+    builder: Option<Builder<S>>,
+    /// name of the module being instantiated
+    module: String,
+    /// dependencies provided by the modules instantiated so far, by type
+    provided: HashMap<TypeId, Box<dyn Any>>,
+    /// modules to add to the app, with their name and store key
+    modules: Vec<(
+        String,
+        Identifier,
+        Box<dyn FnOnce(Builder<S>) -> Builder<S>>,
+    )>,
+    services: GrpcServices,
+}
+
+impl<S> Wiring<S>
+where
+    S: Default + ProvableStore + 'static,
+{
+    /// Returns the store of the module, under its configured store key.
+    pub fn module_store(&self, config: &ModuleConfig) -> SharedStore<ModuleStore<S>> {
+        let builder = self.builder.as_ref().expect("builder is always set");
+        builder.module_store(&Identifier::from(config.store_key().to_owned()))
+    }
+
+    /// Makes a dependency (e.g. a keeper) available to the modules instantiated after this one.
+    pub fn provide<T: Any>(&mut self, dependency: T) {
+        self.provided
+            .insert(TypeId::of::<T>(), Box::new(dependency));
+    }
+
+    /// Returns a dependency provided by a module instantiated before this one.
+    pub fn require<T: Any + Clone>(&self) -> Result<T, Error> {
+        self.provided
+            .get(&TypeId::of::<T>())
+            .and_then(|dependency| dependency.downcast_ref::<T>())
+            .cloned()
+            .ok_or_else(|| Error::AppConfig {
+                reason: format!(
+                    "module `{}` depends on `{}`, which isn't provided by any module listed before it",
+                    self.module,
+                    type_name::<T>()
+                ),
+            })
+    }
+
+    /// Adds the module to the app, under its configured name and store key.
+    pub fn add_module(
+        &mut self,
+        config: &ModuleConfig,
+        module: impl Module<Store = ModuleStore<S>> + 'static,
+    ) {
+        let name = config.name.clone();
+        let prefix = Identifier::from(config.store_key().to_owned());
+        self.modules.push((
+            config.name.clone(),
+            prefix.clone(),
+            Box::new(move |builder: Builder<S>| builder.add_named_module(name, prefix, module)),
+        ));
+    }
+
+    /// Serves the gRPC query service and also answers ABCI queries on its method paths.
+    pub fn add_grpc_service<G>(&mut self, service: G)
+    where
+        G: Service<http::Request<Body>, Response = http::Response<BoxBody>, Error = Infallible>
+            + NamedService
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        G::Future: Send + 'static,
+    {
+        let builder = self.builder.take().expect("builder is always set");
+        self.builder = Some(builder.add_grpc_service(service.clone()));
+        self.serve(service);
+    }
+
+    /// Serves the gRPC service.
+    pub fn serve<G>(&mut self, service: G)
+    where
+        G: Service<http::Request<Body>, Response = http::Response<BoxBody>, Error = Infallible>
+            + NamedService
+            + Clone
+            + Send
+            + 'static,
+        G::Future: Send + 'static,
+    {
+        self.services.names.push(G::NAME);
+        self.services
+            .installers
+            .push(Box::new(move |router: Router| router.add_service(service)));
+    }
+}
+
+/// The gRPC services of the modules of an app.
+#[derive(Default)]
+pub struct GrpcServices {
+    names: Vec<&'static str>,
+    installers: Vec<InstallService>,
+}
+
+impl GrpcServices {
+    /// Names of the services.
+    pub fn names(&self) -> &[&'static str] {
+        &self.names
+    }
+
+    /// Adds the services to the gRPC server.
+    pub fn add_to(self, router: Router) -> Router {
+        self.installers
+            .into_iter()
+            .fold(router, |router, install| install(router))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::BaseCoinApp;
+    use cosmos_store::memory::InMemoryStore;
+
+    /// The app config shipped with basecoin.
+    const APP_TOML: &str = include_str!("../../../../app.toml");
+
+    fn build(config: &AppConfig) -> Result<(BaseCoinApp<InMemoryStore>, GrpcServices), Error> {
+        let builder = Builder::new(InMemoryStore::default());
+        let (builder, services) = ModuleRegistry::default().build(config, builder)?;
+        Ok((builder.build(), services))
+    }
+
+    fn build_error(config: &AppConfig) -> String {
+        match build(config) {
+            Ok(_) => panic!("app config must be invalid"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    /// Returns the store keys of the modules of the app, in the specified order.
+    fn store_keys(app: &BaseCoinApp<InMemoryStore>, order: &[usize]) -> Vec<String> {
+        let modules = app.modules.read().unwrap();
+        order.iter().map(|&i| modules[i].id.to_string()).collect()
+    }
+
+    fn config(modules: &[&str]) -> AppConfig {
+        AppConfig {
+            modules: modules.iter().copied().map(ModuleConfig::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_default_app_config() {
+        let (app, services) = build(&default_app_config()).unwrap();
+
+        let init_genesis = ["auth", "bank", "staking", "ibc", "gov", "upgrade"];
+        assert_eq!(store_keys(&app, &app.order.init_genesis), init_genesis);
+        assert_eq!(store_keys(&app, &app.order.begin_blockers), init_genesis);
+        assert_eq!(store_keys(&app, &app.order.end_blockers), init_genesis);
+        assert!(services.names().contains(&"cosmos.bank.v1beta1.Query"));
+        assert!(services.names().contains(&"cosmos.staking.v1beta1.Query"));
+    }
+
+    #[test]
+    fn test_build_shipped_app_config() {
+        let config = AppConfig::load_toml(APP_TOML).unwrap();
+        let default = default_app_config();
+        let names = |config: &AppConfig| -> Vec<String> {
+            config.modules.iter().map(|m| m.name.clone()).collect()
+        };
+        assert_eq!(names(&config), names(&default));
+        assert_eq!(config.init_genesis, default.init_genesis);
+        assert_eq!(
+            config.modules[0].params::<AuthConfig>().unwrap(),
+            default.modules[0].params::<AuthConfig>().unwrap()
+        );
+        build(&config).unwrap();
+    }
+
+    #[test]
+    fn test_build_with_store_keys_and_orders() {
+        let mut config = config(&["auth", "bank"]);
+        config.modules[1].store_key = Some("balances".to_owned());
+        config.init_genesis = vec!["bank".to_owned(), "auth".to_owned()];
+
+        let (app, _) = build(&config).unwrap();
+        assert_eq!(store_keys(&app, &[0, 1]), ["auth", "balances"]);
+        assert_eq!(
+            store_keys(&app, &app.order.init_genesis),
+            ["balances", "auth"]
+        );
+        assert_eq!(
+            store_keys(&app, &app.order.end_blockers),
+            ["balances", "auth"]
+        );
+    }
+
+    #[test]
+    fn test_build_invalid_app_config() {
+        assert!(build_error(&config(&["auth", "mint"]))
+            .contains("no module registered with name `mint`"));
+        assert!(build_error(&config(&["auth", "auth"])).contains("listed more than once"));
+
+        // `bank` requires the keepers of `auth`, which must be listed before it
+        let error = build_error(&config(&["bank", "auth"]));
+        assert!(error.contains("module `bank` depends on"), "{error}");
+
+        let mut config = config(&["auth", "bank"]);
+        config.begin_blockers = vec!["bank".to_owned()];
+        assert!(build_error(&config).contains("begin_blockers is missing modules: auth"));
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use cosmos_helper::Identifier;
+
+/// Routes transaction messages to the module that handles them, based on the message's
+/// type URL. Similar to the Go SDK's `MsgServiceRouter`.
+#[derive(Clone, Debug, Default)]
+pub struct MsgServiceRouter {
+    /// type URL -> (index of the module in the app's module list, module identifier)
+    routes: HashMap<String, (usize, Identifier)>,
+}
+
+impl MsgServiceRouter {
+    /// Registers the module at `index` (identified by `id`) as the handler of messages with
+    /// the specified type URL.
+    /// Returns an error if a handler for the type URL was previously registered.
+    pub fn register(
+        &mut self,
+        type_url: impl ToString,
+        index: usize,
+        id: &Identifier,
+    ) -> Result<(), Error> {
+        let type_url = type_url.to_string();
+        if let Some((_, registered)) = self.routes.get(&type_url) {
+            return Err(Error::DuplicateMsgRoute {
+                type_url,
+                module: registered.to_string(),
+            });
+        }
+        self.routes.insert(type_url, (index, id.clone()));
+        Ok(())
+    }
+
+    /// Returns the index of the module that handles messages with the specified type URL.
+    pub fn route(&self, type_url: &str) -> Result<usize, Error> {
+        self.routes
+            .get(type_url)
+            .map(|(index, _)| *index)
+            .ok_or_else(|| Error::UnknownMessageType {
+                type_url: type_url.to_owned(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_x_module_api::error::AbciError;
+
+    const MSG_SEND: &str = "/cosmos.bank.v1beta1.MsgSend";
+    const MSG_TRANSFER: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+
+    fn id(id: &str) -> Identifier {
+        Identifier::from(id.to_owned())
+    }
+
+    #[test]
+    fn test_route() {
+        let mut router = MsgServiceRouter::default();
+        router.register(MSG_SEND, 1, &id("bank")).unwrap();
+        router.register(MSG_TRANSFER, 2, &id("ibc")).unwrap();
+
+        assert_eq!(router.route(MSG_SEND).unwrap(), 1);
+        assert_eq!(router.route(MSG_TRANSFER).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_route() {
+        let mut router = MsgServiceRouter::default();
+        router.register(MSG_SEND, 1, &id("bank")).unwrap();
+
+        let e = router.register(MSG_SEND, 2, &id("ibc")).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!("message type URL `{MSG_SEND}` is already handled by the `bank` module")
+        );
+        // the first handler is kept
+        assert_eq!(router.route(MSG_SEND).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_unknown_route() {
+        let mut router = MsgServiceRouter::default();
+        router.register(MSG_SEND, 0, &id("bank")).unwrap();
+
+        let e = router.route(MSG_TRANSFER).unwrap_err();
+        assert!(
+            matches!(&e, Error::UnknownMessageType { type_url } if type_url == MSG_TRANSFER),
+            "{e}"
+        );
+        // `ErrUnknownRequest` of the root codespace
+        let registered = e.registered_error();
+        assert_eq!(registered.codespace(), "sdk");
+        assert_eq!(registered.code(), 6);
+    }
+}
@@ -1,71 +1,65 @@
+use std::path::Path;
+use std::time::Duration;
+
 use ibc_proto::cosmos::base::tendermint::v1beta1::service_server::ServiceServer as HealthServer;
 use ibc_proto::cosmos::tx::v1beta1::service_server::ServiceServer as TxServer;
-use tracing::info;
+use tendermint_rpc::HttpClient;
+use tonic::server::NamedService;
+use tonic_health::pb::health_server::{Health, HealthServer as GrpcHealthServer};
+use tonic_health::ServingStatus;
+use tonic_reflection::server::{ServerReflection, ServerReflectionServer};
+use tracing::{error, info};
 
-use super::Builder;
-use cosmos_config::ServerConfig;
+use super::reflection::{reflection_service, ReflectionV1};
+use super::{default_app_config, ApiServer, Builder, ModuleRegistry, ShutdownReason, TxIndex};
+use crate::error::Error;
+use cosmos_config::{AddressConfig, Config};
+use cosmos_core::appconfig::AppConfig;
 use cosmos_store::memory::InMemoryStore;
-use cosmos_x_auth::impls::Auth;
-use cosmos_x_bank::impls::Bank;
-use cosmos_x_gov::impls::Governance;
-use cosmos_x_ibc::impls::Ibc;
-use cosmos_x_module_api::module::prefix;
-use cosmos_x_module_api::module::Identifiable;
-use cosmos_x_staking::impls::Staking;
-use cosmos_x_upgrade::impls::Upgrade;
-
-#[cfg(not(feature = "tower-abci"))]
+use cosmos_types::config as sdk_config;
+use serde_json::Value;
+
+#[cfg(not(any(feature = "tower-abci", feature = "v0_38")))]
 use tendermint_abci::ServerBuilder;
 
-#[cfg(feature = "tower-abci")]
+#[cfg(all(feature = "tower-abci", not(feature = "v0_38")))]
 use tower_abci::split;
 
-pub async fn default_app_runner(server_cfg: ServerConfig) {
+/// How long the node waits for the block being executed to be committed when shutting down.
+const HALT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs the node until it's requested to shut down (by SIGINT/SIGTERM or once the halt height or
+/// time is reached) or one of its servers fails, and returns the reason it shut down for.
+pub async fn default_app_runner(cfg: Config) -> ShutdownReason {
+    // the address config must be set before any address is derived, e.g. by the modules
+    seal_address_config(&cfg.address).expect("invalid address config");
+    let server_cfg = cfg.server;
+
     // instantiate the application with a KV store implementation of choice
-    let app_builder = Builder::new(InMemoryStore::default());
-
-    // instantiate modules and setup inter-module communication (if required)
-    let auth = Auth::new(app_builder.module_store(&prefix::Auth {}.identifier()));
-    let bank = Bank::new(
-        app_builder.module_store(&prefix::Bank {}.identifier()),
-        auth.account_reader().clone(),
-        auth.account_keeper().clone(),
-    );
-    let staking = Staking::new(app_builder.module_store(&prefix::Staking {}.identifier()));
-    let ibc = Ibc::new(
-        app_builder.module_store(&prefix::Ibc {}.identifier()),
-        bank.bank_keeper().clone(),
-    );
-    let upgrade = Upgrade::new(app_builder.module_store(&prefix::Upgrade {}.identifier()));
-    let governance = Governance::new(
-        app_builder.module_store(&prefix::Governance {}.identifier()),
-        upgrade.clone(),
-    );
+    let rpc_client = HttpClient::new(cfg.cometbft.rpc_addr).expect("invalid CometBFT RPC address");
+    let tx_index = TxIndex::from_config(&cfg.indexer).expect("failed to open the tx index");
+    let app_builder = Builder::new(InMemoryStore::default())
+        .set_rpc_client(rpc_client)
+        .set_tx_index(tx_index)
+        .set_halt_height(server_cfg.halt_height)
+        .set_halt_time(server_cfg.halt_time);
+
+    // instantiate the modules listed by the app config and setup inter-module communication
+    let app_config = load_app_config(&cfg).expect("failed to load the app config");
+    let (app_builder, module_services) = ModuleRegistry::default()
+        .build(&app_config, app_builder)
+        .expect("failed to instantiate the modules of the app config");
+    let app = app_builder.build();
+
+    let shutdown = app.shutdown();
+    shutdown.listen_for_signals();
 
-    // instantiate gRPC services for each module
-    let auth_service = auth.service();
-    let bank_service = bank.service();
-    let ibc_client_service = ibc.client_service();
-    let ibc_conn_service = ibc.connection_service();
-    let ibc_channel_service = ibc.channel_service();
-    let governance_service = governance.service();
-    let staking_service = staking.service();
-    let upgrade_service = upgrade.service();
-
-    // register modules with the app
-    let app = app_builder
-        .add_module(prefix::Auth {}.identifier(), auth.clone())
-        .add_module(prefix::Bank {}.identifier(), bank.clone())
-        .add_module(prefix::Ibc {}.identifier(), ibc)
-        .add_module(prefix::Governance {}.identifier(), governance.clone())
-        .add_module(prefix::Upgrade {}.identifier(), upgrade.clone())
-        .build();
-
-    #[cfg(not(feature = "tower-abci"))]
+    #[cfg(not(any(feature = "tower-abci", feature = "v0_38")))]
     {
         info!("Starting Tendermint ABCI server");
 
-        // run the blocking ABCI server on a separate thread
+        // run the blocking ABCI server on a separate thread, which can't be stopped: once the
+        // node shuts down, the app is halted and the thread ends with the process
         let server = ServerBuilder::new(server_cfg.read_buf_size)
             .bind(
                 format!("{}:{}", server_cfg.host, server_cfg.port),
@@ -73,12 +67,16 @@ pub async fn default_app_runner(server_cfg: ServerConfig) {
             )
             .unwrap();
 
+        let shutdown = shutdown.clone();
         std::thread::spawn(move || {
-            server.listen().unwrap();
+            if let Err(e) = server.listen() {
+                error!("ABCI server failed: {e}");
+                shutdown.trigger(ShutdownReason::Failure);
+            }
         });
     }
 
-    #[cfg(feature = "tower-abci")]
+    #[cfg(all(feature = "tower-abci", not(feature = "v0_38")))]
     {
         info!("Starting tower ABCI server");
 
@@ -93,30 +91,339 @@ pub async fn default_app_runner(server_cfg: ServerConfig) {
             .finish()
             .expect("tower_abci::Server building failed");
 
-        // run the blocking ABCI server on a separate thread
         let server_listen_addr = format!("{}:{}", server_cfg.host, server_cfg.port);
+        let shutdown = shutdown.clone();
         tokio::task::spawn(async move {
-            server.listen(server_listen_addr).await.unwrap();
+            // stop accepting connections on shutdown
+            tokio::select! {
+                result = server.listen(server_listen_addr) => {
+                    if let Err(e) = result {
+                        error!("ABCI server failed: {e}");
+                        shutdown.trigger(ShutdownReason::Failure);
+                    }
+                }
+                _ = shutdown.wait() => {}
+            }
         });
     }
 
-    // run the gRPC server
-    let grpc_server = tonic::transport::Server::builder()
-        .add_service(HealthServer::new(app.clone()))
-        .add_service(TxServer::new(app.clone()))
-        .add_service(ibc_client_service)
-        .add_service(ibc_conn_service)
-        .add_service(ibc_channel_service)
-        .add_service(auth_service)
-        .add_service(bank_service)
-        .add_service(governance_service)
-        .add_service(staking_service)
-        .add_service(upgrade_service)
-        .serve(
-            format!("{}:{}", server_cfg.host, server_cfg.grpc_port)
-                .parse()
-                .unwrap(),
+    #[cfg(feature = "v0_38")]
+    {
+        info!("Starting tower ABCI server (CometBFT v0.38)");
+
+        let app_split = app.clone();
+        let (consensus, mempool, snapshot, info) =
+            tower_abci_v038::v038::split::service(app_split, 10);
+
+        let server = tower_abci_v038::v038::Server::builder()
+            .consensus(consensus)
+            .mempool(mempool)
+            .info(info)
+            .snapshot(snapshot)
+            .finish()
+            .expect("tower_abci::Server building failed");
+
+        let server_listen_addr = format!("{}:{}", server_cfg.host, server_cfg.port);
+        let shutdown = shutdown.clone();
+        tokio::task::spawn(async move {
+            // stop accepting connections on shutdown
+            tokio::select! {
+                result = server.listen(server_listen_addr) => {
+                    if let Err(e) = result {
+                        error!("ABCI server failed: {e}");
+                        shutdown.trigger(ShutdownReason::Failure);
+                    }
+                }
+                _ = shutdown.wait() => {}
+            }
+        });
+    }
+
+    // run the REST server, which also answers the tx and tendermint service endpoints
+    let api_server = if cfg.api.enable {
+        let mut grpc_router = (*app.grpc_router).clone();
+        grpc_router.register(TxServer::new(app.clone()));
+        grpc_router.register(HealthServer::new(app.clone()));
+
+        let api_addr = format!("{}:{}", cfg.api.host, cfg.api.port)
+            .parse()
+            .expect("invalid REST server address");
+        let router = ApiServer::new(grpc_router, cfg.api).router();
+
+        info!("Starting REST server on {api_addr}");
+        let shutdown = shutdown.clone();
+        Some(tokio::task::spawn(async move {
+            let result = axum::Server::bind(&api_addr)
+                .serve(router.into_make_service())
+                .with_graceful_shutdown(async {
+                    shutdown.wait().await;
+                })
+                .await;
+            if let Err(e) = result {
+                error!("REST server failed: {e}");
+                shutdown.trigger(ShutdownReason::Failure);
+            }
+        }))
+    } else {
+        None
+    };
+
+    // report every served gRPC service as serving, and advertise them through server reflection
+    let tendermint_server = HealthServer::new(app.clone());
+    let tx_server = TxServer::new(app.clone());
+    let service_names: Vec<_> = [service_name(&tendermint_server), service_name(&tx_server)]
+        .into_iter()
+        .chain(module_services.names().iter().copied())
+        .collect();
+    let (health_service, reflection_service) = introspection_services(service_names).await;
+
+    // run the gRPC server until the node shuts down, letting in-flight requests complete
+    let grpc_router = tonic::transport::Server::builder()
+        .add_service(health_service)
+        .add_service(reflection_service.clone())
+        .add_service(ReflectionV1(reflection_service))
+        .add_service(tendermint_server)
+        .add_service(tx_server);
+    let grpc_server = module_services.add_to(grpc_router).serve_with_shutdown(
+        format!("{}:{}", server_cfg.host, server_cfg.grpc_port)
+            .parse()
+            .unwrap(),
+        async {
+            shutdown.wait().await;
+        },
+    );
+    if let Err(e) = grpc_server.await {
+        error!("gRPC server failed: {e}");
+        shutdown.trigger(ShutdownReason::Failure);
+    }
+    if let Some(api_server) = api_server {
+        let _ = api_server.await;
+    }
+
+    let reason = shutdown.wait().await;
+    info!("Shutting down: {reason}");
+
+    // let the block being executed (if any) be committed, and keep the app from executing any
+    // further block until the process exits
+    let _exec = tokio::task::block_in_place(|| app.halt(HALT_TIMEOUT));
+    info!(
+        "Stopped at height {}",
+        app.store.read().unwrap().current_height().saturating_sub(1)
+    );
+    reason
+}
+
+fn service_name<S: NamedService>(_service: &S) -> &'static str {
+    S::NAME
+}
+
+/// Returns the gRPC health checking service, reporting the specified services as serving, and
+/// the server reflection service, advertising them along with the health checking service.
+async fn introspection_services(
+    service_names: Vec<&'static str>,
+) -> (
+    GrpcHealthServer<impl Health>,
+    ServerReflectionServer<impl ServerReflection>,
+) {
+    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+    for &name in &service_names {
+        health_reporter
+            .set_service_status(name, ServingStatus::Serving)
+            .await;
+    }
+    let reflection_service = reflection_service(
+        service_names
+            .into_iter()
+            .chain([service_name(&health_service)]),
+    )
+    .expect("failed to build the gRPC reflection service");
+    (health_service, reflection_service)
+}
+
+/// Checks the `app_state` of a genesis file against the modules of the app config, without
+/// running the node.
+pub fn validate_genesis_file(cfg: &Config, path: &Path) -> Result<(), Error> {
+    let genesis = std::fs::read_to_string(path).map_err(|e| Error::Custom {
+        reason: format!("failed to read genesis file {}: {e}", path.display()),
+    })?;
+    let genesis: Value = serde_json::from_str(&genesis).map_err(|e| Error::Custom {
+        reason: format!("genesis file {} isn't valid JSON: {e}", path.display()),
+    })?;
+
+    seal_address_config(&cfg.address)?;
+    let app_config = load_app_config(cfg)?;
+    let (app_builder, _) =
+        ModuleRegistry::default().build(&app_config, Builder::new(InMemoryStore::default()))?;
+    app_builder
+        .build()
+        .validate_genesis(genesis.get("app_state").unwrap_or(&Value::Null))
+}
+
+/// Sets the chain-wide address config (i.e. the bech32 prefixes) from the configuration file,
+/// and seals it so that it can't change while the node is running.
+fn seal_address_config(cfg: &AddressConfig) -> Result<(), Error> {
+    let mut config = sdk_config::Config::with_main_prefix(&cfg.bech32_prefix);
+    for (prefix, field) in [
+        (
+            &cfg.account_pub_prefix,
+            &mut config.bech32_account_pub_prefix,
+        ),
+        (
+            &cfg.validator_prefix,
+            &mut config.bech32_validator_addr_prefix,
+        ),
+        (
+            &cfg.validator_pub_prefix,
+            &mut config.bech32_validator_pub_prefix,
+        ),
+        (
+            &cfg.consensus_prefix,
+            &mut config.bech32_consensus_addr_prefix,
+        ),
+        (
+            &cfg.consensus_pub_prefix,
+            &mut config.bech32_consensus_pub_prefix,
+        ),
+    ] {
+        if let Some(prefix) = prefix {
+            *field = prefix.clone();
+        }
+    }
+    config.purpose = cfg.purpose;
+    config.coin_type = cfg.coin_type;
+
+    // the config can only be sealed once per process
+    if sdk_config::is_config_sealed() && sdk_config::get_config() == config {
+        return Ok(());
+    }
+    sdk_config::set_config(config).map_err(|e| Error::Custom {
+        reason: format!("invalid address config: {e}"),
+    })?;
+    let config = sdk_config::seal_config();
+    info!(
+        "Using bech32 prefix `{}` for account addresses",
+        config.bech32_account_addr_prefix
+    );
+    Ok(())
+}
+
+/// Loads the app config the configuration file points to, or basecoin's default one.
+fn load_app_config(cfg: &Config) -> Result<AppConfig, Error> {
+    match &cfg.global.app_config {
+        Some(path) => AppConfig::load_file(path).map_err(|e| Error::AppConfig {
+            reason: e.to_string(),
+        }),
+        None => Ok(default_app_config()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::codec::ProstCodec;
+    use tonic::codegen::http::uri::PathAndQuery;
+    use tonic::transport::{Channel, Server};
+    use tonic::Code;
+    use tonic_health::pb::health_check_response::ServingStatus as HealthStatus;
+    use tonic_health::pb::health_client::HealthClient;
+    use tonic_health::pb::HealthCheckRequest;
+    use tonic_reflection::pb::server_reflection_request::MessageRequest;
+    use tonic_reflection::pb::server_reflection_response::MessageResponse;
+    use tonic_reflection::pb::{ServerReflectionRequest, ServerReflectionResponse};
+
+    const SERVICE_NAMES: [&str; 3] = [
+        "cosmos.base.tendermint.v1beta1.Service",
+        "cosmos.tx.v1beta1.Service",
+        "cosmos.bank.v1beta1.Query",
+    ];
+
+    /// Serves the health checking and server reflection services the node serves for
+    /// `SERVICE_NAMES` on a local port.
+    async fn serve() -> Channel {
+        let (health_service, reflection_service) =
+            introspection_services(SERVICE_NAMES.to_vec()).await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = futures::stream::unfold(listener, |listener| async move {
+            let stream = listener.accept().await.map(|(stream, _)| stream);
+            Some((stream, listener))
+        });
+        tokio::spawn(
+            Server::builder()
+                .add_service(health_service)
+                .add_service(reflection_service.clone())
+                .add_service(ReflectionV1(reflection_service))
+                .serve_with_incoming(Box::pin(incoming)),
         );
+        Channel::from_shared(format!("http://{addr}"))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap()
+    }
 
-    grpc_server.await.unwrap()
+    /// Lists the services advertised by the server reflection service of the given version.
+    async fn list_services(channel: Channel, version: &str) -> Vec<String> {
+        let mut client = tonic::client::Grpc::new(channel);
+        client.ready().await.unwrap();
+        let path = format!("/grpc.reflection.{version}.ServerReflection/ServerReflectionInfo");
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let mut responses = client
+            .streaming(
+                tonic::Request::new(futures::stream::iter([request])),
+                path.parse::<PathAndQuery>().unwrap(),
+                ProstCodec::<ServerReflectionRequest, ServerReflectionResponse>::default(),
+            )
+            .await
+            .unwrap()
+            .into_inner();
+        let response = responses.message().await.unwrap().unwrap();
+        match response.message_response {
+            Some(MessageResponse::ListServicesResponse(response)) => {
+                let mut names: Vec<_> = response.service.into_iter().map(|s| s.name).collect();
+                names.sort();
+                names
+            }
+            response => panic!("unexpected reflection response {response:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reflection() {
+        let channel = serve().await;
+
+        let mut expected: Vec<_> = SERVICE_NAMES
+            .into_iter()
+            .chain([
+                "grpc.health.v1.Health",
+                "grpc.reflection.v1alpha.ServerReflection",
+            ])
+            .map(String::from)
+            .collect();
+        expected.sort();
+        assert_eq!(list_services(channel.clone(), "v1alpha").await, expected);
+        // the v1 service answers as the v1alpha one does
+        assert_eq!(list_services(channel, "v1").await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let mut client = HealthClient::new(serve().await);
+        let check = |service: &str| HealthCheckRequest {
+            service: service.to_string(),
+        };
+
+        for service in SERVICE_NAMES {
+            let response = client.check(check(service)).await.unwrap().into_inner();
+            assert_eq!(response.status, HealthStatus::Serving as i32, "{service}");
+        }
+        let status = client
+            .check(check("cosmos.staking.v1beta1.Query"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+    }
 }
@@ -1,9 +1,12 @@
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetNodeInfoResponse;
-use std::convert::TryInto;
 use tracing::debug;
 
-use cosmrs::Tx;
-use tendermint_proto::p2p::DefaultNodeInfo;
+use tendermint::block;
+use tendermint::node::info::TxIndexStatus;
+use tendermint_abci::Application;
+use tendermint_proto::abci::RequestQuery;
+use tendermint_proto::p2p::{DefaultNodeInfo, DefaultNodeInfoOther, ProtocolVersion};
+use tendermint_rpc::{HttpClient, Paging};
 use tonic::{Request, Response, Status};
 
 use ibc_proto::cosmos::base::tendermint::v1beta1::service_server::Service as HealthService;
@@ -22,6 +25,7 @@ use ibc_proto::cosmos::base::tendermint::v1beta1::GetValidatorSetByHeightRequest
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetValidatorSetByHeightResponse;
 use ibc_proto::cosmos::base::tendermint::v1beta1::Module as VersionInfoModule;
 use ibc_proto::cosmos::base::tendermint::v1beta1::VersionInfo;
+use ibc_proto::cosmos::base::tendermint::v1beta1::{ProofOp, ProofOps, Validator};
 
 use ibc_proto::cosmos::tx::v1beta1::service_server::Service as TxService;
 use ibc_proto::cosmos::tx::v1beta1::BroadcastTxRequest;
@@ -34,17 +38,64 @@ use ibc_proto::cosmos::tx::v1beta1::GetTxsEventRequest;
 use ibc_proto::cosmos::tx::v1beta1::GetTxsEventResponse;
 use ibc_proto::cosmos::tx::v1beta1::SimulateRequest;
 use ibc_proto::cosmos::tx::v1beta1::SimulateResponse;
+use ibc_proto::cosmos::tx::v1beta1::Tx as RawTx;
+use ibc_proto::cosmos::tx::v1beta1::{BroadcastMode, OrderBy};
+
+use ibc_proto::cosmos::base::abci::v1beta1::TxResponse;
+use ibc_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+use prost::Message;
+use tendermint_rpc::Client;
 
 use super::builder::BaseCoinApp;
+use super::indexer::{tx_hash, Query};
+use crate::error::Error;
 use cosmos_store::ProvableStore;
+use cosmos_types::address::ConsAddress;
+use cosmos_types::crypto::public_key::PublicKey;
+use cosmos_x_module_api::pagination::{page_bounds, DEFAULT_LIMIT};
 
 #[tonic::async_trait]
-impl<S: ProvableStore + 'static> HealthService for BaseCoinApp<S> {
+impl<S: Default + ProvableStore + 'static> HealthService for BaseCoinApp<S> {
     async fn abci_query(
         &self,
-        _request: Request<AbciQueryRequest>,
+        request: Request<AbciQueryRequest>,
     ) -> Result<Response<AbciQueryResponse>, Status> {
-        unimplemented!()
+        let request = request.into_inner();
+        if request.path.is_empty() {
+            return Err(Status::invalid_argument("empty query path"));
+        }
+
+        let response = Application::query(
+            self,
+            RequestQuery {
+                data: request.data.into(),
+                path: request.path,
+                height: request.height,
+                prove: request.prove,
+            },
+        );
+
+        Ok(Response::new(AbciQueryResponse {
+            code: response.code,
+            log: response.log,
+            info: response.info,
+            index: response.index,
+            key: response.key.into(),
+            value: response.value.into(),
+            proof_ops: response.proof_ops.map(|proof_ops| ProofOps {
+                ops: proof_ops
+                    .ops
+                    .into_iter()
+                    .map(|op| ProofOp {
+                        r#type: op.r#type,
+                        key: op.key,
+                        data: op.data,
+                    })
+                    .collect(),
+            }),
+            height: response.height,
+            codespace: response.codespace,
+        }))
     }
 
     async fn get_node_info(
@@ -53,19 +104,49 @@ impl<S: ProvableStore + 'static> HealthService for BaseCoinApp<S> {
     ) -> Result<Response<GetNodeInfoResponse>, Status> {
         debug!("Got node info request");
 
-        // TODO(hu55a1n1): generate below info using build script
+        let node_info = self
+            .rpc_client()?
+            .status()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?
+            .node_info;
+
         Ok(Response::new(GetNodeInfoResponse {
-            default_node_info: Some(DefaultNodeInfo::default()),
+            default_node_info: Some(DefaultNodeInfo {
+                protocol_version: Some(ProtocolVersion {
+                    p2p: node_info.protocol_version.p2p,
+                    block: node_info.protocol_version.block,
+                    app: node_info.protocol_version.app,
+                }),
+                default_node_id: node_info.id.to_string(),
+                listen_addr: node_info.listen_addr.to_string(),
+                network: node_info.network.to_string(),
+                version: node_info.version.to_string(),
+                channels: hex::decode(node_info.channels.to_string()).unwrap_or_default(),
+                moniker: node_info.moniker.to_string(),
+                other: Some(DefaultNodeInfoOther {
+                    tx_index: match node_info.other.tx_index {
+                        TxIndexStatus::On => "on".to_string(),
+                        TxIndexStatus::Off => "off".to_string(),
+                    },
+                    rpc_address: node_info.other.rpc_address,
+                }),
+            }),
             application_version: Some(VersionInfo {
-                name: "basecoin-rs".to_string(),
-                app_name: "basecoind".to_string(),
-                version: "0.1.0".to_string(),
-                git_commit: "209afef7e99ebcb814b25b6738d033aa5e1a932c".to_string(),
-                build_deps: vec![VersionInfoModule {
-                    path: "github.com/cosmos/cosmos-sdk".to_string(),
-                    version: "v0.47.0".to_string(),
-                    sum: "h1:ps1QWfvaX6VLNcykA7wzfii/5IwBfYgTIik6NOVDq/c=".to_string(),
-                }],
+                name: env!("CARGO_PKG_NAME").to_string(),
+                app_name: env!("CARGO_PKG_NAME").to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                git_commit: env!("BASECOIN_GIT_COMMIT").to_string(),
+                build_tags: build_tags(),
+                go_version: env!("BASECOIN_RUSTC_VERSION").to_string(),
+                build_deps: build_info::BUILD_DEPS
+                    .iter()
+                    .map(|(path, version, sum)| VersionInfoModule {
+                        path: path.to_string(),
+                        version: version.to_string(),
+                        sum: sum.to_string(),
+                    })
+                    .collect(),
                 ..VersionInfo::default()
             }),
         }))
@@ -75,82 +156,886 @@ impl<S: ProvableStore + 'static> HealthService for BaseCoinApp<S> {
         &self,
         _request: Request<GetSyncingRequest>,
     ) -> Result<Response<GetSyncingResponse>, Status> {
-        unimplemented!()
+        let status = self
+            .rpc_client()?
+            .status()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        Ok(Response::new(GetSyncingResponse {
+            syncing: status.sync_info.catching_up,
+        }))
     }
 
     async fn get_latest_block(
         &self,
         _request: Request<GetLatestBlockRequest>,
     ) -> Result<Response<GetLatestBlockResponse>, Status> {
-        unimplemented!()
+        let response = self
+            .rpc_client()?
+            .latest_block()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        Ok(Response::new(GetLatestBlockResponse {
+            block_id: Some(response.block_id.into()),
+            block: Some(response.block.into()),
+            ..GetLatestBlockResponse::default()
+        }))
     }
 
     async fn get_block_by_height(
         &self,
-        _request: Request<GetBlockByHeightRequest>,
+        request: Request<GetBlockByHeightRequest>,
     ) -> Result<Response<GetBlockByHeightResponse>, Status> {
-        unimplemented!()
+        let height = block_height(request.into_inner().height)?;
+        let response = self
+            .rpc_client()?
+            .block(height)
+            .await
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(GetBlockByHeightResponse {
+            block_id: Some(response.block_id.into()),
+            block: Some(response.block.into()),
+            ..GetBlockByHeightResponse::default()
+        }))
     }
 
     async fn get_latest_validator_set(
         &self,
-        _request: Request<GetLatestValidatorSetRequest>,
+        request: Request<GetLatestValidatorSetRequest>,
     ) -> Result<Response<GetLatestValidatorSetResponse>, Status> {
-        unimplemented!()
+        let rpc_client = self.rpc_client()?;
+        let height = rpc_client
+            .status()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?
+            .sync_info
+            .latest_block_height;
+        let (block_height, validators, pagination) = self
+            .validator_set(height, request.into_inner().pagination)
+            .await?;
+
+        Ok(Response::new(GetLatestValidatorSetResponse {
+            block_height,
+            validators,
+            pagination,
+        }))
     }
 
     async fn get_validator_set_by_height(
         &self,
-        _request: Request<GetValidatorSetByHeightRequest>,
+        request: Request<GetValidatorSetByHeightRequest>,
     ) -> Result<Response<GetValidatorSetByHeightResponse>, Status> {
-        unimplemented!()
+        let request = request.into_inner();
+        let height = block_height(request.height)?;
+        let (block_height, validators, pagination) =
+            self.validator_set(height, request.pagination).await?;
+
+        Ok(Response::new(GetValidatorSetByHeightResponse {
+            block_height,
+            validators,
+            pagination,
+        }))
+    }
+}
+
+impl<S> BaseCoinApp<S> {
+    fn rpc_client(&self) -> Result<&HttpClient, Status> {
+        self.rpc_client
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("no CometBFT RPC client configured"))
+    }
+
+    /// Returns the requested page of the validator set at the specified height, along with the
+    /// height of the validator set.
+    async fn validator_set(
+        &self,
+        height: block::Height,
+        pagination: Option<PageRequest>,
+    ) -> Result<(i64, Vec<Validator>, Option<PageResponse>), Status> {
+        let (offset, limit) = page_bounds(pagination).map_err(Status::invalid_argument)?;
+        let response = self
+            .rpc_client()?
+            .validators(height, Paging::All)
+            .await
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let total = response.validators.len() as u64;
+        let validators = response
+            .validators
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|validator| Validator {
+                address: ConsAddress::from(validator.address).to_string(),
+                pub_key: PublicKey::from(validator.pub_key).to_any().ok(),
+                voting_power: validator.power.value() as i64,
+                proposer_priority: validator.proposer_priority.value(),
+            })
+            .collect();
+
+        Ok((
+            response.block_height.value() as i64,
+            validators,
+            Some(PageResponse {
+                next_key: vec![],
+                total,
+            }),
+        ))
     }
 }
 
 #[tonic::async_trait]
-impl<S: ProvableStore + 'static> TxService for BaseCoinApp<S> {
+impl<S: Default + ProvableStore + 'static> TxService for BaseCoinApp<S> {
     async fn simulate(
         &self,
         request: Request<SimulateRequest>,
     ) -> Result<Response<SimulateResponse>, Status> {
-        // TODO(hu55a1n1): implement tx based simulate
-        let _: Tx = request
-            .into_inner()
-            .tx_bytes
-            .as_slice()
-            .try_into()
-            .map_err(|_| Status::invalid_argument("failed to deserialize tx"))?;
-        Ok(Response::new(SimulateResponse {
-            gas_info: None,
-            result: None,
-        }))
+        let request = request.into_inner();
+
+        #[allow(deprecated)] // `tx` is still accepted for backward compatibility
+        let tx_bytes = match (request.tx_bytes, request.tx) {
+            (tx_bytes, _) if !tx_bytes.is_empty() => tx_bytes,
+            (_, Some(tx)) => tx.encode_to_vec(),
+            _ => return Err(Status::invalid_argument("empty txBytes is not allowed")),
+        };
+
+        match self.simulate_tx(&tx_bytes) {
+            Ok((gas_info, result)) => Ok(Response::new(SimulateResponse {
+                gas_info: Some(gas_info),
+                result: Some(result),
+            })),
+            Err((e @ Error::TxDecode { .. }, _)) => Err(Status::invalid_argument(e.to_string())),
+            Err((e, gas_info)) => Err(Status::unknown(format!(
+                "{e} With gas wanted: '{}' and gas used: '{}' ",
+                gas_info.gas_wanted, gas_info.gas_used
+            ))),
+        }
     }
 
     async fn get_tx(
         &self,
-        _request: Request<GetTxRequest>,
+        request: Request<GetTxRequest>,
     ) -> Result<Response<GetTxResponse>, Status> {
-        unimplemented!()
+        let hash = request.into_inner().hash;
+        if hash.is_empty() {
+            return Err(Status::invalid_argument("tx hash cannot be empty"));
+        }
+
+        let tx_response = self
+            .tx_index
+            .read()
+            .unwrap()
+            .get(&hash)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("tx not found: {hash}")))?;
+
+        Ok(Response::new(GetTxResponse {
+            tx: Some(tx_of_response(&tx_response)?),
+            tx_response: Some(tx_response),
+        }))
     }
 
     async fn broadcast_tx(
         &self,
-        _request: Request<BroadcastTxRequest>,
+        request: Request<BroadcastTxRequest>,
     ) -> Result<Response<BroadcastTxResponse>, Status> {
-        unimplemented!()
+        let request = request.into_inner();
+        let rpc_client = self.rpc_client()?;
+        let tx_bytes = request.tx_bytes;
+        let txhash = tx_hash(&tx_bytes);
+
+        let tx_response = match BroadcastMode::from_i32(request.mode) {
+            Some(BroadcastMode::Sync) => {
+                let response = rpc_client
+                    .broadcast_tx_sync(tx_bytes)
+                    .await
+                    .map_err(|e| Status::unavailable(e.to_string()))?;
+                TxResponse {
+                    txhash,
+                    codespace: response.codespace,
+                    code: response.code.value(),
+                    data: hex::encode_upper(&response.data),
+                    raw_log: response.log,
+                    ..TxResponse::default()
+                }
+            }
+            Some(BroadcastMode::Async) => {
+                let response = rpc_client
+                    .broadcast_tx_async(tx_bytes)
+                    .await
+                    .map_err(|e| Status::unavailable(e.to_string()))?;
+                TxResponse {
+                    txhash,
+                    codespace: response.codespace,
+                    code: response.code.value(),
+                    data: hex::encode_upper(&response.data),
+                    raw_log: response.log,
+                    ..TxResponse::default()
+                }
+            }
+            // the Go SDK's deprecated `BROADCAST_MODE_BLOCK` waits for the tx to be committed
+            Some(BroadcastMode::Block) => {
+                let response = rpc_client
+                    .broadcast_tx_commit(tx_bytes)
+                    .await
+                    .map_err(|e| Status::unavailable(e.to_string()))?;
+                if response.check_tx.code.is_err() {
+                    let check_tx = response.check_tx;
+                    TxResponse {
+                        txhash,
+                        codespace: check_tx.codespace,
+                        code: check_tx.code.value(),
+                        data: hex::encode_upper(&check_tx.data),
+                        raw_log: check_tx.log,
+                        info: check_tx.info,
+                        gas_wanted: check_tx.gas_wanted,
+                        gas_used: check_tx.gas_used,
+                        events: check_tx.events.into_iter().map(Into::into).collect(),
+                        ..TxResponse::default()
+                    }
+                } else {
+                    let deliver_tx = response.deliver_tx;
+                    TxResponse {
+                        height: response.height.value() as i64,
+                        txhash,
+                        codespace: deliver_tx.codespace,
+                        code: deliver_tx.code.value(),
+                        data: hex::encode_upper(&deliver_tx.data),
+                        raw_log: deliver_tx.log,
+                        info: deliver_tx.info,
+                        gas_wanted: deliver_tx.gas_wanted,
+                        gas_used: deliver_tx.gas_used,
+                        events: deliver_tx.events.into_iter().map(Into::into).collect(),
+                        ..TxResponse::default()
+                    }
+                }
+            }
+            _ => {
+                return Err(Status::invalid_argument(
+                    "supported modes: sync, async, block",
+                ))
+            }
+        };
+
+        Ok(Response::new(BroadcastTxResponse {
+            tx_response: Some(tx_response),
+        }))
     }
 
     async fn get_txs_event(
         &self,
-        _request: Request<GetTxsEventRequest>,
+        request: Request<GetTxsEventRequest>,
     ) -> Result<Response<GetTxsEventResponse>, Status> {
-        unimplemented!()
+        let request = request.into_inner();
+        if request.events.is_empty() {
+            return Err(Status::invalid_argument(
+                "must declare at least one event to search",
+            ));
+        }
+
+        let page = if request.page == 0 { 1 } else { request.page };
+        let limit = if request.limit == 0 {
+            DEFAULT_LIMIT
+        } else {
+            request.limit
+        };
+
+        let query = Query::from_conditions(&request.events)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let order_by = OrderBy::from_i32(request.order_by).unwrap_or(OrderBy::Unspecified);
+
+        let result = self
+            .tx_index
+            .read()
+            .unwrap()
+            .search(&query, order_by, page, limit)
+            .map_err(|e| match e {
+                Error::InvalidQuery { .. } => Status::invalid_argument(e.to_string()),
+                e => Status::internal(e.to_string()),
+            })?;
+
+        Ok(Response::new(GetTxsEventResponse {
+            txs: result
+                .txs
+                .iter()
+                .map(tx_of_response)
+                .collect::<Result<_, _>>()?,
+            tx_responses: result.txs,
+            pagination: None,
+            total: result.total_count,
+        }))
     }
 
     async fn get_block_with_txs(
         &self,
-        _request: Request<GetBlockWithTxsRequest>,
+        request: Request<GetBlockWithTxsRequest>,
     ) -> Result<Response<GetBlockWithTxsResponse>, Status> {
-        unimplemented!()
+        let request = request.into_inner();
+        let height = block_height(request.height)?;
+        let (offset, limit) = page_bounds(request.pagination).map_err(Status::invalid_argument)?;
+
+        let response = self
+            .rpc_client()?
+            .block(height)
+            .await
+            .map_err(|e| Status::not_found(e.to_string()))?;
+
+        let block_txs = response.block.data.clone();
+        let total = block_txs.len() as u64;
+        if offset >= total && total != 0 {
+            return Err(Status::invalid_argument(format!(
+                "out of range: cannot paginate {total} txs with offset {offset} and limit {limit}"
+            )));
+        }
+
+        let txs = block_txs
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|tx| decode_raw_tx(tx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new(GetBlockWithTxsResponse {
+            txs,
+            block_id: Some(response.block_id.into()),
+            block: Some(response.block.into()),
+            pagination: Some(PageResponse {
+                next_key: vec![],
+                total,
+            }),
+        }))
+    }
+}
+
+fn block_height(height: i64) -> Result<block::Height, Status> {
+    block::Height::try_from(height)
+        .ok()
+        .filter(|height| height.value() > 0)
+        .ok_or_else(|| Status::invalid_argument("height must be greater than 0"))
+}
+
+/// The cargo features the app was built with, i.e. the equivalent of the Go build tags.
+fn build_tags() -> String {
+    let mut tags = vec![];
+    if cfg!(feature = "tower-abci") {
+        tags.push("tower-abci");
+    }
+    if cfg!(feature = "v0_38") {
+        tags.push("v0_38");
+    }
+    tags.join(",")
+}
+
+mod build_info {
+    include!(concat!(env!("OUT_DIR"), "/build_deps.rs"));
+}
+
+fn decode_raw_tx(tx: &[u8]) -> Result<RawTx, Status> {
+    RawTx::decode(tx).map_err(|e| Status::internal(format!("failed to decode tx: {e}")))
+}
+
+fn tx_of_response(tx_response: &TxResponse) -> Result<RawTx, Status> {
+    let tx = tx_response
+        .tx
+        .as_ref()
+        .ok_or_else(|| Status::internal("missing tx in indexed tx response"))?;
+    decode_raw_tx(&tx.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use axum::Router;
+    use cosmos_store::memory::InMemoryStore;
+    use serde_json::{json, Value};
+    use tonic::Code;
+
+    use ibc_proto::cosmos::tx::v1beta1::TxBody;
+    use tendermint_proto::abci::{Event, EventAttribute, ResponseDeliverTx};
+
+    use crate::app::indexer::{MemTxIndexer, TxIndex};
+    use crate::app::Builder;
+
+    /// Serves the responses of a CometBFT node to the `status`, `validators`, `block` and
+    /// `broadcast_tx_*` RPC methods, answering any other method with an empty result.
+    fn mock_rpc() -> HttpClient {
+        let router = Router::new().route(
+            "/",
+            post(|body: String| async move {
+                let request: Value = serde_json::from_str(&body).unwrap();
+                let result = match request["method"].as_str() {
+                    Some("status") => status(),
+                    Some("validators") => validators(),
+                    Some("block") => block(),
+                    Some("broadcast_tx_sync") => broadcast_tx("sync"),
+                    Some("broadcast_tx_async") => broadcast_tx("async"),
+                    Some("broadcast_tx_commit") => broadcast_tx_commit(),
+                    _ => Value::Null,
+                };
+                json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string()
+            }),
+        );
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(router.into_make_service()),
+        );
+        HttpClient::new(format!("http://{addr}").as_str()).unwrap()
+    }
+
+    fn status() -> Value {
+        json!({
+            "node_info": {
+                "protocol_version": { "p2p": "8", "block": "11", "app": "1" },
+                "id": "a3d0e8c6e6eb5e0fd2f2eb9b9b2c0e1b6f46d9a3",
+                "listen_addr": "tcp://0.0.0.0:26656",
+                "network": "basecoin-test",
+                "version": "0.37.2",
+                "channels": "40202122233038606100",
+                "moniker": "mock",
+                "other": { "tx_index": "on", "rpc_address": "tcp://0.0.0.0:26657" }
+            },
+            "sync_info": {
+                "latest_block_hash": "BEEF000000000000000000000000000000000000000000000000000000000007",
+                "latest_app_hash": "",
+                "latest_block_height": "7",
+                "latest_block_time": "2023-08-01T00:00:07Z",
+                "earliest_block_hash": "BEEF000000000000000000000000000000000000000000000000000000000001",
+                "earliest_app_hash": "",
+                "earliest_block_height": "1",
+                "earliest_block_time": "2023-08-01T00:00:01Z",
+                "catching_up": true
+            },
+            "validator_info": validator(VALIDATORS[0])
+        })
+    }
+
+    fn validators() -> Value {
+        json!({
+            "block_height": "7",
+            "validators": VALIDATORS.map(validator),
+            "count": "2",
+            "total": "2"
+        })
+    }
+
+    /// Ed25519 public keys (of RFC 8032's test vectors), addresses and voting powers of the
+    /// validators of the mock node.
+    const VALIDATORS: [(&str, &str, u64); 2] = [
+        (
+            "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=",
+            "21FE31DFA154A261626BF854046FD2271B7BED4B",
+            10,
+        ),
+        (
+            "PUAXw+hDiVqStwqnTRt+vJyYLM8uxJaMwM1V8Sr0Zgw=",
+            "39F713D0A644253F04529421B9F51B9B08979D08",
+            5,
+        ),
+    ];
+
+    fn validator((pub_key, address, power): (&str, &str, u64)) -> Value {
+        json!({
+            "address": address,
+            "pub_key": { "type": "tendermint/PubKeyEd25519", "value": pub_key },
+            "voting_power": power.to_string(),
+            "proposer_priority": "0"
+        })
+    }
+
+    /// Hash of the mock node's block, also returned as the hash of the broadcast txs.
+    const HASH: &str = "BEEF000000000000000000000000000000000000000000000000000000000001";
+
+    /// Memos of the txs of the mock node's block.
+    const BLOCK_TXS: [&str; 3] = ["first", "second", "third"];
+
+    fn raw_tx(memo: &str) -> RawTx {
+        RawTx {
+            body: Some(TxBody {
+                memo: memo.to_owned(),
+                ..TxBody::default()
+            }),
+            ..RawTx::default()
+        }
+    }
+
+    fn block() -> Value {
+        let txs = BLOCK_TXS.map(|tx_memo| {
+            String::from_utf8(subtle_encoding::base64::encode(
+                raw_tx(tx_memo).encode_to_vec(),
+            ))
+            .unwrap()
+        });
+        json!({
+            "block_id": { "hash": HASH, "parts": { "total": 1, "hash": HASH } },
+            "block": {
+                "header": {
+                    "version": { "block": "11", "app": "1" },
+                    "chain_id": "basecoin-test",
+                    "height": "1",
+                    "time": "2023-08-01T00:00:01Z",
+                    "last_block_id": { "hash": "", "parts": { "total": 0, "hash": "" } },
+                    "last_commit_hash": "",
+                    "data_hash": "",
+                    "validators_hash": "",
+                    "next_validators_hash": "",
+                    "consensus_hash": "",
+                    "app_hash": "",
+                    "last_results_hash": "",
+                    "evidence_hash": "",
+                    "proposer_address": VALIDATORS[0].1
+                },
+                "data": { "txs": txs },
+                "evidence": { "evidence": [] },
+                "last_commit": null
+            }
+        })
+    }
+
+    /// Response of `broadcast_tx_sync` and `broadcast_tx_async`, logging the broadcast mode.
+    fn broadcast_tx(log: &str) -> Value {
+        json!({ "code": 0, "data": "", "log": log, "codespace": "", "hash": HASH })
+    }
+
+    fn broadcast_tx_commit() -> Value {
+        json!({
+            "check_tx": tx_result("check"),
+            "deliver_tx": tx_result("deliver"),
+            "hash": HASH,
+            "height": "7"
+        })
+    }
+
+    fn tx_result(log: &str) -> Value {
+        json!({
+            "code": 0,
+            "data": "",
+            "log": log,
+            "info": "",
+            "gas_wanted": "200",
+            "gas_used": "100",
+            "events": [{
+                "type": "transfer",
+                "attributes": [{ "key": "amount", "value": "5basecoin", "index": true }]
+            }],
+            "codespace": "",
+            "sender": "",
+            "priority": "0",
+            "mempool_error": ""
+        })
+    }
+
+    /// Returns an index of the txs of blocks 1 to 3, each delivering one tx per height
+    /// transferring `height` coins.
+    fn tx_index() -> TxIndex {
+        let mut tx_index = TxIndex::new(MemTxIndexer::default());
+        for height in 1..=3 {
+            tx_index.begin_block(height, format!("2023-08-01T00:00:0{height}Z"));
+            tx_index.record(
+                raw_tx(&height.to_string()).encode_to_vec(),
+                ResponseDeliverTx {
+                    gas_used: 100,
+                    events: vec![Event {
+                        r#type: "transfer".to_owned(),
+                        attributes: vec![EventAttribute {
+                            key: "amount".to_owned(),
+                            value: format!("{height}basecoin"),
+                            index: true,
+                        }],
+                    }],
+                    ..ResponseDeliverTx::default()
+                },
+            );
+            tx_index.commit().unwrap();
+        }
+        tx_index
+    }
+
+    fn memo(tx: &RawTx) -> &str {
+        &tx.body.as_ref().unwrap().memo
+    }
+
+    fn app(rpc_client: Option<HttpClient>) -> BaseCoinApp<InMemoryStore> {
+        let builder = Builder::new(InMemoryStore::default());
+        match rpc_client {
+            Some(rpc_client) => builder.set_rpc_client(rpc_client),
+            None => builder,
+        }
+        .build()
+    }
+
+    #[tokio::test]
+    async fn test_get_node_info() {
+        let response = app(Some(mock_rpc()))
+            .get_node_info(Request::new(GetNodeInfoRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let node_info = response.default_node_info.unwrap();
+        assert_eq!(
+            node_info.default_node_id,
+            "a3d0e8c6e6eb5e0fd2f2eb9b9b2c0e1b6f46d9a3"
+        );
+        assert_eq!(node_info.network, "basecoin-test");
+        assert_eq!(node_info.moniker, "mock");
+        assert_eq!(node_info.protocol_version.unwrap().block, 11);
+        assert_eq!(node_info.other.unwrap().tx_index, "on");
+
+        let version = response.application_version.unwrap();
+        assert_eq!(version.app_name, "basecoin");
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(version.git_commit, env!("BASECOIN_GIT_COMMIT"));
+        assert!(version.build_deps.iter().any(|dep| dep.path == "tonic"));
+    }
+
+    #[tokio::test]
+    async fn test_get_syncing() {
+        let response = app(Some(mock_rpc()))
+            .get_syncing(Request::new(GetSyncingRequest {}))
+            .await
+            .unwrap();
+        assert!(response.into_inner().syncing);
+    }
+
+    #[tokio::test]
+    async fn test_get_validator_set() {
+        let app = app(Some(mock_rpc()));
+        let response = app
+            .get_latest_validator_set(Request::new(GetLatestValidatorSetRequest {
+                pagination: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.block_height, 7);
+        let powers: Vec<_> = response.validators.iter().map(|v| v.voting_power).collect();
+        assert_eq!(powers, [10, 5]);
+        assert_eq!(
+            response.validators[0].address,
+            ConsAddress::from(VALIDATORS[0].1.parse::<tendermint::account::Id>().unwrap())
+                .to_string()
+        );
+        assert!(response.validators[0].pub_key.is_some());
+
+        let response = app
+            .get_validator_set_by_height(Request::new(GetValidatorSetByHeightRequest {
+                height: 7,
+                pagination: Some(PageRequest {
+                    offset: 1,
+                    limit: 1,
+                    ..PageRequest::default()
+                }),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.validators.len(), 1);
+        assert_eq!(response.validators[0].voting_power, 5);
+        assert_eq!(response.pagination.unwrap().total, 2);
+
+        for (height, reverse) in [(0, false), (7, true)] {
+            let status = app
+                .get_validator_set_by_height(Request::new(GetValidatorSetByHeightRequest {
+                    height,
+                    pagination: Some(PageRequest {
+                        reverse,
+                        ..PageRequest::default()
+                    }),
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_without_rpc_client() {
+        let app = app(None);
+        let status = app
+            .get_syncing(Request::new(GetSyncingRequest {}))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Unavailable);
+
+        let status = app
+            .abci_query(Request::new(AbciQueryRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_tx() {
+        let app = app(Some(mock_rpc()));
+        let tx_bytes = raw_tx("broadcast").encode_to_vec();
+        let broadcast = |mode: BroadcastMode| {
+            app.broadcast_tx(Request::new(BroadcastTxRequest {
+                tx_bytes: tx_bytes.clone(),
+                mode: mode as i32,
+            }))
+        };
+
+        for (mode, log) in [
+            (BroadcastMode::Sync, "sync"),
+            (BroadcastMode::Async, "async"),
+        ] {
+            let tx_response = broadcast(mode)
+                .await
+                .unwrap()
+                .into_inner()
+                .tx_response
+                .unwrap();
+            assert_eq!(tx_response.txhash, tx_hash(&tx_bytes));
+            assert_eq!(tx_response.code, 0);
+            assert_eq!(tx_response.raw_log, log);
+            assert_eq!(tx_response.height, 0);
+        }
+
+        let tx_response = broadcast(BroadcastMode::Block)
+            .await
+            .unwrap()
+            .into_inner()
+            .tx_response
+            .unwrap();
+        assert_eq!(tx_response.txhash, tx_hash(&tx_bytes));
+        assert_eq!(tx_response.height, 7);
+        assert_eq!(tx_response.raw_log, "deliver");
+        assert_eq!((tx_response.gas_wanted, tx_response.gas_used), (200, 100));
+        assert_eq!(tx_response.events.len(), 1);
+        assert_eq!(tx_response.events[0].r#type, "transfer");
+
+        let status = broadcast(BroadcastMode::Unspecified).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_with_txs() {
+        let app = app(Some(mock_rpc()));
+        let get_block_with_txs = |offset, limit| {
+            app.get_block_with_txs(Request::new(GetBlockWithTxsRequest {
+                height: 1,
+                pagination: Some(PageRequest {
+                    offset,
+                    limit,
+                    ..PageRequest::default()
+                }),
+            }))
+        };
+
+        let response = get_block_with_txs(0, 0).await.unwrap().into_inner();
+        assert_eq!(response.txs.iter().map(memo).collect::<Vec<_>>(), BLOCK_TXS);
+        assert_eq!(response.pagination.unwrap().total, 3);
+        assert!(response.block.is_some());
+        assert!(response.block_id.is_some());
+
+        let response = get_block_with_txs(1, 1).await.unwrap().into_inner();
+        assert_eq!(
+            response.txs.iter().map(memo).collect::<Vec<_>>(),
+            ["second"]
+        );
+        assert_eq!(response.pagination.unwrap().total, 3);
+
+        let response = get_block_with_txs(2, 5).await.unwrap().into_inner();
+        assert_eq!(response.txs.iter().map(memo).collect::<Vec<_>>(), ["third"]);
+
+        let status = get_block_with_txs(3, 1).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_get_tx() {
+        let app = Builder::new(InMemoryStore::default())
+            .set_tx_index(tx_index())
+            .build();
+        let hash = tx_hash(&raw_tx("2").encode_to_vec());
+
+        // hashes are looked up case-insensitively
+        let response = app
+            .get_tx(Request::new(GetTxRequest {
+                hash: hash.to_ascii_lowercase(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let tx_response = response.tx_response.unwrap();
+        assert_eq!(tx_response.txhash, hash);
+        assert_eq!(tx_response.height, 2);
+        assert_eq!(tx_response.gas_used, 100);
+        assert_eq!(tx_response.timestamp, "2023-08-01T00:00:02Z");
+        assert_eq!(memo(&response.tx.unwrap()), "2");
+
+        let status = app
+            .get_tx(Request::new(GetTxRequest {
+                hash: tx_hash(b"unknown"),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = app
+            .get_tx(Request::new(GetTxRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_get_txs_event() {
+        let app = Builder::new(InMemoryStore::default())
+            .set_tx_index(tx_index())
+            .build();
+        let get_txs_event = |events: &[&str], order_by: OrderBy, page, limit| {
+            app.get_txs_event(Request::new(GetTxsEventRequest {
+                events: events.iter().map(ToString::to_string).collect(),
+                order_by: order_by as i32,
+                page,
+                limit,
+                ..GetTxsEventRequest::default()
+            }))
+        };
+
+        let response = get_txs_event(&["tx.height>1"], OrderBy::Asc, 0, 0)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.total, 2);
+        assert_eq!(
+            response.txs.iter().map(memo).collect::<Vec<_>>(),
+            ["2", "3"]
+        );
+        let heights: Vec<_> = response.tx_responses.iter().map(|tx| tx.height).collect();
+        assert_eq!(heights, [2, 3]);
+
+        let response = get_txs_event(&["transfer.amount EXISTS"], OrderBy::Desc, 2, 2)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.total, 3);
+        assert_eq!(response.txs.iter().map(memo).collect::<Vec<_>>(), ["1"]);
+
+        let response = get_txs_event(&["transfer.amount='3basecoin'"], OrderBy::Unspecified, 1, 1)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.tx_responses[0].height, 3);
+
+        for (events, page) in [
+            (&[][..], 1),
+            (&["tx.height"][..], 1),
+            (&["tx.height>0"][..], 3),
+        ] {
+            let status = get_txs_event(events, OrderBy::Asc, page, 2)
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
     }
 }
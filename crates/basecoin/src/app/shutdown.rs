@@ -0,0 +1,166 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Why the node is shutting down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// The process received SIGINT (e.g. Ctrl-C)
+    Interrupt,
+    /// The process received SIGTERM
+    Terminate,
+    /// The block at the configured halt height was committed
+    HaltHeight(u64),
+    /// A block with a time at or after the configured halt time (in UNIX seconds) was committed
+    HaltTime(u64),
+    /// One of the node's servers failed
+    Failure,
+}
+
+impl ShutdownReason {
+    /// Exit code of the process: `0` when halting as configured, `128` + the signal number on a
+    /// signal (as shells report processes killed by a signal) and `1` on failure.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ShutdownReason::Interrupt => 130,
+            ShutdownReason::Terminate => 143,
+            ShutdownReason::HaltHeight(_) | ShutdownReason::HaltTime(_) => 0,
+            ShutdownReason::Failure => 1,
+        }
+    }
+}
+
+impl Display for ShutdownReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownReason::Interrupt => write!(f, "received SIGINT"),
+            ShutdownReason::Terminate => write!(f, "received SIGTERM"),
+            ShutdownReason::HaltHeight(height) => write!(f, "reached halt height {height}"),
+            ShutdownReason::HaltTime(time) => write!(f, "reached halt time {time}"),
+            ShutdownReason::Failure => write!(f, "a server failed"),
+        }
+    }
+}
+
+/// Lets any part of the node request a shutdown and the servers wait for it.
+#[derive(Clone, Debug)]
+pub struct Shutdown {
+    sender: Arc<watch::Sender<Option<ShutdownReason>>>,
+    receiver: watch::Receiver<Option<ShutdownReason>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, receiver) = watch::channel(None);
+        Self {
+            sender: Arc::new(sender),
+            receiver,
+        }
+    }
+
+    /// Requests a shutdown. Only the first request is retained.
+    pub fn trigger(&self, reason: ShutdownReason) {
+        self.sender.send_if_modified(|current| {
+            if current.is_none() {
+                *current = Some(reason);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Returns the reason of the requested shutdown, if any.
+    pub fn reason(&self) -> Option<ShutdownReason> {
+        *self.receiver.borrow()
+    }
+
+    /// Waits until a shutdown is requested.
+    pub async fn wait(&self) -> ShutdownReason {
+        let mut receiver = self.receiver.clone();
+        loop {
+            if let Some(reason) = *receiver.borrow_and_update() {
+                return reason;
+            }
+            // the sender lives as long as `self`, so this can't fail
+            let _ = receiver.changed().await;
+        }
+    }
+
+    /// Requests a shutdown when the process receives SIGINT or SIGTERM.
+    pub fn listen_for_signals(&self) {
+        let shutdown = self.clone();
+        tokio::spawn(async move {
+            let reason = signal().await;
+            shutdown.trigger(reason);
+        });
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(unix)]
+async fn signal() -> ShutdownReason {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut interrupt = signal(SignalKind::interrupt()).expect("failed to listen for SIGINT");
+    let mut terminate = signal(SignalKind::terminate()).expect("failed to listen for SIGTERM");
+    tokio::select! {
+        _ = interrupt.recv() => ShutdownReason::Interrupt,
+        _ = terminate.recv() => ShutdownReason::Terminate,
+    }
+}
+
+#[cfg(not(unix))]
+async fn signal() -> ShutdownReason {
+    tokio::signal::ctrl_c()
+        .await
+        .expect("failed to listen for Ctrl-C");
+    ShutdownReason::Interrupt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(ShutdownReason::Interrupt.exit_code(), 130);
+        assert_eq!(ShutdownReason::Terminate.exit_code(), 143);
+        assert_eq!(ShutdownReason::HaltHeight(10).exit_code(), 0);
+        assert_eq!(ShutdownReason::HaltTime(1_000).exit_code(), 0);
+        assert_eq!(ShutdownReason::Failure.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_first_reason_wins() {
+        let shutdown = Shutdown::new();
+        assert_eq!(shutdown.reason(), None);
+
+        let clone = shutdown.clone();
+        clone.trigger(ShutdownReason::HaltHeight(10));
+        shutdown.trigger(ShutdownReason::Terminate);
+        assert_eq!(shutdown.reason(), Some(ShutdownReason::HaltHeight(10)));
+        assert_eq!(clone.reason(), Some(ShutdownReason::HaltHeight(10)));
+    }
+
+    #[tokio::test]
+    async fn test_wait() {
+        let shutdown = Shutdown::new();
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.wait().await }
+        });
+        shutdown.trigger(ShutdownReason::Failure);
+        shutdown.trigger(ShutdownReason::Interrupt);
+        assert_eq!(waiter.await.unwrap(), ShutdownReason::Failure);
+
+        // a shutdown that was already requested is returned right away
+        assert_eq!(shutdown.wait().await, ShutdownReason::Failure);
+    }
+}
@@ -29,9 +29,25 @@ pub struct BasecoinCli {
 
 #[derive(Clone, Debug, Parser)]
 pub enum Commands {
-    Start,
+    Start(StartCmd),
     #[command(subcommand)]
     Query(QueryCmd),
+    #[command(subcommand)]
+    Genesis(GenesisCmd),
+}
+
+#[derive(Clone, Debug, Parser)]
+#[command(about = "Run the Basecoin application until it's interrupted or halted")]
+pub struct StartCmd {
+    /// Shut down the node once the block at this height is committed (overrides
+    /// `server.halt_height` in the configuration file).
+    #[arg(long, value_name = "HEIGHT")]
+    pub halt_height: Option<u64>,
+
+    /// Shut down the node once a block with a time at or after this UNIX time (in seconds) is
+    /// committed (overrides `server.halt_time` in the configuration file).
+    #[arg(long, value_name = "UNIX_TIME")]
+    pub halt_time: Option<u64>,
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -41,6 +57,20 @@ pub enum QueryCmd {
     Upgrade(UpgradeCmd),
 }
 
+#[derive(Clone, Debug, Parser)]
+#[command(about = "Genesis file related commands")]
+pub enum GenesisCmd {
+    Validate(ValidateGenesisCmd),
+}
+
+#[derive(Clone, Debug, Parser)]
+#[command(about = "Validate the app state of a genesis file against the modules of the app")]
+pub struct ValidateGenesisCmd {
+    /// The path to the genesis file.
+    #[arg(value_name = "FILE", default_value = "genesis.json")]
+    pub file: PathBuf,
+}
+
 #[derive(Clone, Debug, Parser)]
 #[command(about = "Query commands for the upgrade module")]
 pub enum UpgradeCmd {
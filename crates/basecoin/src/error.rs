@@ -1,7 +1,14 @@
 use cosmos_helper::error::Error as HelperError;
+use cosmos_x_auth::error::Error as AuthError;
 use cosmos_x_bank::error::Error as BankError;
 use cosmos_x_gov::error::Error as GovError;
 use cosmos_x_ibc::error::Error as IbcError;
+use cosmos_x_module_api::error::{
+    AbciError, Error as ModuleError, RegisteredError, ERR_INTERNAL, ERR_INVALID_PUBKEY,
+    ERR_INVALID_REQUEST, ERR_IO, ERR_KEY_NOT_FOUND, ERR_LOGIC, ERR_NO_SIGNATURES, ERR_TX_DECODE,
+    ERR_UNAUTHORIZED, ERR_UNKNOWN_REQUEST,
+};
+use cosmos_x_upgrade::error::Error as UpgradeError;
 use ibc::core::ContextError;
 use thiserror::Error;
 
@@ -9,8 +16,30 @@ use thiserror::Error;
 pub enum Error {
     #[error("no module could handle specified message")]
     NotHandled,
+    #[error("no message handler found for {type_url}: unknown request")]
+    UnknownMessageType { type_url: String },
+    #[error("message type URL `{type_url}` is already handled by the `{module}` module")]
+    DuplicateMsgRoute { type_url: String, module: String },
+    #[error("failed to decode incoming tx bytes: {reason}")]
+    TxDecode { reason: String },
+    #[error("empty signers")]
+    NoSignatures,
+    #[error("invalid signer: {reason}")]
+    InvalidSigner { reason: String },
+    #[error("must contain at least one message")]
+    EmptyTx,
+    #[error("invalid query: {reason}")]
+    InvalidQuery { reason: String },
+    #[error("tx indexer error: {reason}")]
+    TxIndexer { reason: String },
+    #[error("invalid app config: {reason}")]
+    AppConfig { reason: String },
+    #[error("invalid genesis state of the `{module}` module: {reason}")]
+    InvalidGenesis { module: String, reason: String },
     #[error("custom error: `{reason}`")]
     Custom { reason: String },
+    #[error("{0}")]
+    Module(anyhow::Error),
     #[error("helper error: `{0}`")]
     Helper(HelperError),
     #[error("bank module error: `{0}`")]
@@ -50,3 +79,104 @@ impl From<BankError> for Error {
         Self::Bank(value)
     }
 }
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::NotHandled | Error::UnknownMessageType { .. } => ERR_UNKNOWN_REQUEST,
+            Error::DuplicateMsgRoute { .. } => ERR_LOGIC,
+            Error::TxDecode { .. } => ERR_TX_DECODE,
+            Error::NoSignatures => ERR_NO_SIGNATURES,
+            Error::InvalidSigner { .. } => ERR_INVALID_PUBKEY,
+            Error::EmptyTx => ERR_INVALID_REQUEST,
+            Error::InvalidQuery { .. } => ERR_INVALID_REQUEST,
+            Error::TxIndexer { .. } => ERR_IO,
+            Error::AppConfig { .. } => ERR_LOGIC,
+            Error::InvalidGenesis { .. } => ERR_INVALID_REQUEST,
+            Error::Custom { .. } => ERR_INTERNAL,
+            Error::Module(e) => module_registered_error(e),
+            Error::Helper(_) => ERR_INVALID_REQUEST,
+            Error::Bank(e) => e.registered_error(),
+            Error::Ibc(e) => e.registered_error(),
+            Error::Gov(e) => e.registered_error(),
+        }
+    }
+}
+
+/// Maps the status of a failed gRPC query to a registered error, the same way the Go SDK does.
+pub fn grpc_status_registered_error(status: &tonic::Status) -> RegisteredError {
+    match status.code() {
+        tonic::Code::InvalidArgument => ERR_INVALID_REQUEST,
+        tonic::Code::Unauthenticated => ERR_UNAUTHORIZED,
+        tonic::Code::NotFound => ERR_KEY_NOT_FOUND,
+        _ => ERR_UNKNOWN_REQUEST,
+    }
+}
+
+/// Returns whether a module's error means that the module didn't handle the request, i.e. that it
+/// wraps the `NotHandled` error of any of the modules.
+pub fn is_not_handled(e: &anyhow::Error) -> bool {
+    matches!(e.downcast_ref::<AuthError>(), Some(AuthError::NotHandled))
+        || matches!(e.downcast_ref::<BankError>(), Some(BankError::NotHandled))
+        || matches!(e.downcast_ref::<GovError>(), Some(GovError::NotHandled))
+        || matches!(e.downcast_ref::<IbcError>(), Some(IbcError::NotHandled))
+        || matches!(
+            e.downcast_ref::<UpgradeError>(),
+            Some(UpgradeError::NotHandled)
+        )
+        || matches!(
+            e.downcast_ref::<ModuleError>(),
+            Some(ModuleError::NotHandled)
+        )
+        || matches!(e.downcast_ref::<Error>(), Some(Error::NotHandled))
+}
+
+// modules return `anyhow` errors, so find out which module error type (if any) it wraps
+fn module_registered_error(e: &anyhow::Error) -> RegisteredError {
+    if let Some(e) = e.downcast_ref::<AuthError>() {
+        e.registered_error()
+    } else if let Some(e) = e.downcast_ref::<BankError>() {
+        e.registered_error()
+    } else if let Some(e) = e.downcast_ref::<GovError>() {
+        e.registered_error()
+    } else if let Some(e) = e.downcast_ref::<IbcError>() {
+        e.registered_error()
+    } else if let Some(e) = e.downcast_ref::<UpgradeError>() {
+        e.registered_error()
+    } else if let Some(e) = e.downcast_ref::<ModuleError>() {
+        e.registered_error()
+    } else if let Some(e) = e.downcast_ref::<Error>() {
+        e.registered_error()
+    } else {
+        ERR_INTERNAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_x_module_api::error::validate_registered_errors;
+
+    #[test]
+    fn test_registered_errors() {
+        // the errors of all the crates share the same (codespace, code) space
+        validate_registered_errors(
+            cosmos_x_module_api::error::REGISTERED_ERRORS
+                .iter()
+                .chain(cosmos_x_gov::error::REGISTERED_ERRORS),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_is_not_handled() {
+        assert!(is_not_handled(&AuthError::NotHandled.into()));
+        assert!(is_not_handled(&BankError::NotHandled.into()));
+        assert!(is_not_handled(&IbcError::NotHandled.into()));
+        assert!(is_not_handled(&ModuleError::NotHandled.into()));
+        assert!(is_not_handled(&Error::NotHandled.into()));
+
+        assert!(!is_not_handled(&Error::EmptyTx.into()));
+        assert!(!is_not_handled(&anyhow::anyhow!("not handled")));
+    }
+}
@@ -1,5 +1,5 @@
 use serde_derive::{Deserialize, Serialize};
-pub use std::path::Path;
+pub use std::path::{Path, PathBuf};
 use tendermint_rpc::Url;
 use tracing_subscriber::filter::LevelFilter;
 
@@ -10,11 +10,21 @@ pub struct Config {
     pub global: GlobalConfig,
     pub server: ServerConfig,
     pub cometbft: CometbftConfig,
+    #[serde(default)]
+    pub indexer: IndexerConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub address: AddressConfig,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GlobalConfig {
     pub log_level: LogLevel,
+    /// Path of the app config (TOML, or JSON with a `.json` extension) listing the modules the
+    /// app is composed of, the basecoin modules by default
+    #[serde(default)]
+    pub app_config: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -44,6 +54,13 @@ pub struct ServerConfig {
     pub port: u16,
     pub grpc_port: u16,
     pub read_buf_size: usize,
+    /// Height of the block after which the node shuts down; `0` disables halting at a height
+    #[serde(default)]
+    pub halt_height: u64,
+    /// UNIX time (in seconds) of the first block after which the node shuts down; `0` disables
+    /// halting at a time
+    #[serde(default)]
+    pub halt_time: u64,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -52,6 +69,95 @@ pub struct CometbftConfig {
     pub grpc_addr: Url,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IndexerConfig {
+    pub indexer: IndexerKind,
+    /// Directory of the database of the `kv` indexer
+    pub db_path: PathBuf,
+}
+
+impl Default for IndexerConfig {
+    fn default() -> Self {
+        Self {
+            indexer: IndexerKind::Kv,
+            db_path: PathBuf::from("data/tx_index.db"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiConfig {
+    /// Whether the REST server is started
+    pub enable: bool,
+    pub host: String,
+    pub port: u16,
+    /// Whether the OpenAPI document is served at `/swagger/openapi.json`
+    pub swagger: bool,
+    /// Whether cross-origin requests are accepted from any origin
+    pub enabled_unsafe_cors: bool,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            host: "127.0.0.1".to_string(),
+            port: 1317,
+            swagger: false,
+            enabled_unsafe_cors: false,
+        }
+    }
+}
+
+/// Bech32 prefixes of the chain's addresses and public keys, along with the BIP-44 purpose and
+/// coin type of its keys. The prefixes that aren't set are derived from the main prefix, e.g.
+/// `cosmosvaloper` for validators.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AddressConfig {
+    /// Main prefix, i.e. the prefix of account addresses
+    pub bech32_prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_pub_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validator_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validator_pub_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consensus_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consensus_pub_prefix: Option<String>,
+    pub purpose: u32,
+    pub coin_type: u32,
+}
+
+impl Default for AddressConfig {
+    fn default() -> Self {
+        Self {
+            bech32_prefix: "cosmos".to_string(),
+            account_pub_prefix: None,
+            validator_prefix: None,
+            validator_pub_prefix: None,
+            consensus_prefix: None,
+            consensus_pub_prefix: None,
+            purpose: 44,
+            coin_type: 118,
+        }
+    }
+}
+
+/// Backend used to index the transactions delivered by the app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexerKind {
+    /// Transactions aren't indexed
+    Null,
+    /// Transactions are indexed in memory, without bound, and lost on restart
+    Memory,
+    /// Transactions are indexed in an embedded database
+    Kv,
+}
+
 /// Attempt to load and parse the TOML config file as a `Config`.
 pub fn load_config(path: impl AsRef<Path>) -> anyhow::Result<Config> {
     let config_toml = std::fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("{e:?}"))?;
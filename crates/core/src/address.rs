@@ -1,9 +1,11 @@
 pub mod codec {
     // Codec defines an interface to convert addresses from and to string/bytes.
     pub trait Codec {
+        type Error;
+
         /// StringToBytes decodes text to bytes
-        fn string_to_bytes(&self, text: &str) -> Vec<u8>;
+        fn string_to_bytes(&self, text: &str) -> Result<Vec<u8>, Self::Error>;
         /// BytesToString encodes bytes to text
-        fn bytes_to_string(&self, bytes: &[u8]) -> String;
+        fn bytes_to_string(&self, bytes: &[u8]) -> Result<String, Self::Error>;
     }
 }
@@ -1,126 +1,331 @@
-// //! Package appconfig defines functionality for loading declarative Cosmos SDK
-// //! app configurations.
-
-// package appconfig
-
-// import (
-// 	"fmt"
-// 	"strings"
-
-// 	"github.com/cosmos/cosmos-proto/anyutil"
-// 	"google.golang.org/protobuf/encoding/protojson"
-// 	"google.golang.org/protobuf/proto"
-// 	protoreflect "google.golang.org/protobuf/reflect/protoreflect"
-// 	"google.golang.org/protobuf/reflect/protoregistry"
-// 	"google.golang.org/protobuf/types/known/anypb"
-// 	"sigs.k8s.io/yaml"
-
-// 	appv1alpha1 "cosmossdk.io/api/cosmos/app/v1alpha1"
-// 	"cosmossdk.io/core/internal"
-// 	"cosmossdk.io/depinject"
-// )
-
-// // LoadJSON loads an app config in JSON format.
-// func LoadJSON(bz []byte) depinject.Config {
-// 	config := &appv1alpha1.Config{}
-// 	err := protojson.Unmarshal(bz, config)
-// 	if err != nil {
-// 		return depinject.Error(err)
-// 	}
-
-// 	return Compose(config)
-// }
-
-// // LoadYAML loads an app config in YAML format.
-// func LoadYAML(bz []byte) depinject.Config {
-// 	j, err := yaml.YAMLToJSON(bz)
-// 	if err != nil {
-// 		return depinject.Error(err)
-// 	}
-
-// 	return LoadJSON(j)
-// }
-
-// // WrapAny marshals a proto message into a proto Any instance
-// func WrapAny(config protoreflect.ProtoMessage) *anypb.Any {
-// 	cfg, err := anyutil.New(config)
-// 	if err != nil {
-// 		panic(err)
-// 	}
-
-// 	return cfg
-// }
-
-// // Compose composes a v1alpha1 app config into a container option by resolving
-// // the required modules and composing their options.
-// func Compose(appConfig *appv1alpha1.Config) depinject.Config {
-// 	opts := []depinject.Config{
-// 		depinject.Supply(appConfig),
-// 	}
-
-// 	for _, module := range appConfig.Modules {
-// 		if module.Name == "" {
-// 			return depinject.Error(fmt.Errorf("module is missing name"))
-// 		}
-
-// 		if module.Config == nil {
-// 			return depinject.Error(fmt.Errorf("module %q is missing a config object", module.Name))
-// 		}
-
-// 		msgType, err := protoregistry.GlobalTypes.FindMessageByURL(module.Config.TypeUrl)
-// 		if err != nil {
-// 			return depinject.Error(err)
-// 		}
-
-// 		modules, err := internal.ModulesByProtoMessageName()
-// 		if err != nil {
-// 			return depinject.Error(err)
-// 		}
-
-// 		init, ok := modules[msgType.Descriptor().FullName()]
-// 		if !ok {
-// 			modDesc := proto.GetExtension(msgType.Descriptor().Options(), appv1alpha1.E_Module).(*appv1alpha1.ModuleDescriptor)
-// 			if modDesc == nil {
-// 				return depinject.Error(fmt.Errorf("no module registered for type URL %s and that protobuf type does not have the option %s\n\n%s",
-// 					module.Config.TypeUrl, appv1alpha1.E_Module.TypeDescriptor().FullName(), dumpRegisteredModules(modules)))
-// 			}
-
-// 			return depinject.Error(fmt.Errorf("no module registered for type URL %s, did you forget to import %s: find more information on how to make a module ready for app wiring: https://docs.cosmos.network/main/building-modules/depinject\n\n%s",
-// 				module.Config.TypeUrl, modDesc.GoImport, dumpRegisteredModules(modules)))
-// 		}
-
-// 		config := init.ConfigProtoMessage.ProtoReflect().Type().New().Interface()
-// 		err = anypb.UnmarshalTo(module.Config, config, proto.UnmarshalOptions{})
-// 		if err != nil {
-// 			return depinject.Error(err)
-// 		}
-
-// 		opts = append(opts, depinject.Supply(config))
-
-// 		for _, provider := range init.Providers {
-// 			opts = append(opts, depinject.ProvideInModule(module.Name, provider))
-// 		}
-
-// 		for _, invoker := range init.Invokers {
-// 			opts = append(opts, depinject.InvokeInModule(module.Name, invoker))
-// 		}
-
-// 		for _, binding := range module.GolangBindings {
-// 			opts = append(opts, depinject.BindInterfaceInModule(module.Name, binding.InterfaceType, binding.Implementation))
-// 		}
-// 	}
-
-// 	for _, binding := range appConfig.GolangBindings {
-// 		opts = append(opts, depinject.BindInterface(binding.InterfaceType, binding.Implementation))
-// 	}
-
-// 	return depinject.Configs(opts...)
-// }
-
-// func dumpRegisteredModules(modules map[protoreflect.FullName]*internal.ModuleInitializer) string {
-// 	var mods []string
-// 	for name := range modules {
-// 		mods = append(mods, "  "+string(name))
-// 	}
-// 	return fmt.Sprintf("registered modules are:\n%s", strings.Join(mods, "\n"))
-// }
+//! Package appconfig defines the declarative app configuration, i.e. the modules an app is
+//! composed of, their store keys and params, and the order their hooks are called in, loaded from
+//! TOML or JSON (the counterpart of the Go SDK's `cosmos.app.v1alpha1.Config`).
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Declarative configuration of the modules of an app.
+///
+/// The modules are instantiated in the order they're listed, so a module must be listed after the
+/// modules it depends on (e.g. `bank` after `auth`). When left empty, `init_genesis` defaults to
+/// that order and `begin_blockers`/`end_blockers` default to the `init_genesis` order, otherwise
+/// they must list every module exactly once.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppConfig {
+    pub modules: Vec<ModuleConfig>,
+    /// Order in which the modules initialize their genesis state
+    #[serde(default)]
+    pub init_genesis: Vec<String>,
+    /// Order in which the modules' `BeginBlock` hooks are called
+    #[serde(default)]
+    pub begin_blockers: Vec<String>,
+    /// Order in which the modules' `EndBlock` hooks are called
+    #[serde(default)]
+    pub end_blockers: Vec<String>,
+}
+
+/// Configuration of a single module.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModuleConfig {
+    /// Name the module is registered under (e.g. `bank`)
+    pub name: String,
+    /// Key of the module's store, defaults to the module's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_key: Option<String>,
+    /// Module specific params, interpreted by the module
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+impl AppConfig {
+    /// Loads an app config in JSON format.
+    pub fn load_json(json: &str) -> Result<Self> {
+        let config: Self = serde_json::from_str(json)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads an app config in TOML format.
+    pub fn load_toml(toml: &str) -> Result<Self> {
+        let config: Self = toml::from_str(toml)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads an app config from a file, in JSON format if its extension is `.json` and in TOML
+    /// format otherwise.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read app config {}: {e}", path.display()))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::load_json(&content),
+            _ => Self::load_toml(&content),
+        }
+    }
+
+    /// Checks that module names and store keys are unique and that the hook orders list every
+    /// module exactly once.
+    pub fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        let mut store_keys = HashSet::new();
+        for module in &self.modules {
+            if module.name.is_empty() {
+                bail!("module is missing name");
+            }
+            if !names.insert(module.name.as_str()) {
+                bail!("module `{}` is listed more than once", module.name);
+            }
+            if !store_keys.insert(module.store_key()) {
+                bail!(
+                    "store key `{}` of module `{}` is already used by another module",
+                    module.store_key(),
+                    module.name
+                );
+            }
+        }
+
+        for (kind, order) in [
+            ("init_genesis", &self.init_genesis),
+            ("begin_blockers", &self.begin_blockers),
+            ("end_blockers", &self.end_blockers),
+        ] {
+            if order.is_empty() {
+                continue;
+            }
+            let mut ordered = HashSet::new();
+            for name in order {
+                if !names.contains(name.as_str()) {
+                    bail!("{kind} lists unknown module `{name}`");
+                }
+                if !ordered.insert(name.as_str()) {
+                    bail!("{kind} lists module `{name}` more than once");
+                }
+            }
+            let mut missing: Vec<_> = names.difference(&ordered).copied().collect();
+            if !missing.is_empty() {
+                missing.sort_unstable();
+                bail!("{kind} is missing modules: {}", missing.join(", "));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the config of the module with the specified name.
+    pub fn module(&self, name: &str) -> Option<&ModuleConfig> {
+        self.modules.iter().find(|module| module.name == name)
+    }
+
+    /// Order in which the modules initialize their genesis state.
+    pub fn init_genesis_order(&self) -> Vec<&str> {
+        self.order(&self.init_genesis, &[])
+    }
+
+    /// Order in which the modules' `BeginBlock` hooks are called.
+    pub fn begin_blockers_order(&self) -> Vec<&str> {
+        self.order(&self.begin_blockers, &self.init_genesis)
+    }
+
+    /// Order in which the modules' `EndBlock` hooks are called.
+    pub fn end_blockers_order(&self) -> Vec<&str> {
+        self.order(&self.end_blockers, &self.init_genesis)
+    }
+
+    fn order<'a>(&'a self, order: &'a [String], fallback: &'a [String]) -> Vec<&'a str> {
+        if !order.is_empty() {
+            order.iter().map(String::as_str).collect()
+        } else if !fallback.is_empty() {
+            fallback.iter().map(String::as_str).collect()
+        } else {
+            self.modules
+                .iter()
+                .map(|module| module.name.as_str())
+                .collect()
+        }
+    }
+}
+
+impl ModuleConfig {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            store_key: None,
+            params: Value::Null,
+        }
+    }
+
+    /// Key of the module's store.
+    pub fn store_key(&self) -> &str {
+        self.store_key.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Decodes the module's params, or returns the default params if none are configured.
+    pub fn params<T: DeserializeOwned + Default>(&self) -> Result<T> {
+        if self.params.is_null() {
+            return Ok(T::default());
+        }
+        serde_json::from_value(self.params.clone())
+            .map_err(|e| anyhow!("invalid params for module `{}`: {e}", self.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The app config shipped with basecoin.
+    const APP_TOML: &str = include_str!("../../../app.toml");
+
+    fn config(modules: &[&str]) -> AppConfig {
+        AppConfig {
+            modules: modules.iter().copied().map(ModuleConfig::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn order(names: &[&str]) -> Vec<String> {
+        names.iter().copied().map(String::from).collect()
+    }
+
+    fn assert_invalid(config: &AppConfig, error: &str) {
+        let e = config.validate().unwrap_err();
+        assert!(e.to_string().contains(error), "unexpected error: {e}");
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut valid = config(&["auth", "bank", "gov"]);
+        valid.validate().unwrap();
+        valid.init_genesis = order(&["bank", "auth", "gov"]);
+        valid.end_blockers = order(&["gov", "bank", "auth"]);
+        valid.validate().unwrap();
+
+        assert_invalid(&config(&["auth", ""]), "module is missing name");
+        assert_invalid(
+            &config(&["auth", "auth"]),
+            "`auth` is listed more than once",
+        );
+
+        let mut same_store_key = config(&["auth", "bank"]);
+        same_store_key.modules[1].store_key = Some("auth".to_owned());
+        assert_invalid(&same_store_key, "store key `auth` of module `bank`");
+
+        let mut unknown = valid.clone();
+        unknown.begin_blockers = order(&["auth", "bank", "gov", "mint"]);
+        assert_invalid(&unknown, "begin_blockers lists unknown module `mint`");
+
+        let mut duplicate = valid.clone();
+        duplicate.init_genesis = order(&["auth", "bank", "gov", "bank"]);
+        assert_invalid(
+            &duplicate,
+            "init_genesis lists module `bank` more than once",
+        );
+
+        let mut missing = valid;
+        missing.end_blockers = order(&["bank"]);
+        assert_invalid(&missing, "end_blockers is missing modules: auth, gov");
+    }
+
+    #[test]
+    fn test_default_orders() {
+        let mut config = config(&["auth", "bank", "gov"]);
+        assert_eq!(config.init_genesis_order(), ["auth", "bank", "gov"]);
+        assert_eq!(config.begin_blockers_order(), ["auth", "bank", "gov"]);
+
+        config.init_genesis = order(&["gov", "auth", "bank"]);
+        config.end_blockers = order(&["bank", "gov", "auth"]);
+        assert_eq!(config.init_genesis_order(), ["gov", "auth", "bank"]);
+        assert_eq!(config.begin_blockers_order(), ["gov", "auth", "bank"]);
+        assert_eq!(config.end_blockers_order(), ["bank", "gov", "auth"]);
+    }
+
+    #[test]
+    fn test_load_toml() {
+        let config = AppConfig::load_toml(
+            r#"
+            init_genesis = ['bank', 'auth']
+
+            [[modules]]
+            name = 'auth'
+            params = { max_memo_characters = 512 }
+
+            [[modules]]
+            name = 'bank'
+            store_key = 'balances'
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.init_genesis, order(&["bank", "auth"]));
+        assert_eq!(config.modules[0].params["max_memo_characters"], 512);
+        assert_eq!(config.modules[1].store_key(), "balances");
+        assert!(config.modules[1].params.is_null());
+
+        // keys after a `[[modules]]` table belong to the module
+        let e = AppConfig::load_toml(
+            r#"
+            [[modules]]
+            name = 'auth'
+            init_genesis = ['auth']
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            e.to_string().contains("unknown field `init_genesis`"),
+            "{e}"
+        );
+
+        assert!(AppConfig::load_toml("begin_blocker = []").is_err());
+        assert!(AppConfig::load_toml("init_genesis = ['auth']").is_err());
+    }
+
+    #[test]
+    fn test_load_json() {
+        let config =
+            AppConfig::load_json(r#"{"modules": [{"name": "auth"}, {"name": "bank"}]}"#).unwrap();
+        assert_eq!(config, self::config(&["auth", "bank"]));
+        assert!(AppConfig::load_json(r#"{"modules": [{"name": "auth", "key": "a"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_shipped_app_config() {
+        let config = AppConfig::load_toml(APP_TOML).unwrap();
+        let names: Vec<_> = config.modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["auth", "bank", "staking", "ibc", "upgrade", "gov"]);
+        assert_eq!(
+            config.init_genesis_order(),
+            ["auth", "bank", "staking", "ibc", "gov", "upgrade"]
+        );
+        assert_eq!(config.end_blockers_order(), config.init_genesis_order());
+        assert_eq!(
+            config.module("auth").unwrap().params["module_account_permissions"]
+                .as_array()
+                .unwrap()
+                .len(),
+            5
+        );
+        assert!(config.module("gov").unwrap().params.is_null());
+    }
+
+    #[test]
+    fn test_params() {
+        #[derive(Debug, Default, PartialEq, Deserialize)]
+        struct Params {
+            limit: u64,
+        }
+
+        let mut module = ModuleConfig::new("bank");
+        assert_eq!(module.params::<Params>().unwrap(), Params::default());
+        module.params = serde_json::json!({ "limit": 3 });
+        assert_eq!(module.params::<Params>().unwrap(), Params { limit: 3 });
+        module.params = serde_json::json!({ "limit": "3" });
+        assert!(module.params::<Params>().is_err());
+    }
+}
@@ -9,6 +9,8 @@ use tendermint_proto::abci::{ResponseCheckTx, ResponseDeliverTx, ResponseQuery};
 
 pub trait ResponseFromErrorExt {
     fn from_error(code: u32, log: impl ToString) -> Self;
+
+    fn from_codespace_error(codespace: impl ToString, code: u32, log: impl ToString) -> Self;
 }
 
 macro_rules! impl_response_error_for {
@@ -22,6 +24,13 @@ macro_rules! impl_response_error_for {
                     ..Self::default()
                 }
             }
+
+            fn from_codespace_error(codespace: impl ToString, code: u32, log: impl ToString) -> Self {
+                Self {
+                    codespace: codespace.to_string(),
+                    ..Self::from_error(code, log)
+                }
+            }
         })+
     };
 }
@@ -1,8 +1,7 @@
 //! # AVL Tree
 //!
-//! This module hosts a simple implementation of an AVL Merkle Tree that support the `get` and
-//! `insert` instructions (no delete yet, it's not needed as the on-chain store is supposed to be
-//! immutable).
+//! This module hosts a simple implementation of an AVL Merkle Tree that support the `get`,
+//! `insert` and `remove` instructions.
 //!
 //! Proof of existence are supported using [ICS23](https://github.com/confio/ics23), but proof of
 //! non-existence are not yet implemented.
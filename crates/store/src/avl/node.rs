@@ -125,8 +125,17 @@ where
         self.update_height();
     }
 
-    /// Returns the node's balance factor (left_height - right_height).
+    /// Returns the node's balance factor (left_height - right_height), where a missing child
+    /// counts as a subtree of height -1.
     pub fn balance_factor(&self) -> i32 {
+        let height = |height: Option<u32>| height.map_or(-1, |height| height as i32);
+        height(self.left_height()) - height(self.right_height())
+    }
+
+    /// Returns the balance factor insertions rebalance on, where a missing child counts as a
+    /// leaf. Trees have always been built this way, so it can't change without changing the root
+    /// hashes of the stores, i.e. the app hash.
+    pub(crate) fn insertion_balance_factor(&self) -> i32 {
         match (self.left_height(), self.right_height()) {
             (None, None) => 0,
             (None, Some(h)) => -(h as i32),
@@ -167,38 +167,140 @@ fn integration() {
     ));
 }
 
-/// Check that nodes are ordered, heights are correct and that balance factors are in {-1, 0, 1}.
+#[test]
+fn remove() {
+    let keys = [
+        "M", "N", "O", "L", "K", "Q", "P", "H", "I", "A", "B", "Z", "C",
+    ];
+    let mut tree = AvlTree::new();
+    for (i, key) in keys.iter().enumerate() {
+        tree.insert(*key, [i as u8]);
+    }
+
+    assert_eq!(tree.remove("X"), None);
+    for (i, key) in keys.iter().enumerate().step_by(2) {
+        assert_eq!(tree.remove(*key), Some([i as u8]));
+        assert_eq!(tree.get(*key), None);
+        assert!(check_integrity(&tree.root));
+    }
+
+    let root = tree
+        .root_hash()
+        .expect("Unable to retrieve root hash")
+        .as_bytes()
+        .to_vec();
+    let spec = get_proof_spec();
+    for (i, key) in keys.iter().enumerate().skip(1).step_by(2) {
+        assert_eq!(tree.get(*key), Some(&[i as u8]));
+        let proof = tree
+            .get_proof(*key)
+            .expect("Unable to retrieve a proof for a remaining key");
+        assert!(verify_membership::<HostFunctionsManager>(
+            &proof,
+            &spec,
+            &root,
+            key.as_bytes(),
+            &[i as u8]
+        ));
+    }
+
+    for key in keys.iter().skip(1).step_by(2) {
+        tree.remove(*key);
+    }
+    assert_eq!(tree, AvlTree::new());
+}
+
+#[test]
+fn insertion_root_hashes() {
+    // the rebalancing on insertion determines the root hashes of the stores, i.e. the app hash
+    let keys = [
+        "M", "N", "O", "L", "K", "Q", "P", "H", "I", "A", "B", "Z", "C",
+    ];
+    let mut tree = AvlTree::new();
+    for (i, key) in keys.iter().enumerate() {
+        tree.insert(*key, [i as u8]);
+    }
+    assert_eq!(
+        tree.root_hash().unwrap().to_string(),
+        "800E7A25ED96910AD1A135E77D0671610CA3F4FB9E90C213DC95D8917C3031DF"
+    );
+
+    let mut tree = AvlTree::new();
+    for i in 0..100u8 {
+        tree.insert([i], [i]);
+    }
+    assert_eq!(
+        tree.root_hash().unwrap().to_string(),
+        "D5E5E2BC9910C5A3102002278B5B06B5DD33380550848FDBF338D3612F0F5E1D"
+    );
+
+    let mut tree = AvlTree::new();
+    for i in (0..100u8).rev() {
+        tree.insert([i], [i]);
+    }
+    assert_eq!(
+        tree.root_hash().unwrap().to_string(),
+        "F3AA585E49D081726D9B9C28D294956AFE9D9C5A043102C0570423002E62CF1C"
+    );
+}
+
+#[test]
+fn balance_factor() {
+    let leaf = || as_node_ref("A", [0]);
+    let node = build_node("B", [0], leaf(), None).unwrap();
+    assert_eq!(node.balance_factor(), 1);
+    assert_eq!(node.insertion_balance_factor(), 0);
+
+    let node = build_node("C", [0], build_node("B", [0], leaf(), None), None).unwrap();
+    assert_eq!(node.balance_factor(), 2);
+    assert_eq!(node.insertion_balance_factor(), 1);
+
+    let node = build_node("B", [0], leaf(), as_node_ref("C", [0])).unwrap();
+    assert_eq!(node.balance_factor(), 0);
+}
+
+#[test]
+fn remove_rebalances() {
+    let mut tree = AvlTree::new();
+    for key in ["B", "A", "C", "D"] {
+        tree.insert(key, [0]);
+    }
+    // the root is left with a single child of height 1
+    tree.remove("A");
+
+    let data = [0];
+    let target = AvlTree {
+        root: build_node("C", data, as_node_ref("B", data), as_node_ref("D", data)),
+    };
+    assert_eq!(tree, target);
+}
+
+/// Check that nodes are ordered, heights are correct and that balance factors are in {-1, 0, 1},
+/// except for nodes with a single child of height 1, which insertions don't rebalance (see
+/// `AvlNode::insertion_balance_factor`).
 fn check_integrity<T: Ord, V>(node_ref: &NodeRef<T, V>) -> bool {
     if let Some(node) = node_ref {
-        let mut left_height = 0;
-        let mut right_height = 0;
-        let mut is_leaf = true;
         if let Some(ref left) = node.left {
             if left.key >= node.key {
                 println!("[AVL]: Left child should have a smaller key");
                 return false;
             }
-            left_height = left.height;
-            is_leaf = false;
         }
         if let Some(ref right) = node.right {
             if right.key <= node.key {
                 println!("[AVL]: Right child should have a bigger key");
                 return false;
             }
-            right_height = right.height;
-            is_leaf = false;
         }
-        let balance_factor = (left_height as i32) - (right_height as i32);
-        if balance_factor <= -2 {
-            println!("[AVL] Balance factor <= -2");
-            return false;
-        } else if balance_factor >= 2 {
-            println!("[AVL] Balance factor >= 2");
+        let height = |child: &NodeRef<T, V>| child.as_ref().map_or(-1, |child| child.height as i32);
+        let (left_height, right_height) = (height(&node.left), height(&node.right));
+        let balance_factor = left_height - right_height;
+        let single_child = node.left.is_none() || node.right.is_none();
+        if balance_factor.abs() > 2 || (balance_factor.abs() == 2 && !single_child) {
+            println!("[AVL] Balance factor of {balance_factor}");
             return false;
         }
-        let bonus_height = u32::from(!is_leaf);
-        if node.height != std::cmp::max(left_height, right_height) + bonus_height {
+        if node.height as i32 != std::cmp::max(left_height, right_height) + 1 {
             println!("[AVL] Heights are inconsistent");
             return false;
         }
@@ -18,8 +18,8 @@ use crate::avl::{
     proof, AsBytes,
 };
 
-/// An AVL Tree that supports `get` and `insert` operation and can be used to prove existence of a
-/// given key-value couple.
+/// An AVL Tree that supports `get`, `insert` and `remove` operations and can be used to prove
+/// existence of a given key-value couple.
 #[derive(PartialEq, Eq, Debug, Clone, Default)]
 pub struct AvlTree<K: Ord + AsBytes, V> {
     pub root: NodeRef<K, V>,
@@ -74,12 +74,77 @@ where
                 Ordering::Equal => *old_value = Some(node.set_value(value)),
             }
             node.update();
-            AvlTree::balance_node(node_ref);
+            AvlTree::balance_node_on_insert(node_ref);
         } else {
             *node_ref = as_node_ref(key, value);
         }
     }
 
+    /// Remove the value associated with the key from the AVL tree, this operation runs in
+    /// O(log(n)).
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        AvlTree::remove_rec(&mut self.root, key)
+    }
+
+    /// Remove a value from the tree.
+    fn remove_rec<Q: ?Sized>(node_ref: &mut NodeRef<K, V>, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let node = node_ref.as_mut()?;
+        let old_value = match node.key.borrow().cmp(key) {
+            Ordering::Greater => AvlTree::remove_rec(&mut node.left, key)?,
+            Ordering::Less => AvlTree::remove_rec(&mut node.right, key)?,
+            Ordering::Equal => {
+                let mut node = node_ref.take().expect("[AVL]: Empty node in remove");
+                *node_ref = match (node.left.take(), node.right.take()) {
+                    (None, None) => None,
+                    (Some(child), None) | (None, Some(child)) => Some(child),
+                    // replace the node by its in-order successor
+                    (left, right) => {
+                        let mut right = right;
+                        let mut successor =
+                            AvlTree::remove_min(&mut right).expect("[AVL]: Empty right node");
+                        successor.left = left;
+                        successor.right = right;
+                        successor.update();
+                        Some(successor)
+                    }
+                };
+                if node_ref.is_some() {
+                    AvlTree::balance_node(node_ref);
+                }
+                return Some(node.value);
+            }
+        };
+        node_ref
+            .as_mut()
+            .expect("[AVL]: Empty node in remove")
+            .update();
+        AvlTree::balance_node(node_ref);
+        Some(old_value)
+    }
+
+    /// Detach the node with the smallest key from the tree.
+    fn remove_min(node_ref: &mut NodeRef<K, V>) -> NodeRef<K, V> {
+        let node = node_ref.as_mut()?;
+        if node.left.is_some() {
+            let min = AvlTree::remove_min(&mut node.left);
+            node.update();
+            AvlTree::balance_node(node_ref);
+            min
+        } else {
+            let mut min = node_ref.take()?;
+            *node_ref = min.right.take();
+            Some(min)
+        }
+    }
+
     #[allow(dead_code)]
     /// Return an existence proof for the given element, if it exists.
     pub fn get_proof<Q: ?Sized>(&self, key: &Q) -> Option<CommitmentProof>
@@ -149,7 +214,34 @@ where
         }
     }
 
-    /// Rebalance the AVL tree by performing rotations, if needed.
+    /// Rebalance the AVL tree after an insertion by performing rotations, if needed.
+    fn balance_node_on_insert(node_ref: &mut NodeRef<K, V>) {
+        let node = node_ref
+            .as_mut()
+            .expect("[AVL]: Empty node in node balance");
+        let balance_factor = node.insertion_balance_factor();
+        if balance_factor >= 2 {
+            let left = node
+                .left
+                .as_mut()
+                .expect("[AVL]: Unexpected empty left node");
+            if left.insertion_balance_factor() < 1 {
+                AvlTree::rotate_left(&mut node.left);
+            }
+            AvlTree::rotate_right(node_ref);
+        } else if balance_factor <= -2 {
+            let right = node
+                .right
+                .as_mut()
+                .expect("[AVL]: Unexpected empty right node");
+            if right.insertion_balance_factor() > -1 {
+                AvlTree::rotate_right(&mut node.right);
+            }
+            AvlTree::rotate_left(node_ref);
+        }
+    }
+
+    /// Rebalance the AVL tree after a removal by performing rotations, if needed.
     fn balance_node(node_ref: &mut NodeRef<K, V>) {
         let node = node_ref
             .as_mut()
@@ -160,7 +252,8 @@ where
                 .left
                 .as_mut()
                 .expect("[AVL]: Unexpected empty left node");
-            if left.balance_factor() < 1 {
+            // the left child leans right, so a double rotation is required
+            if left.balance_factor() < 0 {
                 AvlTree::rotate_left(&mut node.left);
             }
             AvlTree::rotate_right(node_ref);
@@ -169,7 +262,8 @@ where
                 .right
                 .as_mut()
                 .expect("[AVL]: Unexpected empty right node");
-            if right.balance_factor() > -1 {
+            // the right child leans left, so a double rotation is required
+            if right.balance_factor() > 0 {
                 AvlTree::rotate_right(&mut node.right);
             }
             AvlTree::rotate_left(node_ref);
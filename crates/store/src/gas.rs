@@ -0,0 +1,288 @@
+//! Gas metering of store operations, modeled after the Go SDK's `GasKVStore`.
+//!
+//! Gas is consumed from the gas meter that is active on the current thread (see
+//! [`with_gas_meter()`]), so that the stores don't need to know about the transaction being
+//! executed. Store operations performed while no gas meter is active (e.g. queries) are free.
+
+use crate::context::{ProvableStore, Store};
+use cosmos_helper::{Height, Path, RawHeight};
+use ics23::CommitmentProof;
+use std::cell::RefCell;
+
+pub type Gas = u64;
+
+/// Gas costs of the store operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasConfig {
+    pub has_cost: Gas,
+    pub delete_cost: Gas,
+    pub read_cost_flat: Gas,
+    pub read_cost_per_byte: Gas,
+    pub write_cost_flat: Gas,
+    pub write_cost_per_byte: Gas,
+    pub iter_next_cost_flat: Gas,
+}
+
+impl Default for GasConfig {
+    /// The costs of Go's `KVGasConfig()`.
+    fn default() -> Self {
+        Self {
+            has_cost: 1000,
+            delete_cost: 1000,
+            read_cost_flat: 1000,
+            read_cost_per_byte: 3,
+            write_cost_flat: 2000,
+            write_cost_per_byte: 30,
+            iter_next_cost_flat: 30,
+        }
+    }
+}
+
+/// Keeps track of the gas consumed during the execution of a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasMeter {
+    /// `None` for an infinite gas meter
+    limit: Option<Gas>,
+    consumed: Gas,
+}
+
+impl GasMeter {
+    pub fn new(limit: Gas) -> Self {
+        Self {
+            limit: Some(limit),
+            consumed: 0,
+        }
+    }
+
+    /// A gas meter that never runs out of gas.
+    pub fn infinite() -> Self {
+        Self::default()
+    }
+
+    pub fn limit(&self) -> Option<Gas> {
+        self.limit
+    }
+
+    pub fn consumed(&self) -> Gas {
+        self.consumed
+    }
+
+    pub fn consume(&mut self, amount: Gas) {
+        self.consumed = self.consumed.saturating_add(amount);
+    }
+
+    pub fn is_out_of_gas(&self) -> bool {
+        self.limit.is_some_and(|limit| self.consumed > limit)
+    }
+}
+
+thread_local! {
+    static GAS_METER: RefCell<Option<GasMeter>> = RefCell::new(None);
+}
+
+/// Runs `f` with `meter` as the active gas meter of the current thread and returns the result
+/// of `f` along with the meter, i.e. the gas consumed by the store operations performed by `f`.
+pub fn with_gas_meter<R>(meter: GasMeter, f: impl FnOnce() -> R) -> (R, GasMeter) {
+    let previous = GAS_METER.with(|active| active.replace(Some(meter)));
+    let result = f();
+    let meter = GAS_METER
+        .with(|active| active.replace(previous))
+        .expect("gas meter must still be active");
+    (result, meter)
+}
+
+/// Consumes gas from the active gas meter of the current thread, if any.
+pub fn consume_gas(amount: Gas) {
+    GAS_METER.with(|active| {
+        if let Some(meter) = active.borrow_mut().as_mut() {
+            meter.consume(amount);
+        }
+    })
+}
+
+/// A wrapper store that charges the active gas meter for every operation.
+#[derive(Clone, Debug, Default)]
+pub struct GasStore<S> {
+    /// backing store
+    store: S,
+    config: GasConfig,
+}
+
+impl<S> GasStore<S>
+where
+    S: Store,
+{
+    pub fn new(store: S) -> Self {
+        Self::with_config(store, GasConfig::default())
+    }
+
+    pub fn with_config(store: S, config: GasConfig) -> Self {
+        Self { store, config }
+    }
+}
+
+impl<S> Store for GasStore<S>
+where
+    S: Store,
+{
+    type Error = S::Error;
+
+    #[inline]
+    fn set(&mut self, path: Path, value: Vec<u8>) -> Result<Option<Vec<u8>>, Self::Error> {
+        let len = path.to_string().len() + value.len();
+        consume_gas(self.config.write_cost_flat);
+        consume_gas(self.config.write_cost_per_byte * len as Gas);
+        self.store.set(path, value)
+    }
+
+    #[inline]
+    fn get(&self, height: Height, path: &Path) -> Option<Vec<u8>> {
+        consume_gas(self.config.read_cost_flat);
+        let value = self.store.get(height, path);
+        let len = path.to_string().len() + value.as_ref().map_or(0, Vec::len);
+        consume_gas(self.config.read_cost_per_byte * len as Gas);
+        value
+    }
+
+    #[inline]
+    fn delete(&mut self, path: &Path) {
+        consume_gas(self.config.delete_cost);
+        self.store.delete(path)
+    }
+
+    #[inline]
+    fn commit(&mut self) -> Result<Vec<u8>, Self::Error> {
+        self.store.commit()
+    }
+
+    #[inline]
+    fn apply(&mut self) -> Result<(), Self::Error> {
+        self.store.apply()
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.store.reset()
+    }
+
+    #[inline]
+    fn prune(&mut self, height: RawHeight) -> Result<RawHeight, Self::Error> {
+        self.store.prune(height)
+    }
+
+    #[inline]
+    fn current_height(&self) -> RawHeight {
+        self.store.current_height()
+    }
+
+    #[inline]
+    fn get_keys(&self, key_prefix: &Path) -> Vec<Path> {
+        let keys = self.store.get_keys(key_prefix);
+        for key in &keys {
+            consume_gas(self.config.iter_next_cost_flat);
+            consume_gas(self.config.read_cost_per_byte * key.to_string().len() as Gas);
+        }
+        keys
+    }
+}
+
+impl<S> ProvableStore for GasStore<S>
+where
+    S: ProvableStore,
+{
+    #[inline]
+    fn root_hash(&self) -> Vec<u8> {
+        self.store.root_hash()
+    }
+
+    #[inline]
+    fn get_proof(&self, height: Height, key: &Path) -> Option<CommitmentProof> {
+        self.store.get_proof(height, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::InMemoryStore;
+    use std::convert::TryInto;
+
+    fn path(path: &str) -> Path {
+        path.to_owned().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_gas_meter() {
+        let mut meter = GasMeter::new(10);
+        assert_eq!(meter.limit(), Some(10));
+        meter.consume(10);
+        assert!(!meter.is_out_of_gas());
+        meter.consume(1);
+        assert!(meter.is_out_of_gas());
+        assert_eq!(meter.consumed(), 11);
+
+        let mut meter = GasMeter::infinite();
+        meter.consume(Gas::MAX);
+        meter.consume(1);
+        assert_eq!(meter.consumed(), Gas::MAX);
+        assert!(!meter.is_out_of_gas());
+    }
+
+    #[test]
+    fn test_store_operations_consume_gas() {
+        let config = GasConfig::default();
+        let mut store = GasStore::new(InMemoryStore::default());
+
+        // `key` and `value` are 3 and 5 bytes long
+        let ((), meter) = with_gas_meter(GasMeter::infinite(), || {
+            store.set(path("key"), b"value".to_vec()).unwrap();
+        });
+        assert_eq!(
+            meter.consumed(),
+            config.write_cost_flat + 8 * config.write_cost_per_byte
+        );
+
+        let (value, meter) = with_gas_meter(GasMeter::infinite(), || {
+            store.get(Height::Pending, &path("key"))
+        });
+        assert_eq!(value, Some(b"value".to_vec()));
+        assert_eq!(
+            meter.consumed(),
+            config.read_cost_flat + 8 * config.read_cost_per_byte
+        );
+
+        let (keys, meter) = with_gas_meter(GasMeter::infinite(), || store.get_keys(&path("key")));
+        assert_eq!(keys.len(), 1);
+        assert_eq!(
+            meter.consumed(),
+            config.iter_next_cost_flat + 3 * config.read_cost_per_byte
+        );
+
+        let ((), meter) = with_gas_meter(GasMeter::new(0), || store.delete(&path("key")));
+        assert_eq!(meter.consumed(), config.delete_cost);
+        assert!(meter.is_out_of_gas());
+    }
+
+    #[test]
+    fn test_operations_without_gas_meter_are_free() {
+        let mut store = GasStore::new(InMemoryStore::default());
+        store.set(path("key"), vec![1]).unwrap();
+
+        // gas consumed outside of `with_gas_meter` isn't charged to the next meter
+        let ((), meter) = with_gas_meter(GasMeter::infinite(), || {});
+        assert_eq!(meter.consumed(), 0);
+        assert_eq!(store.get(Height::Pending, &path("key")), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_with_gas_meter_restores_previous_meter() {
+        let (((), inner), outer) = with_gas_meter(GasMeter::infinite(), || {
+            consume_gas(1);
+            let nested = with_gas_meter(GasMeter::infinite(), || consume_gas(10));
+            consume_gas(2);
+            nested
+        });
+        assert_eq!(inner.consumed(), 10);
+        assert_eq!(outer.consumed(), 3);
+    }
+}
@@ -168,6 +168,10 @@ where
 
     #[inline]
     fn delete(&mut self, path: &Path) {
+        // record the deleted value (if any) so that the delete can be reverted with a set op
+        if let Some(old_value) = self.store.get(Height::Pending, path) {
+            self.op_log.push(RevertOp::Set(path.clone(), old_value));
+        }
         self.store.delete(path)
     }
 
@@ -190,12 +194,14 @@ where
     fn reset(&mut self) {
         // note that we do NOT call the backing store's reset here - this allows users to create
         // multilayered `WalStore`s
+        // the revert ops are applied to the backing store directly, as applying them to `self`
+        // would record them in the operation log again
         trace!("Rollback operation log changes");
         while let Some(op) = self.op_log.pop() {
             match op {
-                RevertOp::Delete(path) => self.delete(&path),
+                RevertOp::Delete(path) => self.store.delete(&path),
                 RevertOp::Set(path, value) => {
-                    self.set(path, value).unwrap(); // safety - reset failures are unrecoverable
+                    self.store.set(path, value).unwrap(); // safety - reset failures are unrecoverable
                 }
             }
         }
@@ -226,3 +232,67 @@ where
         self.store.get_proof(height, key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::InMemoryStore;
+    use std::convert::TryInto;
+
+    fn path(path: &str) -> Path {
+        path.to_owned().try_into().unwrap()
+    }
+
+    fn get(store: &RevertibleStore<InMemoryStore>, key: &str) -> Option<Vec<u8>> {
+        store.get(Height::Pending, &path(key))
+    }
+
+    #[test]
+    fn test_reset_reverts_insert_update_and_delete() {
+        let mut store = RevertibleStore::new(InMemoryStore::default());
+        store.set(path("updated"), vec![1]).unwrap();
+        store.set(path("deleted"), vec![2]).unwrap();
+        store.apply().unwrap();
+
+        store.set(path("inserted"), vec![3]).unwrap();
+        store.set(path("updated"), vec![4]).unwrap();
+        store.set(path("updated"), vec![5]).unwrap();
+        store.delete(&path("deleted"));
+        assert_eq!(get(&store, "inserted"), Some(vec![3]));
+        assert_eq!(get(&store, "updated"), Some(vec![5]));
+        assert_eq!(get(&store, "deleted"), None);
+
+        store.reset();
+        assert_eq!(get(&store, "inserted"), None);
+        assert_eq!(get(&store, "updated"), Some(vec![1]));
+        assert_eq!(get(&store, "deleted"), Some(vec![2]));
+        assert!(store.op_log.is_empty());
+    }
+
+    #[test]
+    fn test_reset_reverts_delete_of_inserted_value() {
+        let mut store = RevertibleStore::new(InMemoryStore::default());
+        store.set(path("key"), vec![1]).unwrap();
+        store.delete(&path("key"));
+        // deleting a missing value has nothing to revert
+        store.delete(&path("missing"));
+
+        store.reset();
+        assert_eq!(get(&store, "key"), None);
+        assert_eq!(get(&store, "missing"), None);
+    }
+
+    #[test]
+    fn test_apply_keeps_changes() {
+        let mut store = RevertibleStore::new(InMemoryStore::default());
+        store.set(path("key"), vec![1]).unwrap();
+        store.apply().unwrap();
+        store.reset();
+        assert_eq!(get(&store, "key"), Some(vec![1]));
+
+        store.delete(&path("key"));
+        store.apply().unwrap();
+        store.reset();
+        assert_eq!(get(&store, "key"), None);
+    }
+}
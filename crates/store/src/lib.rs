@@ -1,12 +1,14 @@
 pub mod avl;
 pub mod codec;
 pub mod context;
+pub mod gas;
 pub mod impls;
 pub mod memory;
 pub mod types;
 
 pub use codec::Codec;
 pub use context::{ProvableStore, Store};
+pub use gas::{GasMeter, GasStore};
 pub use impls::{RevertibleStore, SharedStore};
 pub use memory::InMemoryStore;
 pub use types::{
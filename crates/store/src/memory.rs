@@ -63,8 +63,9 @@ impl Store for InMemoryStore {
         self.get_state(height).and_then(|v| v.get(path).cloned())
     }
 
-    fn delete(&mut self, _path: &Path) {
-        todo!()
+    fn delete(&mut self, path: &Path) {
+        trace!("delete at path = {}", path.to_string());
+        self.pending.remove(path);
     }
 
     fn commit(&mut self) -> Result<Vec<u8>, Self::Error> {
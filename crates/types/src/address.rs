@@ -1,11 +1,25 @@
+pub mod codec;
 pub mod hash;
 pub mod store_key;
 
-use crate::errors::Error;
-use anyhow::Result;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use bech32::{FromBase32, ToBase32, Variant};
+use cosmos_core::address::codec::Codec;
 use lazy_static::lazy_static;
 use lru::LruCache;
-use serde_derive::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::config::{
+    bech32_account_addr_prefix, bech32_consensus_addr_prefix, bech32_validator_addr_prefix,
+};
+use crate::errors::Error;
+
+pub use codec::Bech32Codec;
 
 // const (
 // 	// Constants defined here are the defaults value for address.
@@ -20,7 +34,7 @@ use serde_derive::{Deserialize, Serialize};
 // 	//	config.SetCoinType(yourCoinType)
 // 	//	config.Seal()
 
-//// Bech32MainPrefix defines the main SDK Bech32 prefix of an account's address
+/// Bech32MainPrefix defines the main SDK Bech32 prefix of an account's address
 pub const BECH32_MAIN_PREFIX: &str = "cosmos";
 
 /// Purpose is the ATOM purpose as defined in SLIP44 (`https://github.com/satoshilabs/slips/blob/master/slip-0044.md`)
@@ -54,602 +68,359 @@ pub const PREFIX_ADDRESS: &str = "addr";
 /// Bech32PrefixAccAddr defines the Bech32 prefix of an account's address
 pub const BECH32_PREFIX_ACC_ADDR: &str = BECH32_MAIN_PREFIX;
 
-lazy_static! {
-    /// `BECH32_PREFIX_ACC_PUB` defines the Bech32 prefix of an account's public key
-    static ref BECH32_PREFIX_ACC_PUB: String = format!("{}{}", BECH32_MAIN_PREFIX, PREFIX_PUBLIC);
-}
+/// Length of the addresses derived from public keys, e.g. secp256k1 ones.
+pub const ADDRESS_LENGTH: usize = 20;
 
-lazy_static! {
-    /// `BECH32_PREFIX_VAL_ADDR` defines the Bech32 prefix of a validator's operator address
-    static ref BECH32_PREFIX_VAL_ADDR: String = format!(
-        "{}{}{}",
-        BECH32_MAIN_PREFIX, PREFIX_VALIDATOR, PREFIX_OPERATOR
-    );
-}
+/// Length of the addresses derived with ADR-028, see `hash`.
+pub const LONG_ADDRESS_LENGTH: usize = hash::LEN;
 
-lazy_static! {
-    /// Bech32PrefixValPub defines the Bech32 prefix of a validator's operator public key
-    static ref BECH32_PREFIX_VAL_PUB: String = format!(
-        "{}{}{}{}",
-        BECH32_MAIN_PREFIX, PREFIX_VALIDATOR, PREFIX_OPERATOR, PREFIX_PUBLIC
-    );
-}
+/// Bech32 cache of the addresses, keyed by prefix and address bytes.
+type AddrCache = LruCache<(String, Vec<u8>), String>;
+
+static IS_CACHING_ENABLED: AtomicBool = AtomicBool::new(true);
 
 lazy_static! {
-    /// Bech32PrefixConsAddr defines the Bech32 prefix of a consensus node address
-    static ref BECH32_PREFIX_CONS_ADDR: String = format!(
-        "{}{}{}",
-        BECH32_MAIN_PREFIX, PREFIX_VALIDATOR, PREFIX_CONSENSUS
-    );
+    // `AccAddress::to_string` is expensive and called very often, so the bech32 strings are
+    // cached. In total the caches hold 61k entries, i.e. ~11 MB.
+    static ref ACC_ADDR_CACHE: Mutex<AddrCache> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(60000).unwrap()));
+    static ref VAL_ADDR_CACHE: Mutex<AddrCache> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(500).unwrap()));
+    static ref CONS_ADDR_CACHE: Mutex<AddrCache> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(500).unwrap()));
 }
 
-lazy_static! {
-    /// Bech32PrefixConsPub defines the Bech32 prefix of a consensus node public key
-    static ref BECH32_PREFIX_CONS_PUB: String = format!(
-        "{}{}{}{}",
-        BECH32_MAIN_PREFIX, PREFIX_VALIDATOR, PREFIX_CONSENSUS, PREFIX_PUBLIC
-    );
+/// Enables or disables the bech32 address caches, which are enabled by default.
+pub fn set_addr_cache_enabled(enabled: bool) {
+    IS_CACHING_ENABLED.store(enabled, Ordering::Relaxed);
 }
 
-// // cache variables
-// var (
-// 	// AccAddress.String() is expensive and if unoptimized dominantly showed up in profiles,
-// 	// yet has no mechanisms to trivially cache the result given that AccAddress is a []byte type.
-// 	accAddrMu     sync.Mutex
-// 	accAddrCache  *simplelru.LRU
-// 	consAddrMu    sync.Mutex
-// 	consAddrCache *simplelru.LRU
-// 	valAddrMu     sync.Mutex
-// 	valAddrCache  *simplelru.LRU
-
-// 	isCachingEnabled atomic.Bool
-// )
-
-// func init() {
-// 	var err error
-// 	SetAddrCacheEnabled(true)
-
-// 	// in total the cache size is 61k entries. Key is 32 bytes and value is around 50-70 bytes.
-// 	// That will make around 92 * 61k * 2 (LRU) bytes ~ 11 MB
-// 	if accAddrCache, err = simplelru.NewLRU(60000, nil); err != nil {
-// 		panic(err)
-// 	}
-// 	if consAddrCache, err = simplelru.NewLRU(500, nil); err != nil {
-// 		panic(err)
-// 	}
-// 	if valAddrCache, err = simplelru.NewLRU(500, nil); err != nil {
-// 		panic(err)
-// 	}
-// }
-
-// // SetAddrCacheEnabled enables or disables accAddrCache, consAddrCache, and valAddrCache. By default, caches are enabled.
-// func SetAddrCacheEnabled(enabled bool) {
-// 	isCachingEnabled.Store(enabled)
-// }
-
-// // IsAddrCacheEnabled returns if the address caches are enabled.
-// func IsAddrCacheEnabled() bool {
-// 	return isCachingEnabled.Load()
-// }
+/// Returns whether the bech32 address caches are enabled.
+pub fn is_addr_cache_enabled() -> bool {
+    IS_CACHING_ENABLED.load(Ordering::Relaxed)
+}
 
 /// Address is a common interface for different types of addresses used by the SDK
 pub trait Address:
     std::cmp::PartialEq + std::cmp::Eq + std::fmt::Debug + std::fmt::Display
 {
     fn empty(&self) -> bool;
-    fn marshal(&self) -> Result<Vec<u8>>;
-    fn marshal_json(&self) -> Result<Vec<u8>>;
+    fn marshal(&self) -> Result<Vec<u8>, Error>;
+    fn marshal_json(&self) -> Result<Vec<u8>, Error>;
     fn bytes(&self) -> Vec<u8>;
-    fn to_string(&self) -> String;
-}
-
-// // Ensure that different address types implement the interface
-// var (
-// 	_ Address = AccAddress{}
-// 	_ Address = ValAddress{}
-// 	_ Address = ConsAddress{}
-// )
-
-// ----------------------------------------------------------------------------
-// account
-// ----------------------------------------------------------------------------
-
-// AccAddress a wrapper around bytes meant to represent an account address.
-// When marshaled to a string or JSON, it uses Bech32.
-#[derive(PartialEq, Eq, Serialize, Deserialize, prost::Message)]
-pub struct AccAddress {
-    #[prost(bytes, tag = "1")]
-    pub bytes: Vec<u8>,
 }
 
-/// `acc_address_from_hex_unsafe` creates an AccAddress from a HEX-encoded string.
-///
-/// Note, this function is considered unsafe as it may produce an AccAddress from
-/// otherwise invalid input, such as a transaction hash. Please use
-/// AccAddressFromBech32.
-pub fn acc_address_from_hex_unsafe(address: &str) -> Result<AccAddress> {
-    let bytes = address_bytes_from_hex_string(address)?;
-    Ok(AccAddress { bytes })
+/// Defines an address type, which is encoded as bech32 (with the prefix returned by `$prefix`)
+/// when displayed or serialized.
+macro_rules! address_type {
+    ($(#[$attr:meta])* $name:ident, $prefix:path, $cache:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name {
+            bytes: Vec<u8>,
+        }
+
+        impl $name {
+            /// Creates the address from a bech32 string, which must have the configured prefix.
+            pub fn from_bech32(address: &str) -> Result<Self, Error> {
+                let bytes = Bech32Codec::new($prefix()).string_to_bytes(address)?;
+                Ok(Self { bytes })
+            }
+
+            /// Creates the address from a hex string.
+            ///
+            /// Note, this function is considered unsafe as it may produce an address from
+            /// otherwise invalid input, such as a transaction hash. Please use `from_bech32`.
+            pub fn from_hex_unsafe(address: &str) -> Result<Self, Error> {
+                let bytes = address_bytes_from_hex_string(address)?;
+                Ok(Self { bytes })
+            }
+
+            /// Returns boolean for whether the address is empty
+            pub fn empty(&self) -> bool {
+                self.bytes.is_empty()
+            }
+
+            /// Returns the raw address bytes.
+            pub fn bytes(&self) -> Vec<u8> {
+                self.bytes.clone()
+            }
+
+            pub fn as_bytes(&self) -> &[u8] {
+                &self.bytes
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = Error;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                verify_address_format(bytes)?;
+                Ok(Self {
+                    bytes: bytes.to_vec(),
+                })
+            }
+        }
+
+        impl TryFrom<Vec<u8>> for $name {
+            type Error = Error;
+
+            fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+                verify_address_format(&bytes)?;
+                Ok(Self { bytes })
+            }
+        }
+
+        impl From<$name> for Vec<u8> {
+            fn from(address: $name) -> Self {
+                address.bytes
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                if self.empty() {
+                    return Ok(());
+                }
+                let address = cache_bech32_addr(&$prefix(), &self.bytes, &$cache)
+                    .map_err(|_| fmt::Error)?;
+                f.write_str(&address)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_bech32(s)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                if s.is_empty() {
+                    return Ok(Self::default());
+                }
+                Self::from_bech32(&s).map_err(de::Error::custom)
+            }
+        }
+
+        impl Address for $name {
+            fn empty(&self) -> bool {
+                self.empty()
+            }
+
+            fn marshal(&self) -> Result<Vec<u8>, Error> {
+                Ok(self.bytes())
+            }
+
+            fn marshal_json(&self) -> Result<Vec<u8>, Error> {
+                serde_json::to_vec(self).map_err(|e| Error::Custom(e.to_string()))
+            }
+
+            fn bytes(&self) -> Vec<u8> {
+                self.bytes()
+            }
+        }
+    };
 }
 
-// // VerifyAddressFormat verifies that the provided bytes form a valid address
-// // according to the default address rules or a custom address verifier set by
-// // GetConfig().SetAddressVerifier().
-// // TODO make an issue to get rid of global Config
-// // ref: https://github.com/cosmos/cosmos-sdk/issues/9690
-// func VerifyAddressFormat(bz []byte) error {
-// 	verifier := GetConfig().GetAddressVerifier()
-// 	if verifier != nil {
-// 		return verifier(bz)
-// 	}
-
-// 	if len(bz) == 0 {
-// 		return errorsmod.Wrap(sdkerrors.ErrUnknownAddress, "addresses cannot be empty")
-// 	}
-
-// 	if len(bz) > address.MaxAddrLen {
-// 		return errorsmod.Wrapf(sdkerrors.ErrUnknownAddress, "address max length is %d, got %d", address.MaxAddrLen, len(bz))
-// 	}
-
-// 	return nil
-// }
-
-// // MustAccAddressFromBech32 calls AccAddressFromBech32 and panics on error.
-// func MustAccAddressFromBech32(address string) AccAddress {
-// 	addr, err := AccAddressFromBech32(address)
-// 	if err != nil {
-// 		panic(err)
-// 	}
-
-// 	return addr
-// }
-
-// // AccAddressFromBech32 creates an AccAddress from a Bech32 string.
-// func AccAddressFromBech32(address string) (addr AccAddress, err error) {
-// 	if len(strings.TrimSpace(address)) == 0 {
-// 		return AccAddress{}, errors.New("empty address string is not allowed")
-// 	}
-
-// 	bech32PrefixAccAddr := GetConfig().GetBech32AccountAddrPrefix()
-
-// 	bz, err := GetFromBech32(address, bech32PrefixAccAddr)
-// 	if err != nil {
-// 		return nil, err
-// 	}
-
-// 	err = VerifyAddressFormat(bz)
-// 	if err != nil {
-// 		return nil, err
-// 	}
-
-// 	return AccAddress(bz), nil
-// }
-
-impl AccAddress {
-    /// Returns boolean for whether an AccAddress is empty
-    pub fn empty(&self) -> bool {
-        self.bytes.is_empty()
-    }
-
-    /// Bytes returns the raw address bytes.
-    pub fn bytes(&self) -> Vec<u8> {
-        self.bytes.clone()
+address_type!(
+    /// AccAddress a wrapper around bytes meant to represent an account address.
+    /// When marshaled to a string or JSON, it uses Bech32.
+    AccAddress,
+    bech32_account_addr_prefix,
+    ACC_ADDR_CACHE
+);
+
+address_type!(
+    /// ValAddress defines a wrapper around bytes meant to present a validator's
+    /// operator. When marshaled to a string or JSON, it uses Bech32.
+    ValAddress,
+    bech32_validator_addr_prefix,
+    VAL_ADDR_CACHE
+);
+
+address_type!(
+    /// ConsAddress defines a wrapper around bytes meant to present a consensus node.
+    /// When marshaled to a string or JSON, it uses Bech32.
+    ConsAddress,
+    bech32_consensus_addr_prefix,
+    CONS_ADDR_CACHE
+);
+
+impl From<tendermint::account::Id> for ConsAddress {
+    fn from(id: tendermint::account::Id) -> Self {
+        Self {
+            bytes: id.as_bytes().to_vec(),
+        }
     }
 }
 
-// // String implements the Stringer interface.
-// func (aa AccAddress) String() string {
-// 	if aa.Empty() {
-// 		return ""
-// 	}
-
-// 	key := conv.UnsafeBytesToStr(aa)
-
-// 	if IsAddrCacheEnabled() {
-// 		accAddrMu.Lock()
-// 		defer accAddrMu.Unlock()
-
-// 		addr, ok := accAddrCache.Get(key)
-// 		if ok {
-// 			return addr.(string)
-// 		}
-// 	}
-// 	return cacheBech32Addr(GetConfig().GetBech32AccountAddrPrefix(), aa, accAddrCache, key)
-// }
-
-// // Format implements the fmt.Formatter interface.
-
-// func (aa AccAddress) Format(s fmt.State, verb rune) {
-// 	switch verb {
-// 	case 's':
-// 		s.Write([]byte(aa.String()))
-// 	case 'p':
-// 		s.Write([]byte(fmt.Sprintf("%p", aa)))
-// 	default:
-// 		s.Write([]byte(fmt.Sprintf("%X", []byte(aa))))
-// 	}
-// }
-
-// // ----------------------------------------------------------------------------
-// // validator operator
-// // ----------------------------------------------------------------------------
-
-// // ValAddress defines a wrapper around bytes meant to present a validator's
-// // operator. When marshaled to a string or JSON, it uses Bech32.
-// type ValAddress []byte
-
-// // ValAddressFromHex creates a ValAddress from a hex string.
-// func ValAddressFromHex(address string) (addr ValAddress, err error) {
-// 	bz, err := addressBytesFromHexString(address)
-// 	return ValAddress(bz), err
-// }
-
-// // ValAddressFromBech32 creates a ValAddress from a Bech32 string.
-// func ValAddressFromBech32(address string) (addr ValAddress, err error) {
-// 	if len(strings.TrimSpace(address)) == 0 {
-// 		return ValAddress{}, errors.New("empty address string is not allowed")
-// 	}
-
-// 	bech32PrefixValAddr := GetConfig().GetBech32ValidatorAddrPrefix()
-
-// 	bz, err := GetFromBech32(address, bech32PrefixValAddr)
-// 	if err != nil {
-// 		return nil, err
-// 	}
-
-// 	err = VerifyAddressFormat(bz)
-// 	if err != nil {
-// 		return nil, err
-// 	}
-
-// 	return ValAddress(bz), nil
-// }
-
-// // Returns boolean for whether two ValAddresses are Equal
-// func (va ValAddress) Equals(va2 Address) bool {
-// 	if va.Empty() && va2.Empty() {
-// 		return true
-// 	}
-
-// 	return bytes.Equal(va.Bytes(), va2.Bytes())
-// }
-
-// // Returns boolean for whether an ValAddress is empty
-// func (va ValAddress) Empty() bool {
-// 	return len(va) == 0
-// }
-
-// // Marshal returns the raw address bytes. It is needed for protobuf
-// // compatibility.
-// func (va ValAddress) Marshal() ([]byte, error) {
-// 	return va, nil
-// }
-
-// // Unmarshal sets the address to the given data. It is needed for protobuf
-// // compatibility.
-// func (va *ValAddress) Unmarshal(data []byte) error {
-// 	*va = data
-// 	return nil
-// }
-
-// // MarshalJSON marshals to JSON using Bech32.
-// func (va ValAddress) MarshalJSON() ([]byte, error) {
-// 	return json.Marshal(va.String())
-// }
-
-// // MarshalYAML marshals to YAML using Bech32.
-// func (va ValAddress) MarshalYAML() (interface{}, error) {
-// 	return va.String(), nil
-// }
-
-// // UnmarshalJSON unmarshals from JSON assuming Bech32 encoding.
-// func (va *ValAddress) UnmarshalJSON(data []byte) error {
-// 	var s string
-
-// 	err := json.Unmarshal(data, &s)
-// 	if err != nil {
-// 		return err
-// 	}
-// 	if s == "" {
-// 		*va = ValAddress{}
-// 		return nil
-// 	}
-
-// 	va2, err := ValAddressFromBech32(s)
-// 	if err != nil {
-// 		return err
-// 	}
-
-// 	*va = va2
-// 	return nil
-// }
-
-// // UnmarshalYAML unmarshals from YAML assuming Bech32 encoding.
-// func (va *ValAddress) UnmarshalYAML(data []byte) error {
-// 	var s string
-
-// 	err := yaml.Unmarshal(data, &s)
-// 	if err != nil {
-// 		return err
-// 	}
-// 	if s == "" {
-// 		*va = ValAddress{}
-// 		return nil
-// 	}
-
-// 	va2, err := ValAddressFromBech32(s)
-// 	if err != nil {
-// 		return err
-// 	}
-
-// 	*va = va2
-// 	return nil
-// }
-
-// // Bytes returns the raw address bytes.
-// func (va ValAddress) Bytes() []byte {
-// 	return va
-// }
-
-// // String implements the Stringer interface.
-// func (va ValAddress) String() string {
-// 	if va.Empty() {
-// 		return ""
-// 	}
-
-// 	key := conv.UnsafeBytesToStr(va)
-
-// 	if IsAddrCacheEnabled() {
-// 		valAddrMu.Lock()
-// 		defer valAddrMu.Unlock()
-
-// 		addr, ok := valAddrCache.Get(key)
-// 		if ok {
-// 			return addr.(string)
-// 		}
-// 	}
-// 	return cacheBech32Addr(GetConfig().GetBech32ValidatorAddrPrefix(), va, valAddrCache, key)
-// }
-
-// // Format implements the fmt.Formatter interface.
-
-// func (va ValAddress) Format(s fmt.State, verb rune) {
-// 	switch verb {
-// 	case 's':
-// 		s.Write([]byte(va.String()))
-// 	case 'p':
-// 		s.Write([]byte(fmt.Sprintf("%p", va)))
-// 	default:
-// 		s.Write([]byte(fmt.Sprintf("%X", []byte(va))))
-// 	}
-// }
-
-// // ----------------------------------------------------------------------------
-// // consensus node
-// // ----------------------------------------------------------------------------
-
-// // ConsAddress defines a wrapper around bytes meant to present a consensus node.
-// // When marshaled to a string or JSON, it uses Bech32.
-// type ConsAddress []byte
-
-// // ConsAddressFromHex creates a ConsAddress from a hex string.
-// func ConsAddressFromHex(address string) (addr ConsAddress, err error) {
-// 	bz, err := addressBytesFromHexString(address)
-// 	return ConsAddress(bz), err
-// }
-
-// // ConsAddressFromBech32 creates a ConsAddress from a Bech32 string.
-// func ConsAddressFromBech32(address string) (addr ConsAddress, err error) {
-// 	if len(strings.TrimSpace(address)) == 0 {
-// 		return ConsAddress{}, errors.New("empty address string is not allowed")
-// 	}
-
-// 	bech32PrefixConsAddr := GetConfig().GetBech32ConsensusAddrPrefix()
-
-// 	bz, err := GetFromBech32(address, bech32PrefixConsAddr)
-// 	if err != nil {
-// 		return nil, err
-// 	}
-
-// 	err = VerifyAddressFormat(bz)
-// 	if err != nil {
-// 		return nil, err
-// 	}
-
-// 	return ConsAddress(bz), nil
-// }
-
-// // get ConsAddress from pubkey
-// func GetConsAddress(pubkey cryptotypes.PubKey) ConsAddress {
-// 	return ConsAddress(pubkey.Address())
-// }
-
-// // Returns boolean for whether two ConsAddress are Equal
-// func (ca ConsAddress) Equals(ca2 Address) bool {
-// 	if ca.Empty() && ca2.Empty() {
-// 		return true
-// 	}
-
-// 	return bytes.Equal(ca.Bytes(), ca2.Bytes())
-// }
-
-// // Returns boolean for whether an ConsAddress is empty
-// func (ca ConsAddress) Empty() bool {
-// 	return len(ca) == 0
-// }
-
-// // Marshal returns the raw address bytes. It is needed for protobuf
-// // compatibility.
-// func (ca ConsAddress) Marshal() ([]byte, error) {
-// 	return ca, nil
-// }
-
-// // Unmarshal sets the address to the given data. It is needed for protobuf
-// // compatibility.
-// func (ca *ConsAddress) Unmarshal(data []byte) error {
-// 	*ca = data
-// 	return nil
-// }
-
-// // MarshalJSON marshals to JSON using Bech32.
-// func (ca ConsAddress) MarshalJSON() ([]byte, error) {
-// 	return json.Marshal(ca.String())
-// }
-
-// // MarshalYAML marshals to YAML using Bech32.
-// func (ca ConsAddress) MarshalYAML() (interface{}, error) {
-// 	return ca.String(), nil
-// }
-
-// // UnmarshalJSON unmarshals from JSON assuming Bech32 encoding.
-// func (ca *ConsAddress) UnmarshalJSON(data []byte) error {
-// 	var s string
-
-// 	err := json.Unmarshal(data, &s)
-// 	if err != nil {
-// 		return err
-// 	}
-// 	if s == "" {
-// 		*ca = ConsAddress{}
-// 		return nil
-// 	}
-
-// 	ca2, err := ConsAddressFromBech32(s)
-// 	if err != nil {
-// 		return err
-// 	}
-
-// 	*ca = ca2
-// 	return nil
-// }
-
-// // UnmarshalYAML unmarshals from YAML assuming Bech32 encoding.
-// func (ca *ConsAddress) UnmarshalYAML(data []byte) error {
-// 	var s string
-
-// 	err := yaml.Unmarshal(data, &s)
-// 	if err != nil {
-// 		return err
-// 	}
-// 	if s == "" {
-// 		*ca = ConsAddress{}
-// 		return nil
-// 	}
-
-// 	ca2, err := ConsAddressFromBech32(s)
-// 	if err != nil {
-// 		return err
-// 	}
-
-// 	*ca = ca2
-// 	return nil
-// }
-
-// // Bytes returns the raw address bytes.
-// func (ca ConsAddress) Bytes() []byte {
-// 	return ca
-// }
-
-// // String implements the Stringer interface.
-// func (ca ConsAddress) String() string {
-// 	if ca.Empty() {
-// 		return ""
-// 	}
-
-// 	key := conv.UnsafeBytesToStr(ca)
-
-// 	if IsAddrCacheEnabled() {
-// 		consAddrMu.Lock()
-// 		defer consAddrMu.Unlock()
-
-// 		addr, ok := consAddrCache.Get(key)
-// 		if ok {
-// 			return addr.(string)
-// 		}
-// 	}
-// 	return cacheBech32Addr(GetConfig().GetBech32ConsensusAddrPrefix(), ca, consAddrCache, key)
-// }
-
-// // Bech32ifyAddressBytes returns a bech32 representation of address bytes.
-// // Returns an empty sting if the byte slice is 0-length. Returns an error if the bech32 conversion
-// // fails or the prefix is empty.
-// func Bech32ifyAddressBytes(prefix string, bs []byte) (string, error) {
-// 	if len(bs) == 0 {
-// 		return "", nil
-// 	}
-// 	if len(prefix) == 0 {
-// 		return "", errors.New("prefix cannot be empty")
-// 	}
-// 	return bech32.ConvertAndEncode(prefix, bs)
-// }
-
-// // MustBech32ifyAddressBytes returns a bech32 representation of address bytes.
-// // Returns an empty sting if the byte slice is 0-length. It panics if the bech32 conversion
-// // fails or the prefix is empty.
-// func MustBech32ifyAddressBytes(prefix string, bs []byte) string {
-// 	s, err := Bech32ifyAddressBytes(prefix, bs)
-// 	if err != nil {
-// 		panic(err)
-// 	}
-// 	return s
-// }
-
-// // Format implements the fmt.Formatter interface.
-
-// func (ca ConsAddress) Format(s fmt.State, verb rune) {
-// 	switch verb {
-// 	case 's':
-// 		s.Write([]byte(ca.String()))
-// 	case 'p':
-// 		s.Write([]byte(fmt.Sprintf("%p", ca)))
-// 	default:
-// 		s.Write([]byte(fmt.Sprintf("%X", []byte(ca))))
-// 	}
-// }
-
 // ----------------------------------------------------------------------------
 // auxiliary
 // ----------------------------------------------------------------------------
 
+/// Verifies that the provided bytes form a valid address, i.e. a 20 bytes address derived from a
+/// public key or a 32 bytes one derived with ADR-028.
+pub fn verify_address_format(bytes: &[u8]) -> Result<(), Error> {
+    match bytes.len() {
+        0 => Err(Error::EmptyAddress),
+        ADDRESS_LENGTH | LONG_ADDRESS_LENGTH => Ok(()),
+        len => Err(Error::InvalidAddressLength(len)),
+    }
+}
+
 /// GetFromBech32 decodes a bytestring from a Bech32 encoded string.
-pub fn get_from_bech32(bech32str: &str, prefix: &str) -> Result<Vec<u8>> {
-    if bech32str.is_empty() {
-        return Err(Error::EmptyBech32Address.into());
+pub fn get_from_bech32(bech32str: &str, prefix: &str) -> Result<Vec<u8>, Error> {
+    if bech32str.trim().is_empty() {
+        return Err(Error::EmptyBech32Address);
     }
 
-    let (hrp, bz, var) = bech32::decode(bech32str)?;
-    tracing::info!("hrp: {}, bz: {:?}, var: {:?}", hrp, bz, var);
+    let (hrp, data, _) = bech32::decode(bech32str)?;
     if hrp != prefix {
-        return Err(Error::InvalidBech32Prefix.into());
+        return Err(Error::InvalidBech32Prefix {
+            expected: prefix.to_owned(),
+            got: hrp,
+        });
     }
 
-    Ok(bz.into_iter().map(Into::into).collect::<Vec<u8>>())
+    Ok(Vec::<u8>::from_base32(&data)?)
+}
+
+/// Returns the bech32 representation of the address bytes, i.e. an empty string if there are no
+/// bytes.
+pub fn bech32ify_address_bytes(prefix: &str, bytes: &[u8]) -> Result<String, Error> {
+    if bytes.is_empty() {
+        return Ok(String::new());
+    }
+    if prefix.is_empty() {
+        return Err(Error::Custom("prefix cannot be empty".to_owned()));
+    }
+    Ok(bech32::encode(prefix, bytes.to_base32(), Variant::Bech32)?)
 }
 
-pub fn address_bytes_from_hex_string(address: &str) -> Result<Vec<u8>> {
+pub fn address_bytes_from_hex_string(address: &str) -> Result<Vec<u8>, Error> {
     if address.is_empty() {
-        return Err(Error::EmptyBech32Address.into());
+        return Err(Error::EmptyHexAddress);
     }
 
-    let result = hex::decode(address)?;
-    Ok(result)
+    Ok(hex::decode(address)?)
 }
 
-/// cacheBech32Addr is not concurrency safe. Concurrent access to cache causes race condition.
+/// Returns the bech32 representation of the address, from the cache if it's enabled.
 pub fn cache_bech32_addr(
     prefix: &str,
     addr: &[u8],
-    cache: &mut LruCache<String, String>,
-    cache_key: &str,
-) -> String {
-    let bech32_addr = bech32::encode(
-        prefix,
-        addr.iter()
-            .map(|v| bech32::u5::try_from_u8(*v).unwrap())
-            .collect::<Vec<_>>(),
-        bech32::Variant::Bech32,
-    )
-    .unwrap();
-    cache.put(cache_key.to_string(), bech32_addr.clone());
-    bech32_addr
+    cache: &Mutex<AddrCache>,
+) -> Result<String, Error> {
+    if !is_addr_cache_enabled() {
+        return bech32ify_address_bytes(prefix, addr);
+    }
+
+    let key = (prefix.to_owned(), addr.to_vec());
+    let mut cache = cache.lock().unwrap();
+    if let Some(bech32_addr) = cache.get(&key) {
+        return Ok(bech32_addr.clone());
+    }
+    let bech32_addr = bech32ify_address_bytes(prefix, addr)?;
+    cache.put(key, bech32_addr.clone());
+    Ok(bech32_addr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efws";
+
+    #[test]
+    fn test_acc_address_round_trip() {
+        let address = AccAddress::from_bech32(ADDRESS).unwrap();
+        assert_eq!(address.as_bytes().len(), ADDRESS_LENGTH);
+        assert_eq!(address.to_string(), ADDRESS);
+        // the second time from the cache
+        assert_eq!(address.to_string(), ADDRESS);
+        assert_eq!(AccAddress::from_str(ADDRESS).unwrap(), address);
+
+        let from_hex = AccAddress::from_hex_unsafe(&hex::encode(address.bytes())).unwrap();
+        assert_eq!(from_hex, address);
+    }
+
+    #[test]
+    fn test_address_prefixes() {
+        let bytes = AccAddress::from_bech32(ADDRESS).unwrap().bytes();
+        let val_address = ValAddress::try_from(bytes.clone()).unwrap();
+        assert!(val_address.to_string().starts_with("cosmosvaloper1"));
+        assert_eq!(
+            ValAddress::from_bech32(&val_address.to_string()).unwrap(),
+            val_address
+        );
+
+        let cons_address = ConsAddress::try_from(bytes).unwrap();
+        assert!(cons_address.to_string().starts_with("cosmosvalcons1"));
+        assert_eq!(
+            ConsAddress::from_bech32(&cons_address.to_string()).unwrap(),
+            cons_address
+        );
+
+        assert!(matches!(
+            ValAddress::from_bech32(ADDRESS),
+            Err(Error::InvalidBech32Prefix { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        assert!(matches!(
+            AccAddress::from_bech32(" "),
+            Err(Error::EmptyBech32Address)
+        ));
+        assert!(matches!(
+            AccAddress::from_bech32("cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efwx"),
+            Err(Error::Bech32(_))
+        ));
+
+        let short = bech32ify_address_bytes("cosmos", &[1; 10]).unwrap();
+        assert!(matches!(
+            AccAddress::from_bech32(&short),
+            Err(Error::InvalidAddressLength(10))
+        ));
+        assert!(AccAddress::try_from(&[1; LONG_ADDRESS_LENGTH][..]).is_ok());
+        assert!(matches!(
+            AccAddress::try_from(vec![]),
+            Err(Error::EmptyAddress)
+        ));
+    }
+
+    #[test]
+    fn test_address_json() {
+        let address = AccAddress::from_bech32(ADDRESS).unwrap();
+        let json = serde_json::to_string(&address).unwrap();
+        assert_eq!(json, format!("\"{ADDRESS}\""));
+        assert_eq!(serde_json::from_str::<AccAddress>(&json).unwrap(), address);
+        assert_eq!(address.marshal_json().unwrap(), json.into_bytes());
+
+        let empty: AccAddress = serde_json::from_str("\"\"").unwrap();
+        assert!(empty.empty());
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "\"\"");
+        assert!(serde_json::from_str::<AccAddress>("\"cosmos1\"").is_err());
+    }
+
+    #[test]
+    fn test_codec() {
+        let codec = Bech32Codec::new("cosmos");
+        let bytes = codec.string_to_bytes(ADDRESS).unwrap();
+        assert_eq!(codec.bytes_to_string(&bytes).unwrap(), ADDRESS);
+        assert_eq!(codec.bytes_to_string(&[]).unwrap(), "");
+        assert!(codec.bytes_to_string(&[1; 10]).is_err());
+        assert!(Bech32Codec::new("osmo").string_to_bytes(ADDRESS).is_err());
+    }
 }
@@ -0,0 +1,42 @@
+use cosmos_core::address::codec::Codec;
+
+use super::{bech32ify_address_bytes, get_from_bech32, verify_address_format};
+use crate::errors::Error;
+
+/// Bech32Codec converts addresses from and to bech32 strings with the given prefix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bech32Codec {
+    bech32_prefix: String,
+}
+
+impl Bech32Codec {
+    pub fn new(bech32_prefix: impl Into<String>) -> Self {
+        Self {
+            bech32_prefix: bech32_prefix.into(),
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.bech32_prefix
+    }
+}
+
+impl Codec for Bech32Codec {
+    type Error = Error;
+
+    /// Decodes the bech32 address, which must have the codec's prefix and a valid length.
+    fn string_to_bytes(&self, text: &str) -> Result<Vec<u8>, Self::Error> {
+        let bytes = get_from_bech32(text, &self.bech32_prefix)?;
+        verify_address_format(&bytes)?;
+        Ok(bytes)
+    }
+
+    /// Encodes the address bytes as bech32, empty bytes being encoded as an empty string.
+    fn bytes_to_string(&self, bytes: &[u8]) -> Result<String, Self::Error> {
+        if bytes.is_empty() {
+            return Ok(String::new());
+        }
+        verify_address_format(bytes)?;
+        bech32ify_address_bytes(&self.bech32_prefix, bytes)
+    }
+}
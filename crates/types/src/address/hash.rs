@@ -0,0 +1,162 @@
+//! Address derivation as specified by ADR-028.
+//!
+//! Addresses are derived by hashing a type (e.g. `module`) along with a key, so that addresses of
+//! different types can't collide.
+
+use sha2::{Digest, Sha256};
+
+use super::store_key::length_prefix;
+use super::ADDRESS_LENGTH;
+use crate::errors::Error;
+
+/// Length of the derived addresses.
+pub const LEN: usize = 32;
+
+/// Returns the ADR-028 address of the key for the specified type, i.e.
+/// `sha256(sha256(typ) || key)`.
+pub fn hash(typ: &str, key: &[u8]) -> Vec<u8> {
+    hash_bytes(typ.as_bytes(), key)
+}
+
+fn hash_bytes(typ: &[u8], key: &[u8]) -> Vec<u8> {
+    let type_hash = Sha256::digest(typ);
+    Sha256::new()
+        .chain_update(type_hash)
+        .chain_update(key)
+        .finalize()
+        .to_vec()
+}
+
+/// Returns the address composed of the sub-addresses for the specified type, e.g. the address of
+/// a multisig. The sub-addresses are length prefixed and sorted, so their order doesn't matter.
+///
+/// ## Return
+/// * Errors iff a sub-address is longer than 255 bytes
+pub fn compose(typ: &str, sub_addresses: &[&[u8]]) -> Result<Vec<u8>, Error> {
+    let mut prefixed = sub_addresses
+        .iter()
+        .map(|address| length_prefix(address))
+        .collect::<Result<Vec<_>, _>>()?;
+    prefixed.sort();
+    Ok(hash(typ, &prefixed.concat()))
+}
+
+/// Returns the address of the module account with the specified name and derivation keys.
+///
+/// Without derivation keys, this is the legacy address of the module, i.e. the first 20 bytes of
+/// `sha256(name)`. Otherwise the module address is derived with the first key, and is then
+/// derived with each of the other keys in turn.
+pub fn module(name: &str, derivation_keys: &[&[u8]]) -> Vec<u8> {
+    let Some((first, rest)) = derivation_keys.split_first() else {
+        return Sha256::digest(name.as_bytes())[..ADDRESS_LENGTH].to_vec();
+    };
+
+    let key = [name.as_bytes(), &[0], first].concat();
+    rest.iter()
+        .fold(hash("module", &key), |address, key| derive(&address, key))
+}
+
+/// Returns the address of a sub-account derived from the address with the specified key.
+pub fn derive(address: &[u8], key: &[u8]) -> Vec<u8> {
+    hash_bytes(address, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::bech32ify_address_bytes;
+
+    fn bech32(bytes: &[u8]) -> String {
+        bech32ify_address_bytes("cosmos", bytes).unwrap()
+    }
+
+    #[test]
+    fn test_hash() {
+        let type_hash = Sha256::digest("1");
+        let expected = Sha256::digest([&type_hash[..], &[1]].concat()).to_vec();
+        assert_eq!(hash("1", &[1]), expected);
+        assert_eq!(
+            hex::encode(hash("1", &[1])),
+            "c3eb17fb0963b1c3517ab67c2471f59c4cbcdd53b5c0e35264b1a185f0a00519"
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let (a1, a2): (&[u8], &[u8]) = (&[11, 12], &[21, 22]);
+        let typ = "multisig";
+        let composed = compose(typ, &[a1, a2]).unwrap();
+        assert_eq!(composed.len(), LEN);
+        assert_eq!(composed, hash(typ, &[2, 11, 12, 2, 21, 22]));
+
+        // the order of the sub-addresses doesn't matter, unlike the type
+        assert_eq!(compose(typ, &[a2, a1]).unwrap(), composed);
+        assert_ne!(compose("multisigother", &[a2, a1]).unwrap(), composed);
+
+        assert!(matches!(
+            compose(typ, &[a1, &[0; 300]]),
+            Err(Error::AddressTooLong { len: 300, .. })
+        ));
+    }
+
+    #[test]
+    fn test_module() {
+        let key: &[u8] = &[1, 2];
+        let address = module("myModule", &[key]);
+        assert_eq!(address.len(), LEN);
+        assert_eq!(
+            hex::encode(&address),
+            "61a74bef29dd5b68f5dc669c191be1b47e010bcb5dc1ea86cf72afa5b8a24ef6"
+        );
+        assert_ne!(module("myModule2", &[key]), address);
+        assert_ne!(module("myModule", &[&[1, 2, 3]]), address);
+
+        // each additional key derives the address further
+        assert_eq!(module("myModule", &[key, &[3]]), derive(&address, &[3]));
+    }
+
+    #[test]
+    fn test_legacy_module_addresses() {
+        // module account addresses of a Go chain
+        for (name, address) in [
+            (
+                "fee_collector",
+                "cosmos17xpfvakm2amg962yls6f84z3kell8c5lserqta",
+            ),
+            (
+                "distribution",
+                "cosmos1jv65s3grqf6v6jl3dp4t6c9t9rk99cd88lyufl",
+            ),
+            ("mint", "cosmos1m3h30wlvsf8llruxtpukdvsy0km2kum8g38c8q"),
+            ("gov", "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"),
+            (
+                "bonded_tokens_pool",
+                "cosmos1fl48vsnmsdzcv85q5d2q4z5ajdha8yu34mf0eh",
+            ),
+            (
+                "not_bonded_tokens_pool",
+                "cosmos1tygms3xhhs3yv487phx3dw4a95jn7t7lpm470r",
+            ),
+        ] {
+            assert_eq!(bech32(&module(name, &[])), address);
+        }
+
+        // escrow address of ibc-go's `transfer/channel-0`
+        assert_eq!(
+            bech32(&module("ics20-1\0transfer/channel-0", &[])),
+            "cosmos1a53udazy8ayufvy0s434pfwjcedzqv34kvz9tw"
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        let address = module("myModule", &[&[1, 2]]);
+        let derived = derive(&address, &[3]);
+        assert_eq!(derived.len(), LEN);
+        assert_eq!(
+            hex::encode(&derived),
+            "f95b89816bacb71c449959718d6df2be697a9f358069eaa23c75ee0deff72ac1"
+        );
+        assert_ne!(derive(&address, &[4]), derived);
+    }
+}
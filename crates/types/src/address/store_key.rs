@@ -0,0 +1,51 @@
+use crate::errors::Error;
+
+/// Maximum length of an address, so that its length fits in a single byte prefix.
+pub const MAX_ADDR_LEN: usize = 255;
+
+/// Prefixes the address bytes with their length, so that addresses of different lengths can be
+/// used in store keys (and composed addresses) without ambiguity.
+///
+/// ## Return
+/// * Errors iff the address is longer than `MAX_ADDR_LEN` bytes (empty addresses aren't prefixed)
+pub fn length_prefix(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    if bytes.is_empty() {
+        return Ok(vec![]);
+    }
+    if bytes.len() > MAX_ADDR_LEN {
+        return Err(Error::AddressTooLong {
+            max: MAX_ADDR_LEN,
+            len: bytes.len(),
+        });
+    }
+
+    let mut prefixed = Vec::with_capacity(bytes.len() + 1);
+    prefixed.push(bytes.len() as u8);
+    prefixed.extend_from_slice(bytes);
+    Ok(prefixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_prefix() {
+        let addr10: Vec<u8> = (0..10).collect();
+        let addr20: Vec<u8> = (0..20).collect();
+
+        assert_eq!(
+            length_prefix(&addr10).unwrap(),
+            [&[10][..], &addr10].concat()
+        );
+        assert_eq!(
+            length_prefix(&addr20).unwrap(),
+            [&[20][..], &addr20].concat()
+        );
+        assert!(length_prefix(&[]).unwrap().is_empty());
+        assert!(matches!(
+            length_prefix(&[0; 256]),
+            Err(Error::AddressTooLong { max: 255, len: 256 })
+        ));
+    }
+}
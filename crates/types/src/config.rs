@@ -1,241 +1,228 @@
-// package types
+//! Chain-wide address configuration, i.e. the counterpart of Go's `sdk.Config`. It's set once at
+//! startup (e.g. from the node's config), then sealed so that it can't change while the chain is
+//! running.
 
-// import (
-// 	"context"
-// 	"fmt"
-// 	"sync"
+use std::sync::RwLock;
 
-// 	"github.com/cosmos/cosmos-sdk/version"
-// )
-//
-// TxEncoder marshals transaction to bytes
-// type TxEncoder func(tx Tx) ([]byte, error)
+use lazy_static::lazy_static;
+use serde_derive::{Deserialize, Serialize};
 
-use std::collections::HashMap;
-use std::sync::mpsc::Sender;
-use std::sync::Mutex;
+use crate::address::{
+    BECH32_MAIN_PREFIX, COIN_TYPE, PREFIX_CONSENSUS, PREFIX_OPERATOR, PREFIX_PUBLIC,
+    PREFIX_VALIDATOR, PURPOSE,
+};
+use crate::errors::Error;
 
-pub struct Tx;
+/// `DEFAULT_KEYRING_SERVICE_NAME` defines a default service name for the keyring.
+pub const DEFAULT_KEYRING_SERVICE_NAME: &str = "cosmos";
 
-type TxEncoder = fn(tx: Tx) -> Vec<u8>;
+/// Maximum length of a bech32 human-readable part.
+const MAX_BECH32_PREFIX_LENGTH: usize = 83;
 
-// `DEFAULT_KEYRING_SERVICE_NAME` defines a default service name for the keyring.
-const DEFAULT_KEYRING_SERVICE_NAME: &str = "cosmos";
-
-/// Config is the structure that holds the SDK configuration parameters.
-/// This could be used to initialize certain configuration parameters for the SDK.
+/// Config holds the bech32 prefixes of the addresses and public keys, along with the BIP-44
+/// purpose and coin type of the keys.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Config {
-    pub full_fundraiser_path: String,
-    pub bech32_address_prefix: HashMap<String, String>,
-    pub tx_encoder: TxEncoder,
-    pub address_verifier: fn(Vec<u8>) -> Result<(), String>,
-    pub mtx: Mutex<()>,
-
-    // SLIP-44 related
+    pub bech32_account_addr_prefix: String,
+    pub bech32_account_pub_prefix: String,
+    pub bech32_validator_addr_prefix: String,
+    pub bech32_validator_pub_prefix: String,
+    pub bech32_consensus_addr_prefix: String,
+    pub bech32_consensus_pub_prefix: String,
+    /// SLIP-44 purpose
     pub purpose: u32,
+    /// SLIP-44 coin type
     pub coin_type: u32,
+}
+
+impl Default for Config {
+    /// `cosmos` prefixes and the ATOM coin type.
+    fn default() -> Self {
+        Self::with_main_prefix(BECH32_MAIN_PREFIX)
+    }
+}
+
+impl Config {
+    /// Returns the config with the prefixes derived from the main prefix the same way as the Go
+    /// SDK's, e.g. `cosmos`, `cosmospub`, `cosmosvaloper`, `cosmosvalcons`, etc.
+    pub fn with_main_prefix(main_prefix: &str) -> Self {
+        Self {
+            bech32_account_addr_prefix: main_prefix.to_owned(),
+            bech32_account_pub_prefix: format!("{main_prefix}{PREFIX_PUBLIC}"),
+            bech32_validator_addr_prefix: format!(
+                "{main_prefix}{PREFIX_VALIDATOR}{PREFIX_OPERATOR}"
+            ),
+            bech32_validator_pub_prefix: format!(
+                "{main_prefix}{PREFIX_VALIDATOR}{PREFIX_OPERATOR}{PREFIX_PUBLIC}"
+            ),
+            bech32_consensus_addr_prefix: format!(
+                "{main_prefix}{PREFIX_VALIDATOR}{PREFIX_CONSENSUS}"
+            ),
+            bech32_consensus_pub_prefix: format!(
+                "{main_prefix}{PREFIX_VALIDATOR}{PREFIX_CONSENSUS}{PREFIX_PUBLIC}"
+            ),
+            purpose: PURPOSE,
+            coin_type: COIN_TYPE,
+        }
+    }
+
+    pub fn set_bech32_prefix_for_account(&mut self, address_prefix: &str, pub_key_prefix: &str) {
+        self.bech32_account_addr_prefix = address_prefix.to_owned();
+        self.bech32_account_pub_prefix = pub_key_prefix.to_owned();
+    }
+
+    pub fn set_bech32_prefix_for_validator(&mut self, address_prefix: &str, pub_key_prefix: &str) {
+        self.bech32_validator_addr_prefix = address_prefix.to_owned();
+        self.bech32_validator_pub_prefix = pub_key_prefix.to_owned();
+    }
+
+    pub fn set_bech32_prefix_for_consensus_node(
+        &mut self,
+        address_prefix: &str,
+        pub_key_prefix: &str,
+    ) {
+        self.bech32_consensus_addr_prefix = address_prefix.to_owned();
+        self.bech32_consensus_pub_prefix = pub_key_prefix.to_owned();
+    }
+
+    /// Returns the full BIP-44 path of the first key, e.g. `m/44'/118'/0'/0/0`.
+    pub fn full_bip44_path(&self) -> String {
+        format!("m/{}'/{}'/0'/0/0", self.purpose, self.coin_type)
+    }
+
+    /// Checks that the prefixes are valid bech32 human-readable parts, and that the address
+    /// prefixes are distinct (otherwise the kind of an address would be ambiguous).
+    pub fn validate(&self) -> Result<(), Error> {
+        let prefixes = [
+            &self.bech32_account_addr_prefix,
+            &self.bech32_account_pub_prefix,
+            &self.bech32_validator_addr_prefix,
+            &self.bech32_validator_pub_prefix,
+            &self.bech32_consensus_addr_prefix,
+            &self.bech32_consensus_pub_prefix,
+        ];
+        for prefix in prefixes {
+            let valid = !prefix.is_empty()
+                && prefix.len() <= MAX_BECH32_PREFIX_LENGTH
+                && prefix
+                    .chars()
+                    .all(|c| c.is_ascii_graphic() && !c.is_ascii_uppercase());
+            if !valid {
+                return Err(Error::Custom(format!("invalid bech32 prefix `{prefix}`")));
+            }
+        }
+
+        if self.bech32_account_addr_prefix == self.bech32_validator_addr_prefix
+            || self.bech32_account_addr_prefix == self.bech32_consensus_addr_prefix
+            || self.bech32_validator_addr_prefix == self.bech32_consensus_addr_prefix
+        {
+            return Err(Error::Custom(
+                "account, validator and consensus address prefixes must be distinct".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct GlobalConfig {
+    config: Config,
+    sealed: bool,
+}
+
+lazy_static! {
+    /// cosmos-sdk wide global singleton
+    static ref SDK_CONFIG: RwLock<GlobalConfig> = RwLock::new(GlobalConfig {
+        config: Config::default(),
+        sealed: false,
+    });
+}
+
+/// Returns the config of the SDK, i.e. the default config until it's set.
+pub fn get_config() -> Config {
+    SDK_CONFIG.read().unwrap().config.clone()
+}
+
+/// Sets the config of the SDK.
+///
+/// ## Return
+/// * Errors iff the config is invalid or was already sealed
+pub fn set_config(config: Config) -> Result<(), Error> {
+    config.validate()?;
+    let mut global = SDK_CONFIG.write().unwrap();
+    if global.sealed {
+        return Err(Error::ConfigSealed);
+    }
+    global.config = config;
+    Ok(())
+}
+
+/// Seals the config of the SDK, so that it can't be modified anymore.
+pub fn seal_config() -> Config {
+    let mut global = SDK_CONFIG.write().unwrap();
+    global.sealed = true;
+    global.config.clone()
+}
+
+/// Returns whether the config of the SDK is sealed.
+pub fn is_config_sealed() -> bool {
+    SDK_CONFIG.read().unwrap().sealed
+}
+
+/// Returns the bech32 prefix of account addresses.
+pub fn bech32_account_addr_prefix() -> String {
+    SDK_CONFIG
+        .read()
+        .unwrap()
+        .config
+        .bech32_account_addr_prefix
+        .clone()
+}
 
-    pub sealed: bool,
-    pub selaledch: Sender<()>,
+/// Returns the bech32 prefix of validator operator addresses.
+pub fn bech32_validator_addr_prefix() -> String {
+    SDK_CONFIG
+        .read()
+        .unwrap()
+        .config
+        .bech32_validator_addr_prefix
+        .clone()
 }
 
-// // cosmos-sdk wide global singleton
-// var (
-// 	sdkConfig  *Config
-// 	initConfig sync.Once
-// )
-static mut SDK_CONFIG: Option<Config> = None;
-static INIT_CONFIG: std::sync::Once = std::sync::Once::new();
-
-// // New returns a new Config with default values.
-// func NewConfig() *Config {
-// 	return &Config{
-// 		sealedch: make(chan struct{}),
-// 		bech32AddressPrefix: map[string]string{
-// 			"account_addr":   Bech32PrefixAccAddr,
-// 			"validator_addr": Bech32PrefixValAddr,
-// 			"consensus_addr": Bech32PrefixConsAddr,
-// 			"account_pub":    Bech32PrefixAccPub,
-// 			"validator_pub":  Bech32PrefixValPub,
-// 			"consensus_pub":  Bech32PrefixConsPub,
-// 		},
-// 		fullFundraiserPath: FullFundraiserPath,
-
-// 		purpose:   Purpose,
-// 		coinType:  CoinType,
-// 		txEncoder: nil,
-// 	}
-// }
-//
-
-// // GetConfig returns the config instance for the SDK.
-// func GetConfig() *Config {
-// 	initConfig.Do(func() {
-// 		sdkConfig = NewConfig()
-// 	})
-// 	return sdkConfig
-// }
-
-// // GetSealedConfig returns the config instance for the SDK if/once it is sealed.
-// func GetSealedConfig(ctx context.Context) (*Config, error) {
-// 	config := GetConfig()
-// 	select {
-// 	case <-config.sealedch:
-// 		return config, nil
-// 	case <-ctx.Done():
-// 		return nil, ctx.Err()
-// 	}
-// }
-
-// func (config *Config) assertNotSealed() {
-// 	config.mtx.RLock()
-// 	defer config.mtx.RUnlock()
-
-// 	if config.sealed {
-// 		panic("Config is sealed")
-// 	}
-// }
-
-// // SetBech32PrefixForAccount builds the Config with Bech32 addressPrefix and publKeyPrefix for accounts
-// // and returns the config instance
-// func (config *Config) SetBech32PrefixForAccount(addressPrefix, pubKeyPrefix string) {
-// 	config.assertNotSealed()
-// 	config.bech32AddressPrefix["account_addr"] = addressPrefix
-// 	config.bech32AddressPrefix["account_pub"] = pubKeyPrefix
-// }
-
-// // SetBech32PrefixForValidator builds the Config with Bech32 addressPrefix and publKeyPrefix for validators
-// //
-// //	and returns the config instance
-// func (config *Config) SetBech32PrefixForValidator(addressPrefix, pubKeyPrefix string) {
-// 	config.assertNotSealed()
-// 	config.bech32AddressPrefix["validator_addr"] = addressPrefix
-// 	config.bech32AddressPrefix["validator_pub"] = pubKeyPrefix
-// }
-
-// // SetBech32PrefixForConsensusNode builds the Config with Bech32 addressPrefix and publKeyPrefix for consensus nodes
-// // and returns the config instance
-// func (config *Config) SetBech32PrefixForConsensusNode(addressPrefix, pubKeyPrefix string) {
-// 	config.assertNotSealed()
-// 	config.bech32AddressPrefix["consensus_addr"] = addressPrefix
-// 	config.bech32AddressPrefix["consensus_pub"] = pubKeyPrefix
-// }
-
-// // SetTxEncoder builds the Config with TxEncoder used to marshal StdTx to bytes
-// func (config *Config) SetTxEncoder(encoder TxEncoder) {
-// 	config.assertNotSealed()
-// 	config.txEncoder = encoder
-// }
-
-// // SetAddressVerifier builds the Config with the provided function for verifying that addresses
-// // have the correct format
-// func (config *Config) SetAddressVerifier(addressVerifier func([]byte) error) {
-// 	config.assertNotSealed()
-// 	config.addressVerifier = addressVerifier
-// }
-
-// // Set the FullFundraiserPath (BIP44Prefix) on the config.
-// //
-// // Deprecated: This method is supported for backward compatibility only and will be removed in a future release. Use SetPurpose and SetCoinType instead.
-// func (config *Config) SetFullFundraiserPath(fullFundraiserPath string) {
-// 	config.assertNotSealed()
-// 	config.fullFundraiserPath = fullFundraiserPath
-// }
-
-// // Set the BIP-0044 Purpose code on the config
-// func (config *Config) SetPurpose(purpose uint32) {
-// 	config.assertNotSealed()
-// 	config.purpose = purpose
-// }
-
-// // Set the BIP-0044 CoinType code on the config
-// func (config *Config) SetCoinType(coinType uint32) {
-// 	config.assertNotSealed()
-// 	config.coinType = coinType
-// }
-
-// // Seal seals the config such that the config state could not be modified further
-// func (config *Config) Seal() *Config {
-// 	config.mtx.Lock()
-
-// 	if config.sealed {
-// 		config.mtx.Unlock()
-// 		return config
-// 	}
-
-// 	// signal sealed after state exposed/unlocked
-// 	config.sealed = true
-// 	config.mtx.Unlock()
-// 	close(config.sealedch)
-
-// 	return config
-// }
-
-// // GetBech32AccountAddrPrefix returns the Bech32 prefix for account address
-// func (config *Config) GetBech32AccountAddrPrefix() string {
-// 	return config.bech32AddressPrefix["account_addr"]
-// }
-
-// // GetBech32ValidatorAddrPrefix returns the Bech32 prefix for validator address
-// func (config *Config) GetBech32ValidatorAddrPrefix() string {
-// 	return config.bech32AddressPrefix["validator_addr"]
-// }
-
-// // GetBech32ConsensusAddrPrefix returns the Bech32 prefix for consensus node address
-// func (config *Config) GetBech32ConsensusAddrPrefix() string {
-// 	return config.bech32AddressPrefix["consensus_addr"]
-// }
-
-// // GetBech32AccountPubPrefix returns the Bech32 prefix for account public key
-// func (config *Config) GetBech32AccountPubPrefix() string {
-// 	return config.bech32AddressPrefix["account_pub"]
-// }
-
-// // GetBech32ValidatorPubPrefix returns the Bech32 prefix for validator public key
-// func (config *Config) GetBech32ValidatorPubPrefix() string {
-// 	return config.bech32AddressPrefix["validator_pub"]
-// }
-
-// // GetBech32ConsensusPubPrefix returns the Bech32 prefix for consensus node public key
-// func (config *Config) GetBech32ConsensusPubPrefix() string {
-// 	return config.bech32AddressPrefix["consensus_pub"]
-// }
-
-// // GetTxEncoder return function to encode transactions
-// func (config *Config) GetTxEncoder() TxEncoder {
-// 	return config.txEncoder
-// }
-
-// // GetAddressVerifier returns the function to verify that addresses have the correct format
-// func (config *Config) GetAddressVerifier() func([]byte) error {
-// 	return config.addressVerifier
-// }
-
-// // GetPurpose returns the BIP-0044 Purpose code on the config.
-// func (config *Config) GetPurpose() uint32 {
-// 	return config.purpose
-// }
-
-// // GetCoinType returns the BIP-0044 CoinType code on the config.
-// func (config *Config) GetCoinType() uint32 {
-// 	return config.coinType
-// }
-
-// // GetFullFundraiserPath returns the BIP44Prefix.
-// //
-// // Deprecated: This method is supported for backward compatibility only and will be removed in a future release. Use GetFullBIP44Path instead.
-// func (config *Config) GetFullFundraiserPath() string {
-// 	return config.fullFundraiserPath
-// }
-
-// // GetFullBIP44Path returns the BIP44Prefix.
-// func (config *Config) GetFullBIP44Path() string {
-// 	return fmt.Sprintf("m/%d'/%d'/0'/0/0", config.purpose, config.coinType)
-// }
-
-// func KeyringServiceName() string {
-// 	if len(version.Name) == 0 {
-// 		return DefaultKeyringServiceName
-// 	}
-// 	return version.Name
-// }
+/// Returns the bech32 prefix of consensus node addresses.
+pub fn bech32_consensus_addr_prefix() -> String {
+    SDK_CONFIG
+        .read()
+        .unwrap()
+        .config
+        .bech32_consensus_addr_prefix
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.bech32_account_addr_prefix, "cosmos");
+        assert_eq!(config.bech32_account_pub_prefix, "cosmospub");
+        assert_eq!(config.bech32_validator_addr_prefix, "cosmosvaloper");
+        assert_eq!(config.bech32_validator_pub_prefix, "cosmosvaloperpub");
+        assert_eq!(config.bech32_consensus_addr_prefix, "cosmosvalcons");
+        assert_eq!(config.bech32_consensus_pub_prefix, "cosmosvalconspub");
+        assert_eq!(config.full_bip44_path(), "m/44'/118'/0'/0/0");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_config() {
+        assert!(Config::with_main_prefix("osmo").validate().is_ok());
+        assert!(Config::with_main_prefix("").validate().is_err());
+        assert!(Config::with_main_prefix("Cosmos").validate().is_err());
+
+        let mut config = Config::default();
+        config.set_bech32_prefix_for_validator("cosmos", "cosmospub");
+        assert!(config.validate().is_err());
+    }
+}
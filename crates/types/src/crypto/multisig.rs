@@ -1,16 +1,174 @@
+use crate::base::account_id::AccountId;
+use crate::crypto::public_key::PublicKey;
 use crate::errors::Error;
+use ibc_proto::cosmos::crypto::multisig::v1beta1::MultiSignature as RawMultiSignature;
 use ibc_proto::cosmos::crypto::multisig::LegacyAminoPubKey as RawLegacyAminoPubKey;
+use ibc_proto::cosmos::tx::v1beta1::{mode_info, ModeInfo as RawModeInfo};
 use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use sha2::{Digest, Sha256};
+use v1beta1::CompactBitArray;
+
+/// Amino prefix of `tendermint/PubKeySecp256k1`.
+const AMINO_SECP256K1_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// Amino prefix of `tendermint/PubKeyEd25519`.
+const AMINO_ED25519_PREFIX: [u8; 4] = [0x16, 0x24, 0xde, 0x64];
+
+/// Amino prefix of `tendermint/PubKeyMultisigThreshold`.
+const AMINO_MULTISIG_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+
+/// Length (in bytes) of an address derived from a public key.
+const ADDRESS_LENGTH: usize = 20;
 
 /// LegacyAminoPubKey specifies a public key type
 /// which nests multiple public keys and a threshold,
 /// it uses legacy amino address rules.
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LegacyAminoPubKey {
     pub threshold: u32,
     pub public_keys: Vec<Any>,
 }
 
+impl LegacyAminoPubKey {
+    /// Protobuf [`Any`] type URL for legacy amino multisig public keys
+    pub const TYPE_URL: &'static str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
+
+    /// Create a new threshold multisig public key, mirrors Go's `NewLegacyAminoPubKey`.
+    pub fn new(threshold: u32, public_keys: Vec<Any>) -> Result<Self, Error> {
+        if threshold == 0 {
+            return Err(Error::Custom(
+                "threshold k of n multisignature: k <= 0".into(),
+            ));
+        }
+        if public_keys.len() < threshold as usize {
+            return Err(Error::Custom(
+                "threshold k of n multisignature: len(pubKeys) < k".into(),
+            ));
+        }
+
+        Ok(Self {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// Legacy amino (binary bare) encoding of this public key, this is what the
+    /// address of a multisig is derived from.
+    pub fn amino_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = AMINO_MULTISIG_PREFIX.to_vec();
+        // field 1: threshold (varint)
+        bytes.push(0x08);
+        encode_uvarint(u64::from(self.threshold), &mut bytes);
+        for public_key in &self.public_keys {
+            let encoded = amino_encode_public_key(public_key)?;
+            // field 2: public keys (length-delimited)
+            bytes.push(0x12);
+            encode_uvarint(encoded.len() as u64, &mut bytes);
+            bytes.extend(encoded);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Address of the multisig i.e. the first 20 bytes of the SHA-256 hash of its amino
+    /// encoding, mirrors Go's `LegacyAminoPubKey.Address()`.
+    pub fn address(&self) -> Result<Vec<u8>, Error> {
+        let hash = Sha256::digest(self.amino_bytes()?);
+        Ok(hash[..ADDRESS_LENGTH].to_vec())
+    }
+
+    /// Get the [`AccountId`] for this multisig public key.
+    pub fn account_id(&self, prefix: &str) -> Result<AccountId, Error> {
+        AccountId::new(prefix, &self.address()?)
+    }
+
+    /// Convert this multisig public key to a Protobuf [`Any`] type.
+    pub fn to_any(&self) -> Any {
+        Any {
+            type_url: Self::TYPE_URL.to_owned(),
+            value: RawLegacyAminoPubKey::from(self.clone()).encode_to_vec(),
+        }
+    }
+
+    /// Verify `signature` against this multisig public key.
+    ///
+    /// `get_sign_bytes` returns the bytes that were signed for a given sign mode. Nested
+    /// multisig public keys are verified recursively.
+    pub fn verify_multisignature<F>(
+        &self,
+        get_sign_bytes: &F,
+        signature: &MultiSignatureData,
+    ) -> Result<(), Error>
+    where
+        F: Fn(i32) -> Result<Vec<u8>, Error>,
+    {
+        let bit_array = &signature.bit_array;
+        let signatures = &signature.signatures;
+        let size = bit_array.count();
+        let threshold = self.threshold as usize;
+
+        // ensure bit array is the correct size
+        if self.public_keys.len() != size {
+            return Err(Error::Custom(format!(
+                "bit array size is incorrect, expecting: {}",
+                self.public_keys.len()
+            )));
+        }
+        // ensure size of signature list
+        if signatures.len() < threshold || signatures.len() > size {
+            return Err(Error::Custom(format!(
+                "signature size is incorrect {}",
+                signatures.len()
+            )));
+        }
+        // ensure at least k signatures are set
+        let set = bit_array.num_true_bits_before(size);
+        if set < threshold {
+            return Err(Error::Custom(format!(
+                "not enough signatures set, have {set}, expected {threshold}"
+            )));
+        }
+        if set != signatures.len() {
+            return Err(Error::Custom(format!(
+                "bit array has {set} signatures set but {} were provided",
+                signatures.len()
+            )));
+        }
+
+        let mut signatures = signatures.iter();
+        for (i, public_key) in self.public_keys.iter().enumerate() {
+            if !bit_array.get_index(i) {
+                continue;
+            }
+            // `set == signatures.len()` so this can't run out
+            let Some(data) = signatures.next() else {
+                break;
+            };
+            match data {
+                SignatureData::Single { mode, signature } => {
+                    let msg = get_sign_bytes(*mode)?;
+                    PublicKey::try_from(public_key)?
+                        .verify_signature(&msg, signature)
+                        .map_err(|_| {
+                            Error::Custom(format!("unable to verify signature at index {i}"))
+                        })?;
+                }
+                SignatureData::Multi(nested) => {
+                    if public_key.type_url != Self::TYPE_URL {
+                        return Err(Error::Custom(format!(
+                            "improper signature data type for index {i}"
+                        )));
+                    }
+                    Self::try_from(public_key)?.verify_multisignature(get_sign_bytes, nested)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl TryFrom<RawLegacyAminoPubKey> for LegacyAminoPubKey {
     type Error = Error;
 
@@ -34,6 +192,110 @@ impl From<LegacyAminoPubKey> for RawLegacyAminoPubKey {
     }
 }
 
+impl TryFrom<&Any> for LegacyAminoPubKey {
+    type Error = Error;
+
+    fn try_from(any: &Any) -> Result<Self, Self::Error> {
+        if any.type_url != Self::TYPE_URL {
+            return Err(Error::Custom(format!(
+                "invalid type URL for multisig public key: {}",
+                any.type_url
+            )));
+        }
+        RawLegacyAminoPubKey::decode(&*any.value)?.try_into()
+    }
+}
+
+/// Signature data of a single signer of a transaction, mirrors Go's `signing.SignatureData`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SignatureData {
+    /// Signature of a single key, along with the sign mode that was used.
+    Single { mode: i32, signature: Vec<u8> },
+    /// Signatures of a multisig key.
+    Multi(MultiSignatureData),
+}
+
+/// MultiSignatureData represents the nested signatures of a multisig public key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiSignatureData {
+    /// Bit array specifying which keys within the multisig are signing.
+    pub bit_array: CompactBitArray,
+    /// Signatures of the keys set in `bit_array`.
+    pub signatures: Vec<SignatureData>,
+}
+
+impl SignatureData {
+    /// Reconstruct the signature data from a transaction's `ModeInfo` and the raw signature
+    /// bytes of the corresponding signer, mirrors Go's `ModeInfoAndSigToSignatureData`.
+    pub fn from_mode_info(mode_info: &RawModeInfo, signature: &[u8]) -> Result<Self, Error> {
+        match &mode_info.sum {
+            Some(mode_info::Sum::Single(single)) => Ok(Self::Single {
+                mode: single.mode,
+                signature: signature.to_vec(),
+            }),
+            Some(mode_info::Sum::Multi(multi)) => {
+                let bit_array: CompactBitArray = multi
+                    .bitarray
+                    .clone()
+                    .ok_or_else(|| Error::Custom("missing multisig bit array".into()))?
+                    .try_into()?;
+                let multi_signature = RawMultiSignature::decode(signature)?;
+                if multi_signature.signatures.len() != multi.mode_infos.len() {
+                    return Err(Error::Custom(format!(
+                        "expected {} multisig signatures, got {}",
+                        multi.mode_infos.len(),
+                        multi_signature.signatures.len()
+                    )));
+                }
+                let signatures = multi
+                    .mode_infos
+                    .iter()
+                    .zip(multi_signature.signatures.iter())
+                    .map(|(mode_info, signature)| Self::from_mode_info(mode_info, signature))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Self::Multi(MultiSignatureData {
+                    bit_array,
+                    signatures,
+                }))
+            }
+            None => Err(Error::Custom("missing mode info".into())),
+        }
+    }
+}
+
+/// Legacy amino (binary bare) encoding of a public key wrapped in an [`Any`].
+fn amino_encode_public_key(public_key: &Any) -> Result<Vec<u8>, Error> {
+    if public_key.type_url == LegacyAminoPubKey::TYPE_URL {
+        return LegacyAminoPubKey::try_from(public_key)?.amino_bytes();
+    }
+
+    let key = PublicKey::try_from(public_key)?;
+    let prefix = match key.type_url() {
+        PublicKey::SECP256K1_TYPE_URL => AMINO_SECP256K1_PREFIX,
+        PublicKey::ED25519_TYPE_URL => AMINO_ED25519_PREFIX,
+        other => {
+            return Err(Error::Custom(format!(
+                "unsupported public key type: {other}"
+            )))
+        }
+    };
+    let key_bytes = key.to_bytes();
+    let mut bytes = prefix.to_vec();
+    encode_uvarint(key_bytes.len() as u64, &mut bytes);
+    bytes.extend(key_bytes);
+
+    Ok(bytes)
+}
+
+fn encode_uvarint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
 pub mod v1beta1 {
     use crate::errors::Error;
     use ibc_proto::cosmos::crypto::multisig::v1beta1::CompactBitArray as RawCompactBitArray;
@@ -42,7 +304,7 @@ pub mod v1beta1 {
     /// MultiSignature wraps the signatures from a multisig.LegacyAminoPubKey.
     /// See cosmos.tx.v1betata1.ModeInfo.Multi for how to specify which signers
     /// signed and with which modes.
-    #[derive(Clone, PartialEq)]
+    #[derive(Clone, Debug, PartialEq)]
     pub struct MultiSignature {
         pub signatures: Vec<Vec<u8>>,
     }
@@ -69,12 +331,82 @@ pub mod v1beta1 {
     /// This is used to ensure that the encoded data takes up a minimal amount of
     /// space after proto encoding.
     /// This is not thread safe, and is not intended for concurrent usage.
-    #[derive(Clone, PartialEq)]
+    #[derive(Clone, Debug, PartialEq)]
     pub struct CompactBitArray {
         pub extra_bits_stored: u32,
         pub elems: Vec<u8>,
     }
 
+    impl CompactBitArray {
+        /// Create a new bit array of `bits` bits, all set to false.
+        /// Returns `None` if `bits` is 0.
+        pub fn new(bits: usize) -> Option<Self> {
+            if bits == 0 {
+                return None;
+            }
+
+            Some(Self {
+                extra_bits_stored: (bits % 8) as u32,
+                elems: vec![0; (bits + 7) / 8],
+            })
+        }
+
+        /// Number of bits in the bit array.
+        pub fn count(&self) -> usize {
+            if self.elems.is_empty() {
+                0
+            } else if self.extra_bits_stored == 0 {
+                self.elems.len() * 8
+            } else {
+                (self.elems.len() - 1) * 8 + self.extra_bits_stored as usize
+            }
+        }
+
+        /// Returns true if the bit at index `i` is set.
+        /// Returns false if `i` is out of range.
+        pub fn get_index(&self, i: usize) -> bool {
+            if i >= self.count() {
+                return false;
+            }
+
+            self.elems[i >> 3] & (1 << (7 - (i % 8))) > 0
+        }
+
+        /// Set the bit at index `i` to `value`.
+        /// Returns false if `i` is out of range.
+        pub fn set_index(&mut self, i: usize, value: bool) -> bool {
+            if i >= self.count() {
+                return false;
+            }
+
+            if value {
+                self.elems[i >> 3] |= 1 << (7 - (i % 8));
+            } else {
+                self.elems[i >> 3] &= !(1 << (7 - (i % 8)));
+            }
+
+            true
+        }
+
+        /// Number of bits set to true before `index`, i.e. the position of the signature for
+        /// the key at `index` within the list of signatures.
+        pub fn num_true_bits_before(&self, index: usize) -> usize {
+            let index = index.min(self.count());
+            let full_elems = index / 8;
+            let mut ones = self.elems[..full_elems]
+                .iter()
+                .map(|elem| elem.count_ones() as usize)
+                .sum();
+
+            let remaining_bits = index % 8;
+            if remaining_bits > 0 {
+                ones += (self.elems[full_elems] >> (8 - remaining_bits)).count_ones() as usize;
+            }
+
+            ones
+        }
+    }
+
     impl TryFrom<RawCompactBitArray> for CompactBitArray {
         type Error = Error;
 
@@ -82,6 +414,12 @@ pub mod v1beta1 {
             let extra_bits_stored = value.extra_bits_stored;
             let elems = value.elems;
 
+            if extra_bits_stored >= 8 {
+                return Err(Error::Custom(format!(
+                    "invalid compact bit array: {extra_bits_stored} extra bits stored"
+                )));
+            }
+
             Ok(Self {
                 extra_bits_stored,
                 elems,
@@ -98,3 +436,150 @@ pub mod v1beta1 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::v1beta1::CompactBitArray;
+    use super::{LegacyAminoPubKey, MultiSignatureData, SignatureData};
+    use crate::crypto::public_key::PublicKey;
+    use crate::errors::Error;
+    use ibc_proto::google::protobuf::Any;
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    const SIGN_MODE_DIRECT: i32 = 1;
+    const MSG: &[u8] = b"sign bytes";
+
+    fn signing_keys(n: u8) -> Vec<SigningKey> {
+        (1..=n)
+            .map(|i| SigningKey::from_slice(&[i; 32]).unwrap())
+            .collect()
+    }
+
+    fn to_any(key: &SigningKey) -> Any {
+        PublicKey::from(key.verifying_key()).into()
+    }
+
+    fn sign(key: &SigningKey) -> SignatureData {
+        let signature: Signature = key.sign(MSG);
+        SignatureData::Single {
+            mode: SIGN_MODE_DIRECT,
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    fn get_sign_bytes(_mode: i32) -> Result<Vec<u8>, Error> {
+        Ok(MSG.to_vec())
+    }
+
+    #[test]
+    fn compact_bit_array() {
+        let mut bit_array = CompactBitArray::new(10).unwrap();
+        assert_eq!(bit_array.count(), 10);
+        assert_eq!(bit_array.elems.len(), 2);
+
+        assert!(bit_array.set_index(0, true));
+        assert!(bit_array.set_index(7, true));
+        assert!(bit_array.set_index(9, true));
+        assert!(!bit_array.set_index(10, true));
+
+        assert!(bit_array.get_index(0));
+        assert!(!bit_array.get_index(1));
+        assert!(bit_array.get_index(9));
+        assert!(!bit_array.get_index(10));
+
+        assert_eq!(bit_array.num_true_bits_before(0), 0);
+        assert_eq!(bit_array.num_true_bits_before(1), 1);
+        assert_eq!(bit_array.num_true_bits_before(8), 2);
+        assert_eq!(bit_array.num_true_bits_before(9), 2);
+        assert_eq!(bit_array.num_true_bits_before(10), 3);
+        assert_eq!(bit_array.num_true_bits_before(100), 3);
+
+        assert!(bit_array.set_index(7, false));
+        assert_eq!(bit_array.num_true_bits_before(10), 2);
+        assert!(CompactBitArray::new(0).is_none());
+    }
+
+    #[test]
+    fn verify_threshold_multisig() {
+        let keys = signing_keys(3);
+        let multisig = LegacyAminoPubKey::new(2, keys.iter().map(to_any).collect()).unwrap();
+
+        let mut bit_array = CompactBitArray::new(3).unwrap();
+        bit_array.set_index(0, true);
+        bit_array.set_index(2, true);
+        let signature = MultiSignatureData {
+            bit_array: bit_array.clone(),
+            signatures: vec![sign(&keys[0]), sign(&keys[2])],
+        };
+        assert!(multisig
+            .verify_multisignature(&get_sign_bytes, &signature)
+            .is_ok());
+
+        // signatures don't match the keys set in the bit array
+        let signature = MultiSignatureData {
+            bit_array,
+            signatures: vec![sign(&keys[0]), sign(&keys[1])],
+        };
+        assert!(multisig
+            .verify_multisignature(&get_sign_bytes, &signature)
+            .is_err());
+
+        // below threshold
+        let mut bit_array = CompactBitArray::new(3).unwrap();
+        bit_array.set_index(1, true);
+        let signature = MultiSignatureData {
+            bit_array,
+            signatures: vec![sign(&keys[1])],
+        };
+        assert!(multisig
+            .verify_multisignature(&get_sign_bytes, &signature)
+            .is_err());
+    }
+
+    #[test]
+    fn verify_nested_multisig() {
+        let keys = signing_keys(3);
+        let nested = LegacyAminoPubKey::new(1, vec![to_any(&keys[1]), to_any(&keys[2])]).unwrap();
+        let multisig = LegacyAminoPubKey::new(2, vec![to_any(&keys[0]), nested.to_any()]).unwrap();
+
+        let mut nested_bit_array = CompactBitArray::new(2).unwrap();
+        nested_bit_array.set_index(1, true);
+        let mut bit_array = CompactBitArray::new(2).unwrap();
+        bit_array.set_index(0, true);
+        bit_array.set_index(1, true);
+        let signature = MultiSignatureData {
+            bit_array,
+            signatures: vec![
+                sign(&keys[0]),
+                SignatureData::Multi(MultiSignatureData {
+                    bit_array: nested_bit_array,
+                    signatures: vec![sign(&keys[2])],
+                }),
+            ],
+        };
+        assert!(multisig
+            .verify_multisignature(&get_sign_bytes, &signature)
+            .is_ok());
+    }
+
+    #[test]
+    fn multisig_address() {
+        let keys = signing_keys(2);
+        let multisig = LegacyAminoPubKey::new(2, keys.iter().map(to_any).collect()).unwrap();
+
+        let amino = multisig.amino_bytes().unwrap();
+        // prefix, threshold, then each key as `prefix || len || key`
+        assert_eq!(&amino[..6], &[0x22, 0xc1, 0xf7, 0xe2, 0x08, 0x02]);
+        assert_eq!(&amino[6..12], &[0x12, 0x26, 0xeb, 0x5a, 0xe9, 0x87]);
+        assert_eq!(amino.len(), 6 + 2 * (2 + 4 + 1 + 33));
+
+        assert_eq!(multisig.address().unwrap().len(), 20);
+        let reordered = LegacyAminoPubKey::new(2, keys.iter().rev().map(to_any).collect()).unwrap();
+        assert_ne!(multisig.address().unwrap(), reordered.address().unwrap());
+        assert!(multisig
+            .account_id("cosmos")
+            .unwrap()
+            .as_ref()
+            .starts_with("cosmos1"));
+    }
+}
@@ -80,6 +80,31 @@ impl PublicKey {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Verify a signature over `msg` made by this [`PublicKey`].
+    ///
+    /// Only secp256k1 keys are supported, the signature must be the 64 byte `R || S`
+    /// encoding with a low `S` value (as required by the Go SDK).
+    pub fn verify_signature(&self, msg: &[u8], signature: &[u8]) -> Result<(), Error> {
+        use k256::ecdsa::signature::Verifier;
+
+        match &self.0 {
+            tendermint::PublicKey::Secp256k1(verifying_key) => {
+                let signature = k256::ecdsa::Signature::from_slice(signature)
+                    .map_err(|e| Error::Custom(format!("invalid signature: {e}")))?;
+                if signature.normalize_s().is_some() {
+                    return Err(Error::Custom("signature is not in lower-S form".into()));
+                }
+                verifying_key
+                    .verify(msg, &signature)
+                    .map_err(|e| Error::Custom(format!("signature verification failed: {e}")))
+            }
+            _ => Err(Error::Custom(format!(
+                "signature verification is not supported for {}",
+                self.type_url()
+            ))),
+        }
+    }
 }
 
 impl From<k256::ecdsa::VerifyingKey> for PublicKey {
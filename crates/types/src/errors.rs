@@ -45,8 +45,23 @@ pub enum Error {
     #[error("empty bech32 address")]
     EmptyBech32Address,
 
-    #[error("invalid bech32 prefix")]
-    InvalidBech32Prefix,
+    #[error("invalid bech32 prefix: expected `{expected}`, got `{got}`")]
+    InvalidBech32Prefix { expected: String, got: String },
+
+    #[error("bech32 error: `{0}`")]
+    Bech32(#[from] bech32::Error),
+
+    #[error("invalid hex address: `{0}`")]
+    InvalidHexAddress(#[from] hex::FromHexError),
+
+    #[error("addresses cannot be empty")]
+    EmptyAddress,
+
+    #[error("invalid address length: expected 20 or 32 bytes, got `{0}`")]
+    InvalidAddressLength(usize),
+
+    #[error("address length should be max {max} bytes, got {len}")]
+    AddressTooLong { max: usize, len: usize },
 
     #[error("decoding Bech32 address failed: must provide a non empty address")]
     Bech32EmptyAddress,
@@ -65,6 +80,9 @@ pub enum Error {
 
     #[error("account id error: `{0}`")]
     InvalidAccountId(String),
+
+    #[error("config is sealed")]
+    ConfigSealed,
 }
 
 impl From<std::convert::Infallible> for Error {
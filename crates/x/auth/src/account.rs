@@ -1,18 +1,54 @@
 use std::convert::{TryFrom, TryInto};
 
 use cosmos_helper::Path;
-use cosmrs::AccountId;
-use ibc_proto::{cosmos::auth::v1beta1::BaseAccount, google::protobuf::Any};
+use cosmos_types::address::hash::module;
+use cosmos_types::crypto::multisig::LegacyAminoPubKey;
+use cosmos_x_bank_type::Coin;
+use cosmrs::{crypto::PublicKey, tx::SignerPublicKey, AccountId};
+use ibc_proto::{
+    cosmos::auth::v1beta1::{BaseAccount, ModuleAccount},
+    google::protobuf::Any,
+};
 use prost::Message;
 
+use super::config::ModuleAccountPermission;
 use super::context::Account;
+use super::vesting::{
+    proto::{
+        BaseVestingAccount, ContinuousVestingAccount, DelayedVestingAccount,
+        PeriodicVestingAccount, PermanentLockedAccount,
+    },
+    Vesting, VestingSchedule, CONTINUOUS_VESTING_ACCOUNT_TYPE_URL,
+    DELAYED_VESTING_ACCOUNT_TYPE_URL, PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+    PERMANENT_LOCKED_ACCOUNT_TYPE_URL,
+};
+use crate::error::Error;
 
 /// Address of the account that the relayer uses to sign basecoin transactions.
-/// This is hardcoded as we don't verify signatures currently.
 pub const RELAYER_ACCOUNT: &str = "cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efws";
-pub const ACCOUNT_PREFIX: &str = "cosmos";
 
-#[derive(Clone)]
+/// Returns the bech32 prefix of account addresses, as set in the chain's address config.
+pub fn account_prefix() -> String {
+    cosmos_types::config::bech32_account_addr_prefix()
+}
+
+pub const BASE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.BaseAccount";
+pub const MODULE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.ModuleAccount";
+
+/// Permission of a module account to mint coins.
+pub const MINTER: &str = "minter";
+/// Permission of a module account to burn coins.
+pub const BURNER: &str = "burner";
+/// Permission of a module account to stake coins.
+pub const STAKING: &str = "staking";
+
+/// Returns the address of the module account with the specified name, i.e. its ADR-028 module
+/// address without derivation keys.
+pub fn module_address(name: &str) -> AccountId {
+    AccountId::new(&account_prefix(), &module(name, &[])).unwrap() // safety - the prefix is validated by the address config
+}
+
+#[derive(Clone, Debug)]
 pub struct AccountsPath(pub AccountId);
 
 impl From<AccountsPath> for Path {
@@ -21,38 +57,205 @@ impl From<AccountsPath> for Path {
     }
 }
 
-#[derive(Clone)]
+/// Path of the next account number, i.e. of the global account-number sequence.
+#[derive(Clone, Debug)]
+pub struct GlobalAccountNumberPath;
+
+impl From<GlobalAccountNumberPath> for Path {
+    fn from(_: GlobalAccountNumberPath) -> Self {
+        "globalAccountNumber".to_owned().try_into().unwrap() // safety - cannot fail as the path is a constant
+    }
+}
+
+/// Path of the address of the account with the specified account number.
+#[derive(Clone, Debug)]
+pub struct AccountNumbersPath(pub u64);
+
+impl From<AccountNumbersPath> for Path {
+    fn from(path: AccountNumbersPath) -> Self {
+        format!("accountNumbers/{}", path.0).try_into().unwrap() // safety - cannot fail as AccountNumbersPath is correct-by-construction
+    }
+}
+
+/// Path of the auth module's params.
+#[derive(Clone, Debug)]
+pub struct ParamsPath;
+
+impl From<ParamsPath> for Path {
+    fn from(_: ParamsPath) -> Self {
+        "params".to_owned().try_into().unwrap() // safety - cannot fail as the path is a constant
+    }
+}
+
+/// Kind of an account, along with the state specific to that kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountKind {
+    /// A user account
+    Base,
+    /// An account owned by a module, which can't sign transactions
+    Module {
+        name: String,
+        permissions: Vec<String>,
+    },
+    /// A user account whose coins are locked until they vest
+    Vesting(Vesting),
+}
+
+#[derive(Clone, Debug)]
 pub struct AuthAccount {
     address: AccountId,
+    pub_key: Option<Any>,
     number: u64,
     pub sequence: u64,
+    kind: AccountKind,
 }
 
 impl AuthAccount {
-    pub fn new(address: AccountId) -> Self {
+    pub fn new(address: AccountId, number: u64) -> Self {
         Self {
             address,
-            number: 0,
+            pub_key: None,
+            number,
             sequence: 0,
+            kind: AccountKind::Base,
         }
     }
+
+    /// Returns a new module account, at the address derived from its name.
+    pub fn new_module_account(permission: &ModuleAccountPermission, number: u64) -> Self {
+        Self {
+            address: module_address(&permission.account),
+            pub_key: None,
+            number,
+            sequence: 0,
+            kind: AccountKind::Module {
+                name: permission.account.clone(),
+                permissions: permission.permissions.clone(),
+            },
+        }
+    }
+
+    /// Returns a new vesting account, whose coins are locked until they vest.
+    pub fn new_vesting_account(address: AccountId, number: u64, vesting: Vesting) -> Self {
+        Self {
+            kind: AccountKind::Vesting(vesting),
+            ..Self::new(address, number)
+        }
+    }
+
+    pub fn kind(&self) -> &AccountKind {
+        &self.kind
+    }
+
+    /// Returns the name of the module owning the account, if it's a module account.
+    pub fn module_name(&self) -> Option<&str> {
+        match &self.kind {
+            AccountKind::Module { name, .. } => Some(name),
+            AccountKind::Base | AccountKind::Vesting(_) => None,
+        }
+    }
+
+    /// Sets the account's public key, which must hash to the account's address.
+    pub fn set_pub_key(&mut self, pub_key: &SignerPublicKey) -> Result<(), Error> {
+        let (pub_key, address) = signer_pub_key(pub_key)?;
+        if address != self.address {
+            return Err(Error::InvalidPubKey {
+                reason: format!("pubKey does not match signer address {}", self.address),
+            });
+        }
+        self.pub_key = Some(pub_key);
+        Ok(())
+    }
+}
+
+/// Returns the public key of a signer as a protobuf `Any`, along with the address it hashes to.
+/// Supports both single keys and (possibly nested) legacy amino multisig keys.
+pub fn signer_pub_key(pub_key: &SignerPublicKey) -> Result<(Any, AccountId), Error> {
+    let multisig = match pub_key {
+        SignerPublicKey::Single(pub_key) => return single_pub_key(pub_key),
+        SignerPublicKey::LegacyAminoMultisig(multisig) => {
+            let public_keys = multisig
+                .public_keys
+                .iter()
+                .map(|pub_key| pub_key.to_any().map(proto_any))
+                .collect::<Result<_, _>>()
+                .map_err(invalid_pub_key)?;
+            LegacyAminoPubKey {
+                threshold: multisig.threshold,
+                public_keys,
+            }
+        }
+        // public keys of types unknown to `cosmrs` are handed over as-is, only multisigs are
+        // supported among them
+        SignerPublicKey::Any(any) if any.type_url == LegacyAminoPubKey::TYPE_URL => {
+            LegacyAminoPubKey::try_from(&proto_any(any.clone())).map_err(invalid_pub_key)?
+        }
+        SignerPublicKey::Any(any) => {
+            return Err(Error::InvalidPubKey {
+                reason: format!("unsupported public key type {}", any.type_url),
+            })
+        }
+    };
+
+    let address = multisig.address().map_err(invalid_pub_key)?;
+    let address = AccountId::new(&account_prefix(), &address).map_err(invalid_pub_key)?;
+    Ok((multisig.to_any(), address))
+}
+
+pub(crate) fn single_pub_key(pub_key: &PublicKey) -> Result<(Any, AccountId), Error> {
+    let address = pub_key
+        .account_id(&account_prefix())
+        .map_err(invalid_pub_key)?;
+    let pub_key = pub_key.to_any().map_err(invalid_pub_key)?;
+    Ok((proto_any(pub_key), address))
+}
+
+fn proto_any(any: cosmrs::Any) -> Any {
+    Any {
+        type_url: any.type_url,
+        value: any.value,
+    }
+}
+
+fn invalid_pub_key(e: impl ToString) -> Error {
+    Error::InvalidPubKey {
+        reason: e.to_string(),
+    }
 }
 
 impl Account for AuthAccount {
     type Address = AccountId;
-    type PubKey = Vec<u8>;
+    type PubKey = Option<Any>;
 
     fn address(&self) -> &Self::Address {
         &self.address
     }
 
     fn pub_key(&self) -> &Self::PubKey {
-        unimplemented!()
+        &self.pub_key
+    }
+
+    fn account_number(&self) -> u64 {
+        self.number
+    }
+
+    fn has_permission(&self, permission: &str) -> bool {
+        match &self.kind {
+            AccountKind::Module { permissions, .. } => permissions.iter().any(|p| p == permission),
+            AccountKind::Base | AccountKind::Vesting(_) => false,
+        }
     }
 
     fn sequence(&self) -> u64 {
         self.sequence
     }
+
+    fn locked_coins(&self, block_time: i64) -> Vec<Coin> {
+        match &self.kind {
+            AccountKind::Vesting(vesting) => vesting.locked_coins(block_time),
+            AccountKind::Base | AccountKind::Module { .. } => vec![],
+        }
+    }
 }
 
 impl ibc_proto::protobuf::Protobuf<BaseAccount> for AuthAccount {}
@@ -66,17 +269,105 @@ impl TryFrom<BaseAccount> for AuthAccount {
                 .address
                 .parse()
                 .map_err(|_| "Failed to parse address".to_string())?,
+            pub_key: account.pub_key,
             number: account.account_number,
             sequence: account.sequence,
+            kind: AccountKind::Base,
         })
     }
 }
 
+impl TryFrom<ModuleAccount> for AuthAccount {
+    type Error = String;
+
+    fn try_from(account: ModuleAccount) -> Result<Self, Self::Error> {
+        let base_account = account
+            .base_account
+            .ok_or_else(|| "Missing base account".to_string())?;
+        Ok(AuthAccount {
+            kind: AccountKind::Module {
+                name: account.name,
+                permissions: account.permissions,
+            },
+            ..AuthAccount::try_from(base_account)?
+        })
+    }
+}
+
+impl AuthAccount {
+    /// Decodes a vesting account from its `BaseVestingAccount` and schedule.
+    fn try_from_vesting(
+        account: Option<BaseVestingAccount>,
+        schedule: VestingSchedule,
+    ) -> Result<Self, String> {
+        let (base_account, vesting) = Vesting::from_raw(account, schedule)?;
+        Ok(AuthAccount {
+            kind: AccountKind::Vesting(vesting),
+            ..AuthAccount::try_from(base_account)?
+        })
+    }
+}
+
+impl TryFrom<Any> for AuthAccount {
+    type Error = String;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        match any.type_url.as_str() {
+            BASE_ACCOUNT_TYPE_URL => BaseAccount::decode(any.value.as_slice())
+                .map_err(|e| e.to_string())?
+                .try_into(),
+            MODULE_ACCOUNT_TYPE_URL => ModuleAccount::decode(any.value.as_slice())
+                .map_err(|e| e.to_string())?
+                .try_into(),
+            CONTINUOUS_VESTING_ACCOUNT_TYPE_URL => {
+                let account = ContinuousVestingAccount::decode(any.value.as_slice())
+                    .map_err(|e| e.to_string())?;
+                Self::try_from_vesting(
+                    account.base_vesting_account,
+                    VestingSchedule::Continuous {
+                        start_time: account.start_time,
+                    },
+                )
+            }
+            DELAYED_VESTING_ACCOUNT_TYPE_URL => {
+                let account = DelayedVestingAccount::decode(any.value.as_slice())
+                    .map_err(|e| e.to_string())?;
+                Self::try_from_vesting(account.base_vesting_account, VestingSchedule::Delayed)
+            }
+            PERIODIC_VESTING_ACCOUNT_TYPE_URL => {
+                let account = PeriodicVestingAccount::decode(any.value.as_slice())
+                    .map_err(|e| e.to_string())?;
+                let periods = account
+                    .vesting_periods
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?;
+                Self::try_from_vesting(
+                    account.base_vesting_account,
+                    VestingSchedule::Periodic {
+                        start_time: account.start_time,
+                        periods,
+                    },
+                )
+            }
+            PERMANENT_LOCKED_ACCOUNT_TYPE_URL => {
+                let account = PermanentLockedAccount::decode(any.value.as_slice())
+                    .map_err(|e| e.to_string())?;
+                Self::try_from_vesting(
+                    account.base_vesting_account,
+                    VestingSchedule::PermanentLocked,
+                )
+            }
+            type_url => Err(format!("Unknown account type `{type_url}`")),
+        }
+    }
+}
+
 impl From<AuthAccount> for BaseAccount {
     fn from(account: AuthAccount) -> Self {
         BaseAccount {
             address: account.address.to_string(),
-            pub_key: None,
+            pub_key: account.pub_key,
             account_number: account.number,
             sequence: account.sequence,
         }
@@ -85,10 +376,49 @@ impl From<AuthAccount> for BaseAccount {
 
 impl From<AuthAccount> for Any {
     fn from(account: AuthAccount) -> Self {
-        let account = BaseAccount::from(account);
-        Any {
-            type_url: "/cosmos.auth.v1beta1.BaseAccount".to_string(),
-            value: account.encode_to_vec(),
+        match account.kind.clone() {
+            AccountKind::Base => Any {
+                type_url: BASE_ACCOUNT_TYPE_URL.to_string(),
+                value: BaseAccount::from(account).encode_to_vec(),
+            },
+            AccountKind::Module { name, permissions } => Any {
+                type_url: MODULE_ACCOUNT_TYPE_URL.to_string(),
+                value: ModuleAccount {
+                    base_account: Some(account.into()),
+                    name,
+                    permissions,
+                }
+                .encode_to_vec(),
+            },
+            AccountKind::Vesting(vesting) => {
+                let type_url = vesting.type_url().to_string();
+                let base_vesting_account = Some(vesting.to_raw(account.into()));
+                let value = match vesting.schedule {
+                    VestingSchedule::Continuous { start_time } => ContinuousVestingAccount {
+                        base_vesting_account,
+                        start_time,
+                    }
+                    .encode_to_vec(),
+                    VestingSchedule::Delayed => DelayedVestingAccount {
+                        base_vesting_account,
+                    }
+                    .encode_to_vec(),
+                    VestingSchedule::Periodic {
+                        start_time,
+                        periods,
+                    } => PeriodicVestingAccount {
+                        base_vesting_account,
+                        start_time,
+                        vesting_periods: periods.into_iter().map(Into::into).collect(),
+                    }
+                    .encode_to_vec(),
+                    VestingSchedule::PermanentLocked => PermanentLockedAccount {
+                        base_vesting_account,
+                    }
+                    .encode_to_vec(),
+                };
+                Any { type_url, value }
+            }
         }
     }
 }
@@ -0,0 +1,74 @@
+//! Configuration of the auth module in the app config, i.e. the counterpart of the Go SDK's
+//! `cosmos.auth.module.v1.Module`.
+
+use std::collections::HashSet;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::account::{BURNER, MINTER, STAKING};
+use crate::error::Error;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Config {
+    /// Module accounts registered at genesis, along with their permissions
+    #[serde(default)]
+    pub module_account_permissions: Vec<ModuleAccountPermission>,
+}
+
+impl Config {
+    /// Checks that the module accounts are valid and have unique names.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut names = HashSet::new();
+        for permission in &self.module_account_permissions {
+            permission.validate()?;
+            if !names.insert(permission.account.as_str()) {
+                return Err(Error::InvalidModuleAccount {
+                    reason: format!("duplicate module account `{}`", permission.account),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Permissions of a module account, as configured for the auth module (the counterpart of the Go
+/// SDK's `ModuleAccountPermission`).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModuleAccountPermission {
+    /// Name of the module account
+    pub account: String,
+    /// Permissions of the module account, i.e. `minter`, `burner` and/or `staking`
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+impl ModuleAccountPermission {
+    pub fn new(account: impl Into<String>, permissions: &[&str]) -> Self {
+        Self {
+            account: account.into(),
+            permissions: permissions.iter().map(|&p| p.to_owned()).collect(),
+        }
+    }
+
+    /// Checks that the account has a name and known permissions only.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.account.trim().is_empty() {
+            return Err(Error::InvalidModuleAccount {
+                reason: "module account name cannot be blank".to_owned(),
+            });
+        }
+        match self
+            .permissions
+            .iter()
+            .find(|&p| ![MINTER, BURNER, STAKING].contains(&p.as_str()))
+        {
+            Some(permission) => Err(Error::InvalidModuleAccount {
+                reason: format!(
+                    "unknown permission `{permission}` of module account `{}`",
+                    self.account
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
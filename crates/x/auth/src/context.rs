@@ -1,3 +1,5 @@
+use cosmos_x_bank_type::Coin;
+
 /// AccountI is an interface used to store coins at a given address within state.
 /// It presumes a notion of sequence numbers for replay protection,
 /// a notion of account numbers for replay protection for previously pruned accounts,
@@ -34,8 +36,20 @@ pub trait Account {
     /// Returns the account's public key.
     fn pub_key(&self) -> &Self::PubKey;
 
+    /// Returns the account's number. (used for replay protection of previously pruned accounts)
+    fn account_number(&self) -> u64;
+
+    /// Returns whether the account is a module account holding the specified permission.
+    fn has_permission(&self, permission: &str) -> bool;
+
     /// Returns the account's sequence. (used for replay protection)
     fn sequence(&self) -> u64;
+
+    /// Returns the coins that the account can't spend at the specified block time (as unix
+    /// timestamp), i.e. its vesting coins.
+    fn locked_coins(&self, _block_time: i64) -> Vec<Coin> {
+        vec![]
+    }
 }
 
 pub trait AccountReader {
@@ -50,6 +64,24 @@ pub trait AccountKeeper {
     type Error;
     type Account: Account;
 
+    /// Returns a new account with the next account number and the specified address. Does not
+    /// save the new account to the store.
+    fn new_account_with_address(
+        &mut self,
+        address: <Self::Account as Account>::Address,
+    ) -> Result<Self::Account, Self::Error>;
+
+    /// Returns the next account number and increments the global account-number sequence.
+    fn new_account_number(&mut self) -> Result<u64, Self::Error>;
+
+    /// Returns the account at the specified address, if any.
+    fn get_account(&self, address: &<Self::Account as Account>::Address) -> Option<Self::Account>;
+
+    /// Returns whether an account exists at the specified address.
+    fn has_account(&self, address: &<Self::Account as Account>::Address) -> bool {
+        self.get_account(address).is_some()
+    }
+
     fn set_account(&mut self, account: Self::Account) -> Result<(), Self::Error>;
 
     fn remove_account(&mut self, account: Self::Account) -> Result<(), Self::Error>;
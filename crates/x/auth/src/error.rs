@@ -1,3 +1,8 @@
+use cosmos_x_module_api::error::{
+    AbciError, RegisteredError, ERR_INTERNAL, ERR_INVALID_PUBKEY, ERR_INVALID_REQUEST,
+    ERR_INVALID_SEQUENCE, ERR_NOT_FOUND, ERR_UNAUTHORIZED, ERR_UNKNOWN_ADDRESS,
+    ERR_UNKNOWN_REQUEST, ERR_WRONG_SEQUENCE,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,6 +16,15 @@ pub enum Error {
     #[error("failed to increment signer sequence")]
     FailedToIncrementSignerSequence,
 
+    #[error("account sequence mismatch, expected {expected}, got {got}")]
+    WrongSequence { expected: u64, got: u64 },
+
+    #[error("unauthorized: {reason}")]
+    Unauthorized { reason: String },
+
+    #[error("invalid pubkey: {reason}")]
+    InvalidPubKey { reason: String },
+
     #[error("Invalid path")]
     InvalidPath,
 
@@ -20,6 +34,31 @@ pub enum Error {
     #[error("Data not Found")]
     DataNotFound,
 
+    #[error("invalid genesis state: {reason}")]
+    InvalidGenesis { reason: String },
+
+    #[error("invalid module account: {reason}")]
+    InvalidModuleAccount { reason: String },
+
     #[error("not handled")]
     NotHandled,
 }
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::Unknown(_) => ERR_INTERNAL,
+            Error::UnknownSigner => ERR_UNKNOWN_ADDRESS,
+            Error::FailedToIncrementSignerSequence => ERR_INVALID_SEQUENCE,
+            Error::WrongSequence { .. } => ERR_WRONG_SEQUENCE,
+            Error::Unauthorized { .. } => ERR_UNAUTHORIZED,
+            Error::InvalidPubKey { .. } => ERR_INVALID_PUBKEY,
+            Error::InvalidPath => ERR_INVALID_REQUEST,
+            Error::ProofNotFound | Error::DataNotFound => ERR_NOT_FOUND,
+            Error::InvalidGenesis { .. } | Error::InvalidModuleAccount { .. } => {
+                ERR_INVALID_REQUEST
+            }
+            Error::NotHandled => ERR_UNKNOWN_REQUEST,
+        }
+    }
+}
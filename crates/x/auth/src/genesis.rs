@@ -0,0 +1,308 @@
+use std::collections::HashSet;
+
+use cosmos_x_module_api::genesis::u64_string;
+use cosmrs::{crypto::PublicKey, AccountId};
+use ibc_proto::{cosmos::auth::v1beta1::Params as RawParams, google::protobuf::Any};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::account::{account_prefix, single_pub_key, BASE_ACCOUNT_TYPE_URL};
+use crate::error::Error;
+
+/// Genesis state of the auth module, i.e. `app_state.auth`, as the Go SDK's
+/// `cosmos.auth.v1beta1.GenesisState`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisState {
+    #[serde(default)]
+    pub params: Params,
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+}
+
+/// Params of the auth module, as the Go SDK's `cosmos.auth.v1beta1.Params`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Params {
+    #[serde(with = "u64_string")]
+    pub max_memo_characters: u64,
+    #[serde(with = "u64_string")]
+    pub tx_sig_limit: u64,
+    #[serde(with = "u64_string")]
+    pub tx_size_cost_per_byte: u64,
+    #[serde(with = "u64_string")]
+    pub sig_verify_cost_ed25519: u64,
+    #[serde(with = "u64_string")]
+    pub sig_verify_cost_secp256k1: u64,
+}
+
+impl Default for Params {
+    /// The values of Go's `DefaultParams()`.
+    fn default() -> Self {
+        Self {
+            max_memo_characters: 256,
+            tx_sig_limit: 7,
+            tx_size_cost_per_byte: 10,
+            sig_verify_cost_ed25519: 590,
+            sig_verify_cost_secp256k1: 1000,
+        }
+    }
+}
+
+/// A genesis account, i.e. a proto-JSON encoded `Any` of a `BaseAccount` (module accounts are
+/// created from the auth module's config).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisAccount {
+    #[serde(rename = "@type")]
+    pub type_url: String,
+    pub address: String,
+    #[serde(default)]
+    pub pub_key: Option<Value>,
+    #[serde(default, with = "u64_string")]
+    pub account_number: u64,
+    #[serde(default, with = "u64_string")]
+    pub sequence: u64,
+}
+
+impl GenesisState {
+    /// Decodes the auth module's section of the genesis `app_state`.
+    pub fn decode(genesis: &Value) -> Result<Self, Error> {
+        cosmos_x_module_api::genesis::decode_genesis(genesis)
+            .map_err(|reason| Error::InvalidGenesis { reason })
+    }
+
+    /// Checks that the params are positive and the accounts are valid, with unique addresses and
+    /// account numbers.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.params.validate()?;
+
+        let mut addresses = HashSet::new();
+        let mut account_numbers = HashSet::new();
+        for account in &self.accounts {
+            account.address()?;
+            account.pub_key()?;
+            if !addresses.insert(account.address.as_str()) {
+                return Err(Error::InvalidGenesis {
+                    reason: format!(
+                        "duplicate account found in genesis state; address: {}",
+                        account.address
+                    ),
+                });
+            }
+            if !account_numbers.insert(account.account_number) {
+                return Err(Error::InvalidGenesis {
+                    reason: format!(
+                        "duplicate account number {} found in genesis state; address: {}",
+                        account.account_number, account.address
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Params {
+    pub fn validate(&self) -> Result<(), Error> {
+        for (name, value) in [
+            ("max memo characters", self.max_memo_characters),
+            ("tx signature limit", self.tx_sig_limit),
+            ("tx size cost per byte", self.tx_size_cost_per_byte),
+            (
+                "ED25519 signature verification cost",
+                self.sig_verify_cost_ed25519,
+            ),
+            (
+                "SECP256K1 signature verification cost",
+                self.sig_verify_cost_secp256k1,
+            ),
+        ] {
+            if value == 0 {
+                return Err(Error::InvalidGenesis {
+                    reason: format!("invalid {name}: {value}"),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Params> for RawParams {
+    fn from(params: Params) -> Self {
+        Self {
+            max_memo_characters: params.max_memo_characters,
+            tx_sig_limit: params.tx_sig_limit,
+            tx_size_cost_per_byte: params.tx_size_cost_per_byte,
+            sig_verify_cost_ed25519: params.sig_verify_cost_ed25519,
+            sig_verify_cost_secp256k1: params.sig_verify_cost_secp256k1,
+        }
+    }
+}
+
+impl GenesisAccount {
+    /// Returns the address of the account, checking that the account is supported.
+    pub fn address(&self) -> Result<AccountId, Error> {
+        if self.type_url != BASE_ACCOUNT_TYPE_URL {
+            return Err(Error::InvalidGenesis {
+                reason: format!("unsupported account type `{}`", self.type_url),
+            });
+        }
+        let address: AccountId = self.address.parse().map_err(|_| Error::InvalidGenesis {
+            reason: format!("invalid account address `{}`", self.address),
+        })?;
+        let prefix = account_prefix();
+        if address.prefix() != prefix {
+            return Err(Error::InvalidGenesis {
+                reason: format!(
+                    "invalid account address `{}`: expected prefix `{prefix}`",
+                    self.address
+                ),
+            });
+        }
+        Ok(address)
+    }
+
+    /// Returns the public key of the account (if set), checking that it hashes to the account's
+    /// address.
+    pub fn pub_key(&self) -> Result<Option<Any>, Error> {
+        let pub_key = match &self.pub_key {
+            None | Some(Value::Null) => return Ok(None),
+            Some(pub_key) => pub_key,
+        };
+        let pub_key =
+            PublicKey::from_json(&pub_key.to_string()).map_err(|e| Error::InvalidGenesis {
+                reason: format!("invalid public key of account `{}`: {e}", self.address),
+            })?;
+        let (pub_key, address) = single_pub_key(&pub_key)?;
+        if address.as_ref() != self.address {
+            return Err(Error::InvalidGenesis {
+                reason: format!(
+                    "public key of account `{}` doesn't match its address",
+                    self.address
+                ),
+            });
+        }
+        Ok(Some(pub_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ADDRESS: &str = "cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efws";
+    const OTHER_ADDRESS: &str = "cosmos1t2e0nyjhwn3revunvf2uperhftvhzu4euuzva9";
+    /// Address of the secp256k1 public key `PUB_KEY`.
+    const PUB_KEY_ADDRESS: &str = "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c";
+    const PUB_KEY: &str = "Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY";
+
+    fn account(address: &str, account_number: u64) -> GenesisAccount {
+        GenesisAccount {
+            type_url: BASE_ACCOUNT_TYPE_URL.to_owned(),
+            address: address.to_owned(),
+            pub_key: None,
+            account_number,
+            sequence: 0,
+        }
+    }
+
+    fn pub_key() -> Value {
+        json!({ "@type": "/cosmos.crypto.secp256k1.PubKey", "key": PUB_KEY })
+    }
+
+    #[test]
+    fn test_decode() {
+        let genesis = GenesisState::decode(&Value::Null).unwrap();
+        assert_eq!(genesis, GenesisState::default());
+        genesis.validate().unwrap();
+
+        let genesis = GenesisState::decode(&json!({
+            "params": {
+                "max_memo_characters": "256",
+                "tx_sig_limit": "7",
+                "tx_size_cost_per_byte": "10",
+                "sig_verify_cost_ed25519": "590",
+                "sig_verify_cost_secp256k1": "1000"
+            },
+            "accounts": [{
+                "@type": "/cosmos.auth.v1beta1.BaseAccount",
+                "address": ADDRESS,
+                "pub_key": null,
+                "account_number": "0",
+                "sequence": "0"
+            }, {
+                "@type": "/cosmos.auth.v1beta1.BaseAccount",
+                "address": PUB_KEY_ADDRESS,
+                "pub_key": pub_key(),
+                "account_number": "1",
+                "sequence": "5"
+            }]
+        }))
+        .unwrap();
+        assert_eq!(genesis.params, Params::default());
+        assert_eq!(genesis.accounts[0], account(ADDRESS, 0));
+        assert_eq!(genesis.accounts[1].sequence, 5);
+        genesis.validate().unwrap();
+
+        assert_eq!(genesis.accounts[0].address().unwrap().to_string(), ADDRESS);
+        assert!(genesis.accounts[0].pub_key().unwrap().is_none());
+        assert!(genesis.accounts[1].pub_key().unwrap().is_some());
+
+        // amounts are strings in proto-JSON
+        assert!(GenesisState::decode(&json!({ "params": { "tx_sig_limit": 7 } })).is_err());
+    }
+
+    #[test]
+    fn test_validate_params() {
+        Params::default().validate().unwrap();
+
+        let params = Params {
+            tx_sig_limit: 0,
+            ..Params::default()
+        };
+        assert!(params
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("invalid tx signature limit"));
+    }
+
+    #[test]
+    fn test_validate_accounts() {
+        let invalid = |accounts: Vec<GenesisAccount>| {
+            GenesisState {
+                params: Params::default(),
+                accounts,
+            }
+            .validate()
+            .unwrap_err()
+            .to_string()
+        };
+
+        assert!(
+            invalid(vec![account(ADDRESS, 0), account(ADDRESS, 1)]).contains("duplicate account")
+        );
+        assert!(
+            invalid(vec![account(ADDRESS, 0), account(OTHER_ADDRESS, 0)])
+                .contains("duplicate account number 0")
+        );
+
+        let mut module_account = account(ADDRESS, 0);
+        module_account.type_url = "/cosmos.auth.v1beta1.ModuleAccount".to_owned();
+        assert!(invalid(vec![module_account]).contains("unsupported account type"));
+
+        assert!(invalid(vec![account("cosmos1invalid", 0)]).contains("invalid account address"));
+        assert!(invalid(vec![account(
+            "osmo12xpmzmfpf7tn57xg93rne2hc2q26lcfqh02ecz",
+            0
+        )])
+        .contains("expected prefix `cosmos`"));
+
+        let mut mismatched = account(ADDRESS, 0);
+        mismatched.pub_key = Some(pub_key());
+        assert!(invalid(vec![mismatched]).contains("doesn't match its address"));
+
+        let mut malformed = account(PUB_KEY_ADDRESS, 0);
+        malformed.pub_key = Some(json!({ "key": PUB_KEY }));
+        assert!(invalid(vec![malformed]).contains("invalid public key"));
+    }
+}
@@ -1,21 +1,26 @@
-use crate::account::AuthAccount;
+use crate::account::{module_address, signer_pub_key, AuthAccount};
+use crate::config::{Config, ModuleAccountPermission};
 use crate::error::Error;
+use crate::genesis::{GenesisState, Params};
 use anyhow::Result;
-use cosmos_helper::Height;
-use cosmos_store::{ProtobufStore, ProvableStore, SharedStore, Store, TypedStore};
-use cosmos_x_bank_type::Denom;
+use cosmos_helper::{Height, Path};
+use cosmos_store::{JsonStore, ProtobufStore, ProvableStore, SharedStore, Store, TypedStore};
+use cosmos_types::crypto::multisig::{LegacyAminoPubKey, SignatureData};
+use cosmos_types::crypto::public_key::PublicKey;
 use cosmos_x_module_api::module::Module;
-use cosmrs::AccountId;
-use ibc_proto::{
-    cosmos::auth::v1beta1::{query_server::QueryServer, BaseAccount},
-    google::protobuf::Any,
-};
+use cosmrs::tx::SignDoc;
+use cosmrs::{AccountId, Tx};
+use ibc_proto::cosmos::auth::v1beta1::{query_server::QueryServer, BaseAccount};
+use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+use ibc_proto::cosmos::tx::v1beta1::ModeInfo as RawModeInfo;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
 use serde_json::Value;
-use std::{collections::HashMap, str::FromStr};
-use tendermint_proto::abci::Event;
+use tendermint::block::Header;
+use tendermint_proto::abci::{CommitInfo, Event};
 use tracing::{debug, trace};
 
-use super::account::AccountsPath;
+use super::account::{AccountNumbersPath, AccountsPath, GlobalAccountNumberPath, ParamsPath};
 use super::{
     context::{Account, AccountKeeper, AccountReader},
     service::AuthService,
@@ -26,6 +31,9 @@ pub struct Auth<S> {
     store: SharedStore<S>,
     account_reader: AuthAccountReader<S>,
     account_keeper: AuthAccountKeeper<S>,
+    module_accounts: Vec<ModuleAccountPermission>,
+    /// Chain ID of the current block, which signers sign over
+    chain_id: String,
 }
 
 impl<S: 'static + ProvableStore> Auth<S> {
@@ -34,16 +42,31 @@ impl<S: 'static + ProvableStore> Auth<S> {
             store: store.clone(),
             account_reader: AuthAccountReader {
                 account_store: TypedStore::new(store.clone()),
+                account_numbers_store: TypedStore::new(store.clone()),
+                params_store: TypedStore::new(store.clone()),
             },
             account_keeper: AuthAccountKeeper {
-                account_store: TypedStore::new(store),
+                account_store: TypedStore::new(store.clone()),
+                account_number_store: TypedStore::new(store.clone()),
+                account_numbers_store: TypedStore::new(store.clone()),
+                params_store: TypedStore::new(store),
             },
+            module_accounts: vec![],
+            chain_id: String::new(),
         }
     }
 
+    /// Configures the module accounts registered at genesis.
+    pub fn with_config(mut self, config: Config) -> Result<Self, Error> {
+        config.validate()?;
+        self.module_accounts = config.module_account_permissions;
+        Ok(self)
+    }
+
     pub fn service(&self) -> QueryServer<AuthService<S>> {
         QueryServer::new(AuthService {
             account_reader: self.account_reader().clone(),
+            module_accounts: self.module_accounts.clone(),
         })
     }
 
@@ -59,36 +82,132 @@ impl<S: 'static + ProvableStore> Auth<S> {
 impl<S: Store> Module for Auth<S> {
     type Store = S;
 
-    fn init(&mut self, app_state: Value) {
+    fn init(&mut self, genesis: Value) {
         debug!("Initializing auth module");
         // safety - we panic on errors to prevent chain creation with invalid genesis config
-        let accounts: HashMap<String, HashMap<Denom, String>> =
-            serde_json::from_value(app_state).unwrap();
-        for (account, _) in accounts {
-            trace!("Adding account: {}", account);
+        let mut genesis = GenesisState::decode(&genesis).unwrap();
+        self.account_keeper.set_params(genesis.params).unwrap();
+        genesis
+            .accounts
+            .sort_by_key(|account| account.account_number);
+        for account in genesis.accounts {
+            trace!("Adding account: {}", account.address);
+
+            // the numbers skipped by the genesis accounts are left unused, so that new accounts
+            // are numbered after the last genesis account
+            while self.account_keeper.new_account_number().unwrap() < account.account_number {}
 
-            let account_id = AccountId::from_str(&account).unwrap();
+            let account = AuthAccount::try_from(BaseAccount {
+                pub_key: account.pub_key().unwrap(),
+                address: account.address,
+                account_number: account.account_number,
+                sequence: account.sequence,
+            })
+            .unwrap();
             self.account_keeper
-                .set_account(AuthAccount::new(account_id.clone()))
+                .set_account(account)
                 .map_err(|_| "Failed to create account")
                 .unwrap();
         }
+
+        for permission in &self.module_accounts {
+            trace!("Adding module account: {}", permission.account);
+
+            let account_number = self.account_keeper.new_account_number().unwrap();
+            self.account_keeper
+                .set_account(AuthAccount::new_module_account(permission, account_number))
+                .map_err(|_| "Failed to create module account")
+                .unwrap();
+        }
     }
 
-    fn deliver(&mut self, _message: Any, signer: &AccountId) -> Result<Vec<Event>> {
+    fn validate_genesis(&self, genesis: &Value) -> Result<()> {
+        let genesis = GenesisState::decode(genesis)?;
+        genesis.validate()?;
+
+        // module accounts are created at genesis, so their addresses can't be taken by others
+        for permission in &self.module_accounts {
+            let address = module_address(&permission.account).to_string();
+            if genesis
+                .accounts
+                .iter()
+                .any(|account| account.address == address)
+            {
+                return Err(Error::InvalidGenesis {
+                    reason: format!(
+                        "account {address} is reserved for the `{}` module account",
+                        permission.account
+                    ),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    fn begin_block(&mut self, header: &Header, _last_commit_info: &CommitInfo) -> Vec<Event> {
+        self.chain_id = header.chain_id.to_string();
+        vec![]
+    }
+
+    fn ante_handle(&mut self, tx: &Tx, signer: &AccountId, simulate: bool) -> Result<Vec<Event>> {
         let mut account = self
             .account_reader
             .get_account(signer.clone())
             .map_err(|_| Error::UnknownSigner)?;
 
+        // only single-signer transactions are supported, so that no signer goes unverified
+        let signer_infos = &tx.auth_info.signer_infos;
+        if signer_infos.len() != 1 {
+            return Err(Error::Unauthorized {
+                reason: format!("expected a single signer, got {}", signer_infos.len()),
+            }
+            .into());
+        }
+        if tx.signatures.len() != signer_infos.len() {
+            return Err(Error::Unauthorized {
+                reason: format!(
+                    "wrong number of signatures; expected {}, got {}",
+                    signer_infos.len(),
+                    tx.signatures.len()
+                ),
+            }
+            .into());
+        }
+        // the sign doc includes the signer info's sequence, so this keeps signed transactions
+        // from being replayed - checked when simulating too, like Go's `SigVerificationDecorator`
+        if signer_infos[0].sequence != account.sequence {
+            return Err(Error::WrongSequence {
+                expected: account.sequence,
+                got: signer_infos[0].sequence,
+            }
+            .into());
+        }
+
+        // signatures aren't verified when simulating, as the client may not have signed yet
+        if !simulate {
+            verify_signature(tx, &account, &self.chain_id)?;
+        }
+
+        // record the signer's public key the first time it signs
+        if account.pub_key().is_none() {
+            if let Some(pub_key) = tx
+                .auth_info
+                .signer_infos
+                .first()
+                .and_then(|signer_info| signer_info.public_key.as_ref())
+            {
+                account.set_pub_key(pub_key)?;
+            }
+        }
+
         account.sequence += 1;
 
         self.account_keeper
             .set_account(account)
             .map_err(|_| Error::FailedToIncrementSignerSequence)?;
 
-        // we're only intercepting the deliverTx here, so return unhandled.
-        Err(Error::NotHandled.into())
+        Ok(vec![])
     }
 
     fn store_mut(&mut self) -> &mut SharedStore<S> {
@@ -100,9 +219,121 @@ impl<S: Store> Module for Auth<S> {
     }
 }
 
-#[derive(Clone)]
+/// Verifies the signature of the transaction's signer over its `SIGN_MODE_DIRECT` sign doc, with
+/// the public key of its signer info or, if it has none, the one recorded on the account.
+/// Multisig signatures are verified including the bit array of the signing keys and nested
+/// multisigs. Other sign modes (e.g. `SIGN_MODE_LEGACY_AMINO_JSON`) aren't supported, so
+/// transactions signed with them are rejected.
+fn verify_signature(tx: &Tx, account: &AuthAccount, chain_id: &str) -> Result<(), Error> {
+    let unauthorized = |reason: String| Error::Unauthorized { reason };
+    let Some(signer_info) = tx.auth_info.signer_infos.first() else {
+        return Err(unauthorized("no signer info".to_owned()));
+    };
+    let public_key = match &signer_info.public_key {
+        Some(public_key) => signer_pub_key(public_key)?.0,
+        None => account
+            .pub_key()
+            .clone()
+            .ok_or_else(|| Error::InvalidPubKey {
+                reason: format!("pubkey on account {} is not set", account.address()),
+            })?,
+    };
+
+    let signature = tx
+        .signatures
+        .first()
+        .ok_or_else(|| unauthorized("no signatures supplied".to_owned()))?;
+    // `cosmrs` and `ibc-proto` have distinct (but identical) protobuf types
+    let mode_info = RawModeInfo::decode(
+        cosmrs::proto::cosmos::tx::v1beta1::ModeInfo::from(signer_info.mode_info.clone())
+            .encode_to_vec()
+            .as_slice(),
+    )
+    .map_err(|e| unauthorized(e.to_string()))?;
+    let signature = SignatureData::from_mode_info(&mode_info, signature)
+        .map_err(|e| unauthorized(e.to_string()))?;
+
+    let chain_id = chain_id
+        .parse()
+        .map_err(|_| unauthorized(format!("invalid chain id `{chain_id}`")))?;
+    let sign_bytes = SignDoc::new(&tx.body, &tx.auth_info, &chain_id, account.account_number())
+        .and_then(SignDoc::into_bytes)
+        .map_err(|e| unauthorized(e.to_string()))?;
+    let get_sign_bytes = |mode: i32| {
+        if mode == SignMode::Direct as i32 {
+            Ok(sign_bytes.clone())
+        } else {
+            Err(cosmos_types::errors::Error::Custom(format!(
+                "unsupported sign mode {mode}"
+            )))
+        }
+    };
+
+    let is_multisig = public_key.type_url == LegacyAminoPubKey::TYPE_URL;
+    match signature {
+        SignatureData::Single { mode, signature } if !is_multisig => {
+            let public_key =
+                PublicKey::try_from(&public_key).map_err(|e| Error::InvalidPubKey {
+                    reason: e.to_string(),
+                })?;
+            let sign_bytes = get_sign_bytes(mode).map_err(|e| unauthorized(e.to_string()))?;
+            public_key
+                .verify_signature(&sign_bytes, &signature)
+                .map_err(|e| unauthorized(e.to_string()))
+        }
+        SignatureData::Multi(signature) if is_multisig => {
+            let multisig =
+                LegacyAminoPubKey::try_from(&public_key).map_err(|e| Error::InvalidPubKey {
+                    reason: e.to_string(),
+                })?;
+            multisig
+                .verify_multisignature(&get_sign_bytes, &signature)
+                .map_err(|e| unauthorized(e.to_string()))
+        }
+        SignatureData::Single { .. } => Err(unauthorized(
+            "expected multisig signature data for a multisig signer".to_owned(),
+        )),
+        SignatureData::Multi(_) => Err(unauthorized(
+            "expected single signature data for a single-key signer".to_owned(),
+        )),
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct AuthAccountReader<S> {
-    account_store: ProtobufStore<SharedStore<S>, AccountsPath, AuthAccount, BaseAccount>,
+    account_store: ProtobufStore<SharedStore<S>, AccountsPath, AuthAccount, Any>,
+    account_numbers_store: JsonStore<SharedStore<S>, AccountNumbersPath, String>,
+    params_store: JsonStore<SharedStore<S>, ParamsPath, Params>,
+}
+
+impl<S: Store> AuthAccountReader<S> {
+    /// Returns all the accounts, in no particular order.
+    pub fn get_accounts(&self) -> Vec<AuthAccount> {
+        let prefix: Path = "accounts".to_owned().try_into().unwrap(); // safety - the path is a constant
+        self.account_store
+            .get_keys(&prefix)
+            .into_iter()
+            .filter_map(|path| {
+                let address = path.to_string().strip_prefix("accounts/")?.parse().ok()?;
+                self.account_store
+                    .get(Height::Pending, &AccountsPath(address))
+            })
+            .collect()
+    }
+
+    /// Returns the auth module's params, i.e. the defaults until they're set at genesis.
+    pub fn get_params(&self) -> Params {
+        self.params_store
+            .get(Height::Pending, &ParamsPath)
+            .unwrap_or_default()
+    }
+
+    /// Returns the address of the account with the specified account number.
+    pub fn get_account_address(&self, account_number: u64) -> Option<AccountId> {
+        self.account_numbers_store
+            .get(Height::Pending, &AccountNumbersPath(account_number))
+            .and_then(|address| address.parse().ok())
+    }
 }
 
 impl<S: Store> AccountReader for AuthAccountReader<S> {
@@ -117,23 +348,259 @@ impl<S: Store> AccountReader for AuthAccountReader<S> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct AuthAccountKeeper<S> {
-    account_store: ProtobufStore<SharedStore<S>, AccountsPath, AuthAccount, BaseAccount>,
+    account_store: ProtobufStore<SharedStore<S>, AccountsPath, AuthAccount, Any>,
+    /// next account number
+    account_number_store: JsonStore<SharedStore<S>, GlobalAccountNumberPath, u64>,
+    /// account number -> address
+    account_numbers_store: JsonStore<SharedStore<S>, AccountNumbersPath, String>,
+    params_store: JsonStore<SharedStore<S>, ParamsPath, Params>,
+}
+
+impl<S: Store> AuthAccountKeeper<S> {
+    pub fn set_params(&mut self, params: Params) -> Result<(), Error> {
+        self.params_store
+            .set(ParamsPath, params)
+            .map(|_| ())
+            .map_err(|e| Error::Unknown(format!("failed to set params: {e:?}")))
+    }
 }
 
 impl<S: Store> AccountKeeper for AuthAccountKeeper<S> {
     type Error = ();
     type Account = AuthAccount;
 
+    fn new_account_with_address(
+        &mut self,
+        address: AccountId,
+    ) -> Result<Self::Account, Self::Error> {
+        Ok(AuthAccount::new(address, self.new_account_number()?))
+    }
+
+    fn new_account_number(&mut self) -> Result<u64, Self::Error> {
+        let account_number = self
+            .account_number_store
+            .get(Height::Pending, &GlobalAccountNumberPath)
+            .unwrap_or_default();
+        self.account_number_store
+            .set(GlobalAccountNumberPath, account_number + 1)
+            .map_err(|_| ())?;
+        Ok(account_number)
+    }
+
+    fn get_account(&self, address: &AccountId) -> Option<Self::Account> {
+        self.account_store
+            .get(Height::Pending, &AccountsPath(address.clone()))
+    }
+
     fn set_account(&mut self, account: Self::Account) -> Result<(), Self::Error> {
+        self.account_numbers_store
+            .set(
+                AccountNumbersPath(account.account_number()),
+                account.address().to_string(),
+            )
+            .map_err(|_| ())?;
         self.account_store
             .set(AccountsPath(account.address().clone()), account)
             .map(|_| ())
             .map_err(|_| ())
     }
 
-    fn remove_account(&mut self, _account: Self::Account) -> Result<(), Self::Error> {
-        unimplemented!()
+    fn remove_account(&mut self, account: Self::Account) -> Result<(), Self::Error> {
+        // the account number isn't reused, so that replay protection holds for pruned accounts
+        self.account_numbers_store
+            .delete(AccountNumbersPath(account.account_number()));
+        self.account_store
+            .delete(AccountsPath(account.address().clone()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_store::InMemoryStore;
+    use cosmos_types::crypto::multisig::v1beta1::CompactBitArray;
+    use ibc_proto::cosmos::crypto::multisig::v1beta1::MultiSignature;
+    use ibc_proto::cosmos::tx::v1beta1::{
+        mode_info, AuthInfo, Fee, ModeInfo, SignerInfo, TxBody, TxRaw,
+    };
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    const CHAIN_ID: &str = "test-chain";
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    fn pub_key(key: &SigningKey) -> Any {
+        PublicKey::from(key.verifying_key()).into()
+    }
+
+    fn direct() -> ModeInfo {
+        ModeInfo {
+            sum: Some(mode_info::Sum::Single(mode_info::Single {
+                mode: SignMode::Direct as i32,
+            })),
+        }
+    }
+
+    /// Returns a transaction of the signer with the specified public key, without signatures.
+    fn unsigned_tx(public_key: Any, mode_info: ModeInfo) -> Tx {
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+                value: vec![],
+            }],
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: Some(public_key),
+                mode_info: Some(mode_info),
+                sequence: 0,
+            }],
+            fee: Some(Fee {
+                gas_limit: 200_000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let raw = TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![],
+        };
+        Tx::from_bytes(&raw.encode_to_vec()).unwrap()
+    }
+
+    fn sign(key: &SigningKey, tx: &Tx) -> Vec<u8> {
+        let sign_bytes = SignDoc::new(&tx.body, &tx.auth_info, &CHAIN_ID.parse().unwrap(), 0)
+            .and_then(SignDoc::into_bytes)
+            .unwrap();
+        let signature: Signature = key.sign(&sign_bytes);
+        signature.to_bytes().to_vec()
+    }
+
+    /// Returns the auth module with an account (number 0) for the signer of the transaction.
+    fn auth(tx: &Tx) -> (Auth<InMemoryStore>, AccountId) {
+        let mut auth = Auth::new(SharedStore::new(InMemoryStore::default()));
+        auth.chain_id = CHAIN_ID.to_owned();
+        let public_key = tx.auth_info.signer_infos[0].public_key.as_ref().unwrap();
+        let (_, address) = signer_pub_key(public_key).unwrap();
+        let account_number = auth.account_keeper.new_account_number().unwrap();
+        auth.account_keeper
+            .set_account(AuthAccount::new(address.clone(), account_number))
+            .unwrap();
+        (auth, address)
+    }
+
+    fn assert_unauthorized(result: Result<Vec<Event>>) {
+        let e = result.unwrap_err();
+        assert!(
+            matches!(e.downcast_ref::<Error>(), Some(Error::Unauthorized { .. })),
+            "{e}"
+        );
+    }
+
+    #[test]
+    fn test_ante_handle_single_key_signature() {
+        let (key, other_key) = (signing_key(1), signing_key(2));
+        let mut tx = unsigned_tx(pub_key(&key), direct());
+        let (mut simulating, _) = auth(&tx);
+        let (mut auth, signer) = auth(&tx);
+
+        // signed by another key than the signer's
+        tx.signatures = vec![sign(&other_key, &tx)];
+        assert_unauthorized(auth.ante_handle(&tx, &signer, false));
+        // signatures aren't verified when simulating
+        simulating.ante_handle(&tx, &signer, true).unwrap();
+
+        // signed over another chain
+        tx.signatures = vec![sign(&key, &tx)];
+        auth.chain_id = "other-chain".to_owned();
+        assert_unauthorized(auth.ante_handle(&tx, &signer, false));
+
+        auth.chain_id = CHAIN_ID.to_owned();
+        auth.ante_handle(&tx, &signer, false).unwrap();
+        let account = auth.account_reader.get_account(signer).unwrap();
+        assert_eq!(account.pub_key(), &Some(pub_key(&key)));
+        assert_eq!(account.sequence, 1);
+    }
+
+    #[test]
+    fn test_ante_handle_replay() {
+        let key = signing_key(1);
+        let mut tx = unsigned_tx(pub_key(&key), direct());
+        let (mut auth, signer) = auth(&tx);
+        tx.signatures = vec![sign(&key, &tx)];
+
+        auth.ante_handle(&tx, &signer, false).unwrap();
+        let e = auth.ante_handle(&tx, &signer, false).unwrap_err();
+        assert!(
+            matches!(
+                e.downcast_ref::<Error>(),
+                Some(Error::WrongSequence {
+                    expected: 1,
+                    got: 0
+                })
+            ),
+            "{e}"
+        );
+        // the sequence is checked when simulating too
+        assert!(auth.ante_handle(&tx, &signer, true).is_err());
+    }
+
+    #[test]
+    fn test_ante_handle_signature_count() {
+        let key = signing_key(1);
+        let mut tx = unsigned_tx(pub_key(&key), direct());
+        let (mut auth, signer) = auth(&tx);
+
+        tx.signatures = vec![];
+        assert_unauthorized(auth.ante_handle(&tx, &signer, false));
+        tx.signatures = vec![sign(&key, &tx), sign(&key, &tx)];
+        assert_unauthorized(auth.ante_handle(&tx, &signer, false));
+
+        // a second signer, even with a valid signature, isn't verified, so isn't accepted
+        let signer_info = tx.auth_info.signer_infos[0].clone();
+        tx.auth_info.signer_infos.push(signer_info);
+        tx.signatures = vec![sign(&key, &tx), sign(&key, &tx)];
+        assert_unauthorized(auth.ante_handle(&tx, &signer, false));
+
+        tx.auth_info.signer_infos.pop();
+        tx.signatures = vec![sign(&key, &tx)];
+        auth.ante_handle(&tx, &signer, false).unwrap();
+    }
+
+    #[test]
+    fn test_ante_handle_multisig_signature() {
+        let keys = [signing_key(1), signing_key(2)];
+        let multisig = LegacyAminoPubKey::new(2, keys.iter().map(pub_key).collect()).unwrap();
+        let mut bit_array = CompactBitArray::new(2).unwrap();
+        bit_array.set_index(0, true);
+        bit_array.set_index(1, true);
+        let mode_info = ModeInfo {
+            sum: Some(mode_info::Sum::Multi(mode_info::Multi {
+                bitarray: Some(bit_array.into()),
+                mode_infos: vec![direct(), direct()],
+            })),
+        };
+        let mut tx = unsigned_tx(multisig.to_any(), mode_info);
+        let (mut auth, signer) = auth(&tx);
+        let multi_signature = |signers: [&SigningKey; 2], tx: &Tx| {
+            MultiSignature {
+                signatures: signers.iter().map(|key| sign(key, tx)).collect(),
+            }
+            .encode_to_vec()
+        };
+
+        // the second signature is made by a key that isn't part of the multisig
+        tx.signatures = vec![multi_signature([&keys[0], &signing_key(3)], &tx)];
+        assert_unauthorized(auth.ante_handle(&tx, &signer, false));
+
+        tx.signatures = vec![multi_signature([&keys[0], &keys[1]], &tx)];
+        auth.ante_handle(&tx, &signer, false).unwrap();
     }
 }
@@ -1,5 +1,8 @@
 pub mod account;
+pub mod config;
 pub mod context;
 pub mod error;
+pub mod genesis;
 pub mod impls;
 pub mod service;
+pub mod vesting;
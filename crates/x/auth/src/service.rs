@@ -1,5 +1,9 @@
-use crate::{account::RELAYER_ACCOUNT, context::AccountReader};
+use crate::context::{Account, AccountReader};
+use cosmos_core::address::codec::Codec;
 use cosmos_store::ProvableStore;
+use cosmos_types::address::Bech32Codec;
+use cosmos_x_module_api::pagination::paginate;
+use cosmrs::AccountId;
 use ibc_proto::cosmos::auth::v1beta1::{
     query_server::Query, AddressBytesToStringRequest, AddressBytesToStringResponse,
     AddressStringToBytesRequest, AddressStringToBytesResponse, Bech32PrefixRequest,
@@ -13,28 +17,61 @@ use tonic::{Request, Response, Status};
 use tracing::debug;
 
 use super::impls::AuthAccountReader;
+use crate::account::{account_prefix, module_address, AuthAccount};
+use crate::config::ModuleAccountPermission;
 
 pub struct AuthService<S> {
     pub account_reader: AuthAccountReader<S>,
+    pub module_accounts: Vec<ModuleAccountPermission>,
+}
+
+impl<S: ProvableStore> AuthService<S> {
+    fn module_account(&self, name: &str) -> Option<AuthAccount> {
+        self.account_reader
+            .get_account(module_address(name))
+            .ok()
+            .filter(|account| account.module_name() == Some(name))
+    }
 }
 
 #[tonic::async_trait]
 impl<S: ProvableStore + 'static> Query for AuthService<S> {
     async fn accounts(
         &self,
-        _request: Request<QueryAccountsRequest>,
+        request: Request<QueryAccountsRequest>,
     ) -> Result<Response<QueryAccountsResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth accounts request");
+
+        // paginated by address bytes, i.e. in the order of the accounts' store keys
+        let accounts = self
+            .account_reader
+            .get_accounts()
+            .into_iter()
+            .map(|account| (account.address().to_bytes(), account))
+            .collect();
+        let (accounts, pagination) = paginate(accounts, request.into_inner().pagination)
+            .map_err(Status::invalid_argument)?;
+
+        Ok(Response::new(QueryAccountsResponse {
+            accounts: accounts.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination),
+        }))
     }
 
     async fn account(
         &self,
-        _request: Request<QueryAccountRequest>,
+        request: Request<QueryAccountRequest>,
     ) -> Result<Response<QueryAccountResponse>, Status> {
         debug!("Got auth account request");
 
-        let account_id = RELAYER_ACCOUNT.parse().unwrap();
-        let account = self.account_reader.get_account(account_id).unwrap();
+        let address = request.into_inner().address;
+        let account_id: AccountId = address
+            .parse()
+            .map_err(|_| Status::invalid_argument(format!("invalid address: {address}")))?;
+        let account = self
+            .account_reader
+            .get_account(account_id)
+            .map_err(|_| Status::not_found(format!("account {address} not found")))?;
 
         Ok(Response::new(QueryAccountResponse {
             account: Some(account.into()),
@@ -45,48 +82,132 @@ impl<S: ProvableStore + 'static> Query for AuthService<S> {
         &self,
         _request: Request<QueryParamsRequest>,
     ) -> Result<Response<QueryParamsResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth params request");
+
+        Ok(Response::new(QueryParamsResponse {
+            params: Some(self.account_reader.get_params().into()),
+        }))
     }
 
     async fn account_address_by_id(
         &self,
-        _request: Request<QueryAccountAddressByIdRequest>,
+        request: Request<QueryAccountAddressByIdRequest>,
     ) -> Result<Response<QueryAccountAddressByIdResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth account address by id request");
+
+        let request = request.into_inner();
+        // the deprecated `id` takes precedence over `account_id` if set
+        #[allow(deprecated)]
+        let account_number = match request.id {
+            0 => request.account_id,
+            id => u64::try_from(id).map_err(|_| Status::invalid_argument("Invalid account id"))?,
+        };
+
+        let address = self
+            .account_reader
+            .get_account_address(account_number)
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "account address not found for account number {account_number}"
+                ))
+            })?;
+
+        Ok(Response::new(QueryAccountAddressByIdResponse {
+            account_address: address.to_string(),
+        }))
     }
 
     async fn module_accounts(
         &self,
         _request: Request<QueryModuleAccountsRequest>,
     ) -> Result<Response<QueryModuleAccountsResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth module accounts request");
+
+        // sorted by name, as Go's `ModuleAccounts` query returns them
+        let mut names: Vec<_> = self
+            .module_accounts
+            .iter()
+            .map(|permission| permission.account.as_str())
+            .collect();
+        names.sort_unstable();
+        let accounts = names
+            .into_iter()
+            .filter_map(|name| self.module_account(name))
+            .map(Into::into)
+            .collect();
+
+        Ok(Response::new(QueryModuleAccountsResponse { accounts }))
     }
 
     async fn module_account_by_name(
         &self,
-        _request: Request<QueryModuleAccountByNameRequest>,
+        request: Request<QueryModuleAccountByNameRequest>,
     ) -> Result<Response<QueryModuleAccountByNameResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth module account by name request");
+
+        let name = request.into_inner().name;
+        if name.is_empty() {
+            return Err(Status::invalid_argument("module name is empty"));
+        }
+        let account = self
+            .module_account(&name)
+            .ok_or_else(|| Status::not_found(format!("account {name} not found")))?;
+
+        Ok(Response::new(QueryModuleAccountByNameResponse {
+            account: Some(account.into()),
+        }))
     }
 
     async fn bech32_prefix(
         &self,
         _request: Request<Bech32PrefixRequest>,
     ) -> Result<Response<Bech32PrefixResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth bech32 prefix request");
+
+        Ok(Response::new(Bech32PrefixResponse {
+            bech32_prefix: account_prefix(),
+        }))
     }
 
     async fn address_bytes_to_string(
         &self,
-        _request: Request<AddressBytesToStringRequest>,
+        request: Request<AddressBytesToStringRequest>,
     ) -> Result<Response<AddressBytesToStringResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth address bytes to string request");
+
+        let address_bytes = request.into_inner().address_bytes;
+        if address_bytes.is_empty() {
+            return Err(Status::invalid_argument(
+                "empty address bytes is not allowed",
+            ));
+        }
+        let address = Bech32Codec::new(account_prefix())
+            .bytes_to_string(&address_bytes)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(AddressBytesToStringResponse {
+            address_string: address,
+        }))
     }
 
     async fn address_string_to_bytes(
         &self,
-        _request: Request<AddressStringToBytesRequest>,
+        request: Request<AddressStringToBytesRequest>,
     ) -> Result<Response<AddressStringToBytesResponse>, Status> {
-        unimplemented!()
+        debug!("Got auth address string to bytes request");
+
+        let address = request.into_inner().address_string;
+        if address.trim().is_empty() {
+            return Err(Status::invalid_argument(
+                "empty address string is not allowed",
+            ));
+        }
+        let address_bytes = Bech32Codec::new(account_prefix())
+            .string_to_bytes(&address)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(AddressStringToBytesResponse {
+            address_bytes,
+        }))
     }
 }
@@ -0,0 +1,517 @@
+//! Vesting accounts, as in the Go SDK's `x/auth/vesting` module.
+//!
+//! The coins of a vesting account are locked until they vest according to the account's
+//! schedule, locked coins can't be sent by the account (see `Account::locked_coins`).
+
+use std::convert::{TryFrom, TryInto};
+
+use cosmos_x_bank_type::{Coin, Denom};
+use ibc_proto::cosmos::{auth::v1beta1::BaseAccount, base::v1beta1::Coin as RawCoin};
+use primitive_types::U256;
+
+pub const CONTINUOUS_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.ContinuousVestingAccount";
+pub const DELAYED_VESTING_ACCOUNT_TYPE_URL: &str = "/cosmos.vesting.v1beta1.DelayedVestingAccount";
+pub const PERIODIC_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.PeriodicVestingAccount";
+pub const PERMANENT_LOCKED_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.PermanentLockedAccount";
+
+/// Type URL of the `MsgCreateVestingAccount` message
+pub const MSG_CREATE_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreateVestingAccount";
+/// Type URL of the `MsgCreatePermanentLockedAccount` message
+pub const MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreatePermanentLockedAccount";
+/// Type URL of the `MsgCreatePeriodicVestingAccount` message
+pub const MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount";
+
+/// Protobuf types of the `cosmos.vesting.v1beta1` package, which isn't part of `ibc-proto`.
+pub mod proto {
+    use ibc_proto::cosmos::{auth::v1beta1::BaseAccount, base::v1beta1::Coin};
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct BaseVestingAccount {
+        #[prost(message, optional, tag = "1")]
+        pub base_account: Option<BaseAccount>,
+        #[prost(message, repeated, tag = "2")]
+        pub original_vesting: Vec<Coin>,
+        #[prost(message, repeated, tag = "3")]
+        pub delegated_free: Vec<Coin>,
+        #[prost(message, repeated, tag = "4")]
+        pub delegated_vesting: Vec<Coin>,
+        /// Vesting end time, as unix timestamp (in seconds)
+        #[prost(int64, tag = "5")]
+        pub end_time: i64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ContinuousVestingAccount {
+        #[prost(message, optional, tag = "1")]
+        pub base_vesting_account: Option<BaseVestingAccount>,
+        /// Vesting start time, as unix timestamp (in seconds)
+        #[prost(int64, tag = "2")]
+        pub start_time: i64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct DelayedVestingAccount {
+        #[prost(message, optional, tag = "1")]
+        pub base_vesting_account: Option<BaseVestingAccount>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Period {
+        /// Period duration in seconds
+        #[prost(int64, tag = "1")]
+        pub length: i64,
+        #[prost(message, repeated, tag = "2")]
+        pub amount: Vec<Coin>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PeriodicVestingAccount {
+        #[prost(message, optional, tag = "1")]
+        pub base_vesting_account: Option<BaseVestingAccount>,
+        #[prost(int64, tag = "2")]
+        pub start_time: i64,
+        #[prost(message, repeated, tag = "3")]
+        pub vesting_periods: Vec<Period>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PermanentLockedAccount {
+        #[prost(message, optional, tag = "1")]
+        pub base_vesting_account: Option<BaseVestingAccount>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MsgCreateVestingAccount {
+        #[prost(string, tag = "1")]
+        pub from_address: String,
+        #[prost(string, tag = "2")]
+        pub to_address: String,
+        #[prost(message, repeated, tag = "3")]
+        pub amount: Vec<Coin>,
+        /// Vesting end time, as unix timestamp (in seconds)
+        #[prost(int64, tag = "4")]
+        pub end_time: i64,
+        #[prost(bool, tag = "5")]
+        pub delayed: bool,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MsgCreateVestingAccountResponse {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MsgCreatePermanentLockedAccount {
+        #[prost(string, tag = "1")]
+        pub from_address: String,
+        #[prost(string, tag = "2")]
+        pub to_address: String,
+        #[prost(message, repeated, tag = "3")]
+        pub amount: Vec<Coin>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MsgCreatePermanentLockedAccountResponse {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MsgCreatePeriodicVestingAccount {
+        #[prost(string, tag = "1")]
+        pub from_address: String,
+        #[prost(string, tag = "2")]
+        pub to_address: String,
+        /// Vesting start time, as unix timestamp (in seconds)
+        #[prost(int64, tag = "3")]
+        pub start_time: i64,
+        #[prost(message, repeated, tag = "4")]
+        pub vesting_periods: Vec<Period>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MsgCreatePeriodicVestingAccountResponse {}
+}
+
+/// Vesting schedule of an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VestingSchedule {
+    /// Coins vest linearly between the start and end times
+    Continuous { start_time: i64 },
+    /// All coins vest at the end time
+    Delayed,
+    /// Coins vest at the end of each period, the first one starting at the start time
+    Periodic {
+        start_time: i64,
+        periods: Vec<Period>,
+    },
+    /// Coins never vest
+    PermanentLocked,
+}
+
+/// A vesting period, i.e. coins that vest `length` seconds after the end of the previous period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Period {
+    pub length: i64,
+    pub amount: Vec<Coin>,
+}
+
+/// Vesting state of an account, as the Go SDK's `BaseVestingAccount` along with the schedule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vesting {
+    /// Coins locked at the creation of the account
+    pub original_vesting: Vec<Coin>,
+    /// Vested coins that are delegated
+    pub delegated_free: Vec<Coin>,
+    /// Vesting coins that are delegated
+    pub delegated_vesting: Vec<Coin>,
+    /// Vesting end time, as unix timestamp (in seconds)
+    pub end_time: i64,
+    pub schedule: VestingSchedule,
+}
+
+impl Vesting {
+    /// Returns the vesting state of an account whose coins vest by the end time, according to the
+    /// schedule.
+    pub fn new(original_vesting: Vec<Coin>, end_time: i64, schedule: VestingSchedule) -> Self {
+        Self {
+            original_vesting,
+            delegated_free: vec![],
+            delegated_vesting: vec![],
+            end_time,
+            schedule,
+        }
+    }
+
+    /// Returns the coins that have vested at the specified block time.
+    pub fn vested_coins(&self, block_time: i64) -> Vec<Coin> {
+        match &self.schedule {
+            VestingSchedule::Continuous { start_time } => {
+                if block_time <= *start_time {
+                    vec![]
+                } else if block_time >= self.end_time {
+                    self.original_vesting.clone()
+                } else {
+                    // safety - the start time is before the block time which is before the end time
+                    let elapsed = U256::from((block_time - start_time) as u64);
+                    let duration = U256::from((self.end_time - start_time) as u64);
+                    self.original_vesting
+                        .iter()
+                        .map(|coin| Coin {
+                            denom: coin.denom.clone(),
+                            // i.e. `amount * elapsed / duration`, without overflowing
+                            amount: coin.amount / duration * elapsed
+                                + coin.amount % duration * elapsed / duration,
+                        })
+                        .filter(|coin| !coin.amount.is_zero())
+                        .collect()
+                }
+            }
+            VestingSchedule::Delayed => {
+                if block_time >= self.end_time {
+                    self.original_vesting.clone()
+                } else {
+                    vec![]
+                }
+            }
+            VestingSchedule::Periodic {
+                start_time,
+                periods,
+            } => {
+                if block_time <= *start_time {
+                    return vec![];
+                } else if block_time >= self.end_time {
+                    return self.original_vesting.clone();
+                }
+                let mut vested = vec![];
+                let mut period_end = *start_time;
+                for period in periods {
+                    period_end += period.length;
+                    if block_time < period_end {
+                        break;
+                    }
+                    vested = add_coins(vested, &period.amount);
+                }
+                vested
+            }
+            VestingSchedule::PermanentLocked => vec![],
+        }
+    }
+
+    /// Returns the coins that are still vesting at the specified block time.
+    pub fn vesting_coins(&self, block_time: i64) -> Vec<Coin> {
+        sub_coins(&self.original_vesting, &self.vested_coins(block_time))
+    }
+
+    /// Returns the coins that can't be sent at the specified block time, i.e. the vesting coins
+    /// that aren't delegated (as delegated coins aren't part of the balance anymore).
+    pub fn locked_coins(&self, block_time: i64) -> Vec<Coin> {
+        sub_coins(&self.vesting_coins(block_time), &self.delegated_vesting)
+    }
+
+    /// Returns the type URL of the account, according to its schedule.
+    pub fn type_url(&self) -> &'static str {
+        match self.schedule {
+            VestingSchedule::Continuous { .. } => CONTINUOUS_VESTING_ACCOUNT_TYPE_URL,
+            VestingSchedule::Delayed => DELAYED_VESTING_ACCOUNT_TYPE_URL,
+            VestingSchedule::Periodic { .. } => PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+            VestingSchedule::PermanentLocked => PERMANENT_LOCKED_ACCOUNT_TYPE_URL,
+        }
+    }
+}
+
+/// Adds the coins by denomination.
+fn add_coins(mut coins: Vec<Coin>, other: &[Coin]) -> Vec<Coin> {
+    for coin in other {
+        match coins.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount = c.amount.saturating_add(coin.amount),
+            None => coins.push(coin.clone()),
+        }
+    }
+    coins
+}
+
+/// Subtracts the coins by denomination, saturating at zero (zero coins are left out).
+fn sub_coins(coins: &[Coin], other: &[Coin]) -> Vec<Coin> {
+    coins
+        .iter()
+        .map(|coin| {
+            let sub = other
+                .iter()
+                .find(|c| c.denom == coin.denom)
+                .map(|c| c.amount)
+                .unwrap_or_default();
+            Coin {
+                denom: coin.denom.clone(),
+                amount: coin.amount.saturating_sub(sub),
+            }
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect()
+}
+
+fn coins_from_raw(coins: Vec<RawCoin>) -> Result<Vec<Coin>, String> {
+    coins
+        .into_iter()
+        .map(|coin| coin.try_into().map_err(|e| format!("{e}")))
+        .collect()
+}
+
+fn coins_to_raw(coins: Vec<Coin>) -> Vec<RawCoin> {
+    coins.into_iter().map(RawCoin::from).collect()
+}
+
+impl TryFrom<proto::Period> for Period {
+    type Error = String;
+
+    fn try_from(period: proto::Period) -> Result<Self, Self::Error> {
+        Ok(Self {
+            length: period.length,
+            amount: coins_from_raw(period.amount)?,
+        })
+    }
+}
+
+impl From<Period> for proto::Period {
+    fn from(period: Period) -> Self {
+        Self {
+            length: period.length,
+            amount: coins_to_raw(period.amount),
+        }
+    }
+}
+
+impl Vesting {
+    /// Decodes the vesting state of a `BaseVestingAccount`, returning its base account as well.
+    pub(crate) fn from_raw(
+        account: Option<proto::BaseVestingAccount>,
+        schedule: VestingSchedule,
+    ) -> Result<(BaseAccount, Self), String> {
+        let account = account.ok_or_else(|| "Missing base vesting account".to_string())?;
+        let base_account = account
+            .base_account
+            .ok_or_else(|| "Missing base account".to_string())?;
+        Ok((
+            base_account,
+            Self {
+                original_vesting: coins_from_raw(account.original_vesting)?,
+                delegated_free: coins_from_raw(account.delegated_free)?,
+                delegated_vesting: coins_from_raw(account.delegated_vesting)?,
+                end_time: account.end_time,
+                schedule,
+            },
+        ))
+    }
+
+    /// Encodes the vesting state as a `BaseVestingAccount` with the specified base account.
+    pub(crate) fn to_raw(&self, base_account: BaseAccount) -> proto::BaseVestingAccount {
+        proto::BaseVestingAccount {
+            base_account: Some(base_account),
+            original_vesting: coins_to_raw(self.original_vesting.clone()),
+            delegated_free: coins_to_raw(self.delegated_free.clone()),
+            delegated_vesting: coins_to_raw(self.delegated_vesting.clone()),
+            end_time: self.end_time,
+        }
+    }
+}
+
+/// Returns the amount of the specified denomination in the coins.
+pub fn amount_of(coins: &[Coin], denom: &Denom) -> U256 {
+    coins
+        .iter()
+        .find(|coin| &coin.denom == denom)
+        .map(|coin| coin.amount)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // values of Go's `x/auth/vesting/types/vesting_account_test.go`
+    const NOW: i64 = 1_600_000_000;
+    const HOUR: i64 = 60 * 60;
+    const END_TIME: i64 = NOW + 24 * HOUR;
+
+    fn coins(fee: u64, stake: u64) -> Vec<Coin> {
+        [("fee", fee), ("stake", stake)]
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .map(|(denom, amount)| (Denom(denom.to_owned()), U256::from(amount)).into())
+            .collect()
+    }
+
+    fn orig_coins() -> Vec<Coin> {
+        coins(1000, 100)
+    }
+
+    fn periods() -> Vec<Period> {
+        vec![
+            Period {
+                length: 12 * HOUR,
+                amount: coins(500, 50),
+            },
+            Period {
+                length: 6 * HOUR,
+                amount: coins(250, 25),
+            },
+            Period {
+                length: 6 * HOUR,
+                amount: coins(250, 25),
+            },
+        ]
+    }
+
+    /// Checks the vested, vesting and locked coins at each of the times, given as
+    /// `(elapsed time, vested fee, vested stake)`.
+    fn check_schedule(vesting: &Vesting, cases: &[(i64, u64, u64)]) {
+        for &(elapsed, fee, stake) in cases {
+            let block_time = NOW + elapsed;
+            let vested = coins(fee, stake);
+            let vesting_coins = sub_coins(&orig_coins(), &vested);
+            assert_eq!(vesting.vested_coins(block_time), vested, "at {elapsed}s");
+            assert_eq!(
+                vesting.vesting_coins(block_time),
+                vesting_coins,
+                "at {elapsed}s"
+            );
+            assert_eq!(
+                vesting.locked_coins(block_time),
+                vesting_coins,
+                "at {elapsed}s"
+            );
+        }
+    }
+
+    #[test]
+    fn test_continuous_vesting() {
+        let vesting = Vesting::new(
+            orig_coins(),
+            END_TIME,
+            VestingSchedule::Continuous { start_time: NOW },
+        );
+        check_schedule(
+            &vesting,
+            &[
+                (-HOUR, 0, 0),
+                (0, 0, 0),
+                (6 * HOUR, 250, 25),
+                (12 * HOUR, 500, 50),
+                (24 * HOUR, 1000, 100),
+                (48 * HOUR, 1000, 100),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_continuous_vesting_rounds_down() {
+        let vesting = Vesting::new(
+            orig_coins(),
+            NOW + 3,
+            VestingSchedule::Continuous { start_time: NOW },
+        );
+        // 1000 * 1/3 and 100 * 1/3
+        check_schedule(&vesting, &[(1, 333, 33), (2, 666, 66)]);
+    }
+
+    #[test]
+    fn test_delayed_vesting() {
+        let vesting = Vesting::new(orig_coins(), END_TIME, VestingSchedule::Delayed);
+        check_schedule(
+            &vesting,
+            &[
+                (0, 0, 0),
+                (12 * HOUR, 0, 0),
+                (24 * HOUR - 1, 0, 0),
+                (24 * HOUR, 1000, 100),
+                (48 * HOUR, 1000, 100),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_periodic_vesting() {
+        let vesting = Vesting::new(
+            orig_coins(),
+            END_TIME,
+            VestingSchedule::Periodic {
+                start_time: NOW,
+                periods: periods(),
+            },
+        );
+        check_schedule(
+            &vesting,
+            &[
+                (0, 0, 0),
+                (6 * HOUR, 0, 0),
+                (12 * HOUR, 500, 50),
+                (15 * HOUR, 500, 50),
+                (18 * HOUR, 750, 75),
+                (24 * HOUR, 1000, 100),
+                (48 * HOUR, 1000, 100),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_permanent_locked() {
+        let vesting = Vesting::new(orig_coins(), 0, VestingSchedule::PermanentLocked);
+        check_schedule(&vesting, &[(0, 0, 0), (1000 * 365 * 24 * HOUR, 0, 0)]);
+    }
+
+    #[test]
+    fn test_locked_coins_exclude_delegated_vesting() {
+        let mut vesting = Vesting::new(
+            orig_coins(),
+            END_TIME,
+            VestingSchedule::Continuous { start_time: NOW },
+        );
+        vesting.delegated_vesting = coins(0, 100);
+
+        assert_eq!(vesting.locked_coins(NOW), coins(1000, 0));
+        // the delegated coins exceed the vesting ones
+        assert_eq!(vesting.locked_coins(NOW + 12 * HOUR), coins(500, 0));
+        assert!(vesting.locked_coins(END_TIME).is_empty());
+    }
+}
@@ -10,7 +10,7 @@ use cosmos_helper::{error::Error, Path};
 #[serde(transparent)]
 pub struct Denom(pub String);
 
-#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
 pub struct Coin {
     pub denom: Denom,
     pub amount: U256,
@@ -42,4 +42,18 @@ pub trait BankKeeper {
         account: Self::Address,
         amount: impl IntoIterator<Item = Self::Coin>,
     ) -> Result<(), Self::Error>;
+
+    /// Checks that the account holds enough spendable coins to send the amount, without sending
+    /// anything
+    fn validate_send_coins(
+        &self,
+        from: &Self::Address,
+        amount: &[Self::Coin],
+    ) -> Result<(), Self::Error>;
+
+    /// Checks that the account is allowed to mint coins, without minting anything
+    fn validate_mint_coins(&self, account: &Self::Address) -> Result<(), Self::Error>;
+
+    /// Checks that the account is allowed to burn coins, without burning anything
+    fn validate_burn_coins(&self, account: &Self::Address) -> Result<(), Self::Error>;
 }
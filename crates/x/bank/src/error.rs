@@ -1,5 +1,10 @@
 use cosmrs::AccountId;
 
+use cosmos_x_module_api::error::{
+    AbciError, RegisteredError, ERR_INSUFFICIENT_FUNDS, ERR_INVALID_ADDRESS, ERR_INVALID_COINS,
+    ERR_INVALID_REQUEST, ERR_IO, ERR_TX_DECODE, ERR_UNAUTHORIZED, ERR_UNKNOWN_ADDRESS,
+    ERR_UNKNOWN_REQUEST,
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -10,6 +15,9 @@ pub enum Error {
     #[error("failed to validate message: `{reason}`")]
     MsgValidationFailure { reason: String },
 
+    #[error("invalid address `{address}`: {reason}")]
+    InvalidAddress { address: String, reason: String },
+
     #[error("account `{account}` doesn't exist")]
     NonExistentAccount { account: AccountId },
 
@@ -19,9 +27,46 @@ pub enum Error {
     #[error("receiver account funds overflow")]
     DestFundOverflow,
 
+    #[error("account `{account}` already exists")]
+    AccountExists { account: AccountId },
+
+    #[error("account `{account}` doesn't have the `{permission}` permission")]
+    MissingPermission {
+        account: AccountId,
+        permission: String,
+    },
+
+    #[error("`{signer}` isn't the sender `{sender}` of the message")]
+    InvalidSigner {
+        signer: AccountId,
+        sender: AccountId,
+    },
+
+    #[error("invalid genesis state: {reason}")]
+    InvalidGenesis { reason: String },
+
     #[error("Store error: `{reason}`")]
     Store { reason: String },
 
     #[error("not handled")]
     NotHandled,
 }
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::MsgDecodeFailure => ERR_TX_DECODE,
+            Error::MsgValidationFailure { .. } => ERR_INVALID_REQUEST,
+            Error::InvalidAddress { .. } => ERR_INVALID_ADDRESS,
+            Error::NonExistentAccount { .. } => ERR_UNKNOWN_ADDRESS,
+            Error::InsufficientSourceFunds => ERR_INSUFFICIENT_FUNDS,
+            Error::DestFundOverflow => ERR_INVALID_COINS,
+            Error::AccountExists { .. } => ERR_INVALID_REQUEST,
+            Error::MissingPermission { .. } => ERR_UNAUTHORIZED,
+            Error::InvalidSigner { .. } => ERR_UNAUTHORIZED,
+            Error::InvalidGenesis { .. } => ERR_INVALID_REQUEST,
+            Error::Store { .. } => ERR_IO,
+            Error::NotHandled => ERR_UNKNOWN_REQUEST,
+        }
+    }
+}
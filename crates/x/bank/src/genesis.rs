@@ -0,0 +1,410 @@
+use std::collections::{HashMap, HashSet};
+
+use cosmos_x_auth::account::account_prefix;
+use cosmos_x_bank_type::{Coin, Denom};
+use cosmrs::AccountId;
+use primitive_types::U256;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Genesis state of the bank module, i.e. `app_state.bank`, as the Go SDK's
+/// `cosmos.bank.v1beta1.GenesisState`.
+///
+/// Only the balances are stored by the module for now, the other fields are validated only.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisState {
+    #[serde(default)]
+    pub params: Params,
+    #[serde(default)]
+    pub balances: Vec<Balance>,
+    /// Total supply, computed from the balances if empty
+    #[serde(default)]
+    pub supply: Vec<GenesisCoin>,
+    #[serde(default)]
+    pub denom_metadata: Vec<Metadata>,
+    #[serde(default)]
+    pub send_enabled: Vec<SendEnabled>,
+}
+
+/// Params of the bank module, as the Go SDK's `cosmos.bank.v1beta1.Params`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Params {
+    #[serde(default)]
+    pub send_enabled: Vec<SendEnabled>,
+    #[serde(default = "default_send_enabled")]
+    pub default_send_enabled: bool,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            send_enabled: vec![],
+            default_send_enabled: default_send_enabled(),
+        }
+    }
+}
+
+fn default_send_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SendEnabled {
+    pub denom: String,
+    pub enabled: bool,
+}
+
+/// Balances of an account at genesis.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Balance {
+    pub address: String,
+    #[serde(default)]
+    pub coins: Vec<GenesisCoin>,
+}
+
+/// A proto-JSON encoded coin, i.e. with a decimal amount.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+/// Metadata of a denomination, as the Go SDK's `cosmos.bank.v1beta1.Metadata`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Metadata {
+    pub description: String,
+    pub denom_units: Vec<DenomUnit>,
+    pub base: String,
+    pub display: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub uri_hash: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DenomUnit {
+    pub denom: String,
+    pub exponent: u32,
+    pub aliases: Vec<String>,
+}
+
+impl GenesisState {
+    /// Decodes the bank module's section of the genesis `app_state`.
+    pub fn decode(genesis: &Value) -> Result<Self, Error> {
+        cosmos_x_module_api::genesis::decode_genesis(genesis)
+            .map_err(|reason| Error::InvalidGenesis { reason })
+    }
+
+    /// Checks that the balances are of unique and valid addresses, with valid coins, and add up to
+    /// the supply (if specified).
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_send_enabled(&self.params.send_enabled)?;
+        validate_send_enabled(&self.send_enabled)?;
+
+        let mut addresses = HashSet::new();
+        let mut total: HashMap<String, U256> = HashMap::new();
+        for balance in &self.balances {
+            balance.address()?;
+            if !addresses.insert(balance.address.as_str()) {
+                return Err(invalid_genesis(format!(
+                    "duplicate balance for address {}",
+                    balance.address
+                )));
+            }
+            for coin in balance.coins()? {
+                let sum = total.entry(coin.denom.0.clone()).or_default();
+                *sum = sum.checked_add(coin.amount).ok_or_else(|| {
+                    invalid_genesis(format!("total supply of {} overflows", coin.denom.0))
+                })?;
+            }
+        }
+
+        if !self.supply.is_empty() {
+            let supply = parse_coins(&self.supply)?;
+            let matches = supply.len() == total.len()
+                && supply
+                    .iter()
+                    .all(|coin| total.get(coin.denom.0.as_str()) == Some(&coin.amount));
+            if !matches {
+                return Err(invalid_genesis(format!(
+                    "genesis supply is incorrect, expected {}, got {}",
+                    display_coins(total.iter().map(|(denom, amount)| (denom.as_str(), amount))),
+                    display_coins(
+                        supply
+                            .iter()
+                            .map(|coin| (coin.denom.0.as_str(), &coin.amount))
+                    ),
+                )));
+            }
+        }
+
+        let mut bases = HashSet::new();
+        for metadata in &self.denom_metadata {
+            validate_denom(&metadata.base)?;
+            if !bases.insert(metadata.base.as_str()) {
+                return Err(invalid_genesis(format!(
+                    "duplicate client metadata for denom {}",
+                    metadata.base
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Balance {
+    pub fn address(&self) -> Result<AccountId, Error> {
+        let address: AccountId = self
+            .address
+            .parse()
+            .map_err(|_| invalid_genesis(format!("invalid address `{}`", self.address)))?;
+        let prefix = account_prefix();
+        if address.prefix() != prefix {
+            return Err(invalid_genesis(format!(
+                "invalid address `{}`: expected prefix `{prefix}`",
+                self.address
+            )));
+        }
+        Ok(address)
+    }
+
+    /// Returns the coins of the balance, which must have valid and unique denominations and
+    /// positive amounts.
+    pub fn coins(&self) -> Result<Vec<Coin>, Error> {
+        parse_coins(&self.coins)
+    }
+}
+
+fn parse_coins(coins: &[GenesisCoin]) -> Result<Vec<Coin>, Error> {
+    let mut denoms = HashSet::new();
+    coins
+        .iter()
+        .map(|coin| {
+            validate_denom(&coin.denom)?;
+            if !denoms.insert(coin.denom.as_str()) {
+                return Err(invalid_genesis(format!(
+                    "duplicate denomination {}",
+                    coin.denom
+                )));
+            }
+            let amount = U256::from_dec_str(&coin.amount)
+                .ok()
+                .filter(|amount| !amount.is_zero())
+                .ok_or_else(|| {
+                    invalid_genesis(format!("invalid amount {}{}", coin.amount, coin.denom))
+                })?;
+            Ok(Coin {
+                denom: Denom(coin.denom.clone()),
+                amount,
+            })
+        })
+        .collect()
+}
+
+fn validate_send_enabled(send_enabled: &[SendEnabled]) -> Result<(), Error> {
+    let mut denoms = HashSet::new();
+    for entry in send_enabled {
+        validate_denom(&entry.denom)?;
+        if !denoms.insert(entry.denom.as_str()) {
+            return Err(invalid_genesis(format!(
+                "duplicate send enabled found: '{}'",
+                entry.denom
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_denom(denom: &str) -> Result<(), Error> {
+    cosmos_x_module_api::genesis::validate_denom(denom).map_err(invalid_genesis)
+}
+
+fn display_coins<'a>(coins: impl Iterator<Item = (&'a str, &'a U256)>) -> String {
+    let mut coins: Vec<_> = coins
+        .map(|(denom, amount)| format!("{amount}{denom}"))
+        .collect();
+    coins.sort();
+    coins.join(",")
+}
+
+fn invalid_genesis(reason: String) -> Error {
+    Error::InvalidGenesis { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ADDRESS: &str = "cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efws";
+    const OTHER_ADDRESS: &str = "cosmos1t2e0nyjhwn3revunvf2uperhftvhzu4euuzva9";
+
+    fn coin(denom: &str, amount: &str) -> GenesisCoin {
+        GenesisCoin {
+            denom: denom.to_owned(),
+            amount: amount.to_owned(),
+        }
+    }
+
+    fn balance(address: &str, coins: &[(&str, &str)]) -> Balance {
+        Balance {
+            address: address.to_owned(),
+            coins: coins
+                .iter()
+                .map(|(denom, amount)| coin(denom, amount))
+                .collect(),
+        }
+    }
+
+    fn genesis() -> GenesisState {
+        GenesisState {
+            balances: vec![
+                balance(ADDRESS, &[("basecoin", "100"), ("othercoin", "5")]),
+                balance(OTHER_ADDRESS, &[("basecoin", "20")]),
+            ],
+            ..GenesisState::default()
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let default = GenesisState::decode(&Value::Null).unwrap();
+        assert_eq!(default, GenesisState::default());
+        assert!(default.params.default_send_enabled);
+        default.validate().unwrap();
+
+        let decoded = GenesisState::decode(&json!({
+            "params": { "send_enabled": [], "default_send_enabled": true },
+            "balances": [{
+                "address": ADDRESS,
+                "coins": [
+                    { "denom": "basecoin", "amount": "100" },
+                    { "denom": "othercoin", "amount": "5" }
+                ]
+            }, {
+                "address": OTHER_ADDRESS,
+                "coins": [{ "denom": "basecoin", "amount": "20" }]
+            }],
+            "supply": [],
+            "denom_metadata": [],
+            "send_enabled": []
+        }))
+        .unwrap();
+        assert_eq!(decoded, genesis());
+        decoded.validate().unwrap();
+
+        let coins = decoded.balances[0].coins().unwrap();
+        assert_eq!(coins[0].denom, Denom("basecoin".to_owned()));
+        assert_eq!(coins[0].amount, U256::from(100));
+        assert_eq!(decoded.balances[0].address().unwrap().to_string(), ADDRESS);
+    }
+
+    #[test]
+    fn test_validate_supply() {
+        let mut state = genesis();
+        state.supply = vec![coin("basecoin", "120"), coin("othercoin", "5")];
+        state.validate().unwrap();
+
+        state.supply = vec![coin("basecoin", "100"), coin("othercoin", "5")];
+        assert_eq!(
+            state.validate().unwrap_err().to_string(),
+            invalid_genesis(
+                "genesis supply is incorrect, expected 120basecoin,5othercoin, got \
+                 100basecoin,5othercoin"
+                    .to_owned()
+            )
+            .to_string()
+        );
+
+        // the supply must list every denomination of the balances
+        state.supply = vec![coin("basecoin", "120")];
+        assert!(state.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_balances() {
+        let invalid = |balances: Vec<Balance>| {
+            GenesisState {
+                balances,
+                ..GenesisState::default()
+            }
+            .validate()
+            .unwrap_err()
+            .to_string()
+        };
+
+        assert!(invalid(vec![
+            balance(ADDRESS, &[("basecoin", "1")]),
+            balance(ADDRESS, &[("othercoin", "1")]),
+        ])
+        .contains("duplicate balance"));
+        assert!(invalid(vec![balance("cosmos1invalid", &[])]).contains("invalid address"));
+        assert!(invalid(vec![balance(
+            "osmo12xpmzmfpf7tn57xg93rne2hc2q26lcfqh02ecz",
+            &[]
+        )])
+        .contains("expected prefix `cosmos`"));
+        assert!(invalid(vec![balance(ADDRESS, &[("basecoin", "0")])]).contains("invalid amount"));
+        assert!(invalid(vec![balance(ADDRESS, &[("basecoin", "-1")])]).contains("invalid amount"));
+        assert!(invalid(vec![balance(ADDRESS, &[("1coin", "1")])]).contains("invalid denom"));
+        assert!(invalid(vec![balance(
+            ADDRESS,
+            &[("basecoin", "1"), ("basecoin", "2")]
+        )])
+        .contains("duplicate denomination"));
+
+        let max = U256::MAX.to_string();
+        assert!(invalid(vec![
+            balance(ADDRESS, &[("basecoin", &max)]),
+            balance(OTHER_ADDRESS, &[("basecoin", "1")]),
+        ])
+        .contains("overflows"));
+    }
+
+    #[test]
+    fn test_validate_send_enabled_and_metadata() {
+        let send_enabled = |denom: &str| SendEnabled {
+            denom: denom.to_owned(),
+            enabled: false,
+        };
+
+        let mut state = genesis();
+        state.send_enabled = vec![send_enabled("basecoin"), send_enabled("othercoin")];
+        state.params.send_enabled = vec![send_enabled("basecoin")];
+        state.denom_metadata = vec![Metadata {
+            base: "basecoin".to_owned(),
+            ..Metadata::default()
+        }];
+        state.validate().unwrap();
+
+        let mut duplicate = state.clone();
+        duplicate.send_enabled.push(send_enabled("basecoin"));
+        assert!(duplicate
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("duplicate send enabled"));
+
+        let mut duplicate = state.clone();
+        duplicate
+            .denom_metadata
+            .push(duplicate.denom_metadata[0].clone());
+        assert!(duplicate
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("duplicate client metadata"));
+
+        state.params.send_enabled = vec![send_enabled("x")];
+        assert!(state
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("invalid denom"));
+    }
+}
@@ -1,19 +1,47 @@
 use super::context::{BankKeeper, BankReader};
 use super::service::BankService;
 use crate::error::Error;
+use crate::genesis::GenesisState;
 use anyhow::Result;
 use cosmos_x_bank_type::{Balances, BalancesPath, Coin, Denom};
 use cosmrs::{bank::MsgSend, proto, AccountId};
-use ibc_proto::{cosmos::bank::v1beta1::query_server::QueryServer, google::protobuf::Any};
+use ibc_proto::{
+    cosmos::{bank::v1beta1::query_server::QueryServer, base::v1beta1::Coin as RawCoin},
+    google::protobuf::Any,
+};
 use primitive_types::U256;
 use prost::Message;
-use std::{collections::HashMap, convert::TryInto, fmt::Debug, str::FromStr};
-use tendermint_proto::abci::Event;
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+    fmt::Debug,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+use tendermint_proto::abci::{CommitInfo, Event, EventAttribute};
 use tracing::{debug, trace};
 
-use cosmos_x_auth::account::{AuthAccount, ACCOUNT_PREFIX};
-use cosmos_x_auth::context::{AccountKeeper, AccountReader};
+use cosmos_x_auth::account::{account_prefix, AuthAccount, BURNER, MINTER};
+use cosmos_x_auth::context::{Account, AccountKeeper, AccountReader};
+use cosmos_x_auth::impls::AuthAccountKeeper;
+use cosmos_x_auth::vesting::{
+    amount_of,
+    proto::{
+        MsgCreatePeriodicVestingAccount, MsgCreatePeriodicVestingAccountResponse,
+        MsgCreatePermanentLockedAccount, MsgCreatePermanentLockedAccountResponse,
+        MsgCreateVestingAccount, MsgCreateVestingAccountResponse,
+    },
+    Period, Vesting, VestingSchedule, MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+    MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL, MSG_CREATE_VESTING_ACCOUNT_TYPE_URL,
+};
 use cosmos_x_module_api::module::Module;
+use cosmos_x_module_api::types::DeliverResult;
+
+use cosmos_core::address::codec::Codec as _;
+use cosmos_types::address::Bech32Codec;
 
 use cosmos_helper::{Height, Path, QueryResult};
 use cosmos_store::{
@@ -22,6 +50,9 @@ use cosmos_store::{
     {JsonStore, TypedStore}, {ProvableStore, Store},
 };
 
+/// Type URL of the `MsgSend` message handled by the bank module
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+
 #[derive(Clone)]
 pub struct BankBalanceReader<S> {
     balance_store: JsonStore<SharedStore<S>, BalancesPath, Balances>,
@@ -42,11 +73,131 @@ impl<S: Store> BankReader for BankBalanceReader<S> {
 }
 
 #[derive(Clone, Debug)]
-pub struct BankBalanceKeeper<S> {
+pub struct BankBalanceKeeper<S, AK = AuthAccountKeeper<S>> {
     balance_store: JsonStore<SharedStore<S>, BalancesPath, Balances>,
+    account_keeper: AK,
+    /// time of the current block (as unix timestamp), against which vesting schedules are
+    /// evaluated - shared with the keepers handed over to other modules
+    block_time: Arc<AtomicI64>,
 }
 
-impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
+impl<S: Store, AK> BankBalanceKeeper<S, AK>
+where
+    AK: AccountKeeper,
+    AK::Account: Account<Address = AccountId>,
+{
+    /// Adds coins to the balances of the account, without any permission check (e.g. to set the
+    /// genesis balances).
+    pub fn add_coins(
+        &mut self,
+        account: AccountId,
+        amount: impl IntoIterator<Item = Coin>,
+    ) -> Result<(), Error> {
+        self.ensure_account(&account)?;
+        let balance_path = BalancesPath(account);
+        let mut balances = self
+            .balance_store
+            .get(Height::Pending, &balance_path)
+            .map(|b| b.0)
+            .unwrap_or_default();
+
+        for Coin { denom, amount } in amount {
+            let balance = if let Some(i) = balances.iter_mut().position(|c| c.denom == denom) {
+                &mut balances[i]
+            } else {
+                balances.push(Coin {
+                    denom,
+                    amount: 0u64.into(),
+                });
+                balances.last_mut().unwrap()
+            };
+
+            if balance.amount > U256::MAX - amount {
+                return Err(Error::DestFundOverflow);
+            }
+
+            balance.amount += amount;
+        }
+
+        // Store the updated account balances
+        self.balance_store
+            .set(balance_path, Balances(balances))
+            .map(|_| ())
+            .map_err(|e| Error::Store {
+                reason: format!("{e:?}"),
+            })?;
+
+        Ok(())
+    }
+
+    /// Returns the balances of the account that aren't locked (e.g. by a vesting schedule) at
+    /// the current block time.
+    pub fn spendable_coins(&self, address: &AccountId) -> Vec<Coin> {
+        let locked = self.locked_coins(address);
+        self.balance_store
+            .get(Height::Pending, &BalancesPath(address.clone()))
+            .map(|b| b.0)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|coin| Coin {
+                amount: coin.amount.saturating_sub(amount_of(&locked, &coin.denom)),
+                denom: coin.denom,
+            })
+            .filter(|coin| !coin.amount.is_zero())
+            .collect()
+    }
+
+    /// Returns the current block time, as unix timestamp.
+    pub fn block_time(&self) -> i64 {
+        self.block_time.load(Ordering::SeqCst)
+    }
+
+    fn locked_coins(&self, address: &AccountId) -> Vec<Coin> {
+        self.account_keeper
+            .get_account(address)
+            .map(|account| account.locked_coins(self.block_time()))
+            .unwrap_or_default()
+    }
+
+    /// Checks that the account is a module account holding the specified permission.
+    fn check_permission(&self, address: &AccountId, permission: &str) -> Result<(), Error> {
+        match self.account_keeper.get_account(address) {
+            Some(account) if account.has_permission(permission) => Ok(()),
+            _ => Err(Error::MissingPermission {
+                account: address.clone(),
+                permission: permission.to_owned(),
+            }),
+        }
+    }
+
+    /// Creates an account for the recipient of coins if it doesn't exist yet, so that it can
+    /// sign transactions later on.
+    fn ensure_account(&mut self, address: &AccountId) -> Result<(), Error> {
+        validate_address(address)?;
+        if self.account_keeper.has_account(address) {
+            return Ok(());
+        }
+
+        trace!("Creating account: {}", address);
+        let account = self
+            .account_keeper
+            .new_account_with_address(address.clone())
+            .map_err(|_| Error::Store {
+                reason: format!("failed to create account {address}"),
+            })?;
+        self.account_keeper
+            .set_account(account)
+            .map_err(|_| Error::Store {
+                reason: format!("failed to create account {address}"),
+            })
+    }
+}
+
+impl<S: Store, AK> BankKeeper for BankBalanceKeeper<S, AK>
+where
+    AK: AccountKeeper,
+    AK::Account: Account<Address = AccountId>,
+{
     type Error = anyhow::Error;
     type Address = AccountId;
     type Denom = Denom;
@@ -58,6 +209,7 @@ impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
         to: Self::Address,
         amount: impl IntoIterator<Item = Self::Coin>,
     ) -> Result<(), Self::Error> {
+        let locked = self.locked_coins(&from);
         let src_balance_path = BalancesPath(from);
         let mut src_balances = self
             .balance_store
@@ -65,6 +217,7 @@ impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
             .map(|b| b.0)
             .unwrap_or_default();
 
+        self.ensure_account(&to)?;
         let dst_balance_path = BalancesPath(to);
         let mut dst_balances = self
             .balance_store
@@ -73,10 +226,11 @@ impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
             .unwrap_or_default();
 
         for Coin { denom, amount } in amount {
+            // locked coins (e.g. vesting ones) can't be sent
             let src_balance = src_balances
                 .iter_mut()
                 .find(|c| c.denom == denom)
-                .filter(|c| c.amount >= amount)
+                .filter(|c| c.amount.saturating_sub(amount_of(&locked, &denom)) >= amount)
                 .ok_or(Error::InsufficientSourceFunds)?;
 
             let dst_balance =
@@ -118,40 +272,8 @@ impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
         account: Self::Address,
         amount: impl IntoIterator<Item = Self::Coin>,
     ) -> Result<(), Self::Error> {
-        let balance_path = BalancesPath(account);
-        let mut balances = self
-            .balance_store
-            .get(Height::Pending, &balance_path)
-            .map(|b| b.0)
-            .unwrap_or_default();
-
-        for Coin { denom, amount } in amount {
-            let balance = if let Some(i) = balances.iter_mut().position(|c| c.denom == denom) {
-                &mut balances[i]
-            } else {
-                balances.push(Coin {
-                    denom,
-                    amount: 0u64.into(),
-                });
-                balances.last_mut().unwrap()
-            };
-
-            if balance.amount > U256::MAX - amount {
-                return Err(Error::DestFundOverflow.into());
-            }
-
-            balance.amount += amount;
-        }
-
-        // Store the updated account balances
-        self.balance_store
-            .set(balance_path, Balances(balances))
-            .map(|_| ())
-            .map_err(|e| Error::Store {
-                reason: format!("{e:?}"),
-            })?;
-
-        Ok(())
+        self.check_permission(&account, MINTER)?;
+        Ok(self.add_coins(account, amount)?)
     }
 
     fn burn_coins(
@@ -159,6 +281,7 @@ impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
         account: Self::Address,
         amount: impl IntoIterator<Item = Self::Coin>,
     ) -> Result<(), Self::Error> {
+        self.check_permission(&account, BURNER)?;
         let balance_path = BalancesPath(account);
         let mut balances = self
             .balance_store
@@ -186,6 +309,29 @@ impl<S: Store> BankKeeper for BankBalanceKeeper<S> {
 
         Ok(())
     }
+
+    fn validate_send_coins(
+        &self,
+        from: &Self::Address,
+        amount: &[Self::Coin],
+    ) -> Result<(), Self::Error> {
+        let spendable = self.spendable_coins(from);
+        if amount
+            .iter()
+            .any(|coin| amount_of(&spendable, &coin.denom) < coin.amount)
+        {
+            return Err(Error::InsufficientSourceFunds.into());
+        }
+        Ok(())
+    }
+
+    fn validate_mint_coins(&self, account: &Self::Address) -> Result<(), Self::Error> {
+        Ok(self.check_permission(account, MINTER)?)
+    }
+
+    fn validate_burn_coins(&self, account: &Self::Address) -> Result<(), Self::Error> {
+        Ok(self.check_permission(account, BURNER)?)
+    }
 }
 
 /// The bank module
@@ -195,10 +341,8 @@ pub struct Bank<S, AR, AK> {
     /// The module is guaranteed exclusive access to all paths in the store key-space.
     store: SharedStore<S>,
     balance_reader: BankBalanceReader<S>,
-    balance_keeper: BankBalanceKeeper<S>,
+    balance_keeper: BankBalanceKeeper<S, AK>,
     account_reader: AR,
-    #[allow(dead_code)]
-    account_keeper: AK,
 }
 
 impl<S: 'static + ProvableStore + Default, AR: AccountReader, AK: AccountKeeper> Bank<S, AR, AK> {
@@ -210,50 +354,61 @@ impl<S: 'static + ProvableStore + Default, AR: AccountReader, AK: AccountKeeper>
             },
             balance_keeper: BankBalanceKeeper {
                 balance_store: TypedStore::new(store),
+                account_keeper,
+                block_time: Arc::new(AtomicI64::new(0)),
             },
             account_reader,
-            account_keeper,
         }
     }
 
-    pub fn service(&self) -> QueryServer<BankService<S>> {
+    pub fn service(&self) -> QueryServer<BankService<S, AK>>
+    where
+        AK: Clone,
+    {
         QueryServer::new(BankService {
             bank_reader: self.balance_reader.clone(),
+            bank_keeper: self.balance_keeper.clone(),
         })
     }
 
-    pub fn bank_keeper(&self) -> &BankBalanceKeeper<S> {
+    pub fn bank_keeper(&self) -> &BankBalanceKeeper<S, AK> {
         &self.balance_keeper
     }
 }
 
-impl<S: Store, AR: AccountReader, AK: AccountKeeper> Bank<S, AR, AK> {
+impl<S: Store, AR: AccountReader, AK: AccountKeeper> Bank<S, AR, AK>
+where
+    <AR as AccountReader>::Address: From<AccountId>,
+    <AK as AccountKeeper>::Account: From<AuthAccount> + Account<Address = AccountId>,
+{
     fn decode<T: Message + Default>(message: Any) -> Result<T> {
-        if message.type_url != "/cosmos.bank.v1beta1.MsgSend" {
-            return Err(Error::NotHandled.into());
-        }
         Ok(
             Message::decode(message.value.as_ref()).map_err(|e| Error::MsgValidationFailure {
                 reason: format!("{e:?}"),
             })?,
         )
     }
-}
 
-impl<S: ProvableStore, AR: AccountReader + Send + Sync, AK: AccountKeeper + Send + Sync> Module
-    for Bank<S, AR, AK>
-where
-    <AR as AccountReader>::Address: From<AccountId>,
-    <AK as AccountKeeper>::Account: From<AuthAccount>,
-{
-    type Store = S;
+    /// Checks that the message is signed by its sender, i.e. `from_address`.
+    fn check_signer(sender: &AccountId, signer: &AccountId) -> Result<(), Error> {
+        if sender != signer {
+            return Err(Error::InvalidSigner {
+                signer: signer.clone(),
+                sender: sender.clone(),
+            });
+        }
+        Ok(())
+    }
 
-    fn deliver(&mut self, message: Any, _signer: &AccountId) -> Result<Vec<Event>> {
+    fn send(&mut self, message: Any, signer: &AccountId) -> Result<Vec<Event>> {
         let message: MsgSend = Self::decode::<proto::cosmos::bank::v1beta1::MsgSend>(message)?
             .try_into()
             .map_err(|e| Error::MsgValidationFailure {
                 reason: format!("{e:?}"),
             })?;
+        validate_address(&message.from_address)?;
+        validate_address(&message.to_address)?;
+        Self::check_signer(&message.from_address, signer)?;
 
         self.account_reader
             .get_account(message.from_address.clone().into())
@@ -261,37 +416,256 @@ where
                 account: message.from_address.clone(),
             })?;
 
-        // Note: we allow transfers to non-existent destination accounts
+        // Note: an account is created for the recipient if it doesn't exist yet
         let amounts: Vec<Coin> = message.amount.iter().map(|amt| amt.into()).collect();
 
+        let events = transfer_events(&message.from_address, &message.to_address, &amounts);
         self.balance_keeper
             .send_coins(message.from_address, message.to_address, amounts)?;
 
-        Ok(vec![])
+        Ok(events)
+    }
+
+    /// Creates a vesting account funded by the sender. The coins vest continuously from the
+    /// current block time, or all at once at the end time if `delayed`.
+    fn create_vesting_account(&mut self, message: Any, signer: &AccountId) -> Result<Vec<Event>> {
+        let message = Self::decode::<MsgCreateVestingAccount>(message)?;
+        if message.end_time <= 0 {
+            return Err(invalid("invalid end time".to_owned()).into());
+        }
+        let amount = parse_coins(message.amount)?;
+
+        let schedule = if message.delayed {
+            VestingSchedule::Delayed
+        } else {
+            VestingSchedule::Continuous {
+                start_time: self.balance_keeper.block_time(),
+            }
+        };
+        self.fund_vesting_account(
+            &message.from_address,
+            &message.to_address,
+            signer,
+            amount,
+            message.end_time,
+            schedule,
+        )
+    }
+
+    /// Creates an account whose coins never vest, funded by the sender.
+    fn create_permanent_locked_account(
+        &mut self,
+        message: Any,
+        signer: &AccountId,
+    ) -> Result<Vec<Event>> {
+        let message = Self::decode::<MsgCreatePermanentLockedAccount>(message)?;
+        let amount = parse_coins(message.amount)?;
+
+        self.fund_vesting_account(
+            &message.from_address,
+            &message.to_address,
+            signer,
+            amount,
+            0,
+            VestingSchedule::PermanentLocked,
+        )
+    }
+
+    /// Creates a periodic vesting account funded by the sender with the total amount of the
+    /// periods - the coins of a period vest at its end, the first one starting at the start time.
+    fn create_periodic_vesting_account(
+        &mut self,
+        message: Any,
+        signer: &AccountId,
+    ) -> Result<Vec<Event>> {
+        let message = Self::decode::<MsgCreatePeriodicVestingAccount>(message)?;
+
+        let mut end_time = message.start_time;
+        let mut total = BTreeMap::<String, U256>::new();
+        let mut periods = vec![];
+        for (i, period) in message.vesting_periods.into_iter().enumerate() {
+            if period.length < 1 {
+                return Err(invalid(format!(
+                    "invalid period length of {} in period {i}, length must be greater than 0",
+                    period.length
+                ))
+                .into());
+            }
+            let period = Period::try_from(period).map_err(invalid)?;
+            validate_coins(&period.amount)
+                .map_err(|e| invalid(format!("invalid amount in period {i}: {e}")))?;
+
+            end_time = end_time
+                .checked_add(period.length)
+                .ok_or_else(|| invalid("vesting end time overflows".to_owned()))?;
+            for coin in &period.amount {
+                let sum = total.entry(coin.denom.0.clone()).or_default();
+                *sum = sum.checked_add(coin.amount).ok_or_else(|| {
+                    invalid(format!("total amount of {} overflows", coin.denom.0))
+                })?;
+            }
+            periods.push(period);
+        }
+        if periods.is_empty() {
+            return Err(invalid("no vesting periods".to_owned()).into());
+        }
+        let amount = total
+            .into_iter()
+            .map(|(denom, amount)| Coin {
+                denom: Denom(denom),
+                amount,
+            })
+            .collect();
+
+        let schedule = VestingSchedule::Periodic {
+            start_time: message.start_time,
+            periods,
+        };
+        self.fund_vesting_account(
+            &message.from_address,
+            &message.to_address,
+            signer,
+            amount,
+            end_time,
+            schedule,
+        )
+    }
+
+    /// Creates the recipient's account with the vesting state, and sends it the (validated)
+    /// amount, which is its original vesting.
+    fn fund_vesting_account(
+        &mut self,
+        from_address: &str,
+        to_address: &str,
+        signer: &AccountId,
+        amount: Vec<Coin>,
+        end_time: i64,
+        schedule: VestingSchedule,
+    ) -> Result<Vec<Event>> {
+        let from_address: AccountId = from_address
+            .parse()
+            .map_err(|_| invalid(format!("invalid 'from' address: {from_address}")))?;
+        Self::check_signer(&from_address, signer)?;
+        let to_address: AccountId = to_address
+            .parse()
+            .map_err(|_| invalid(format!("invalid 'to' address: {to_address}")))?;
+        validate_address(&from_address)?;
+        validate_address(&to_address)?;
+
+        self.account_reader
+            .get_account(from_address.clone().into())
+            .map_err(|_| Error::NonExistentAccount {
+                account: from_address.clone(),
+            })?;
+        if self.balance_keeper.account_keeper.has_account(&to_address) {
+            return Err(Error::AccountExists {
+                account: to_address,
+            }
+            .into());
+        }
+
+        // the funds are checked before the vesting account is created, so that it's never
+        // created without its coins
+        self.balance_keeper
+            .validate_send_coins(&from_address, &amount)?;
+
+        let account_keeper = &mut self.balance_keeper.account_keeper;
+        let store_error = |_| Error::Store {
+            reason: format!("failed to create account {to_address}"),
+        };
+        let account_number = account_keeper.new_account_number().map_err(store_error)?;
+        account_keeper
+            .set_account(
+                AuthAccount::new_vesting_account(
+                    to_address.clone(),
+                    account_number,
+                    Vesting::new(amount.clone(), end_time, schedule),
+                )
+                .into(),
+            )
+            .map_err(store_error)?;
+
+        let events = transfer_events(&from_address, &to_address, &amount);
+        self.balance_keeper
+            .send_coins(from_address, to_address, amount)?;
+
+        Ok(events)
+    }
+}
+
+impl<S: ProvableStore, AR: AccountReader + Send + Sync, AK: AccountKeeper + Send + Sync> Module
+    for Bank<S, AR, AK>
+where
+    <AR as AccountReader>::Address: From<AccountId>,
+    <AK as AccountKeeper>::Account: From<AuthAccount> + Account<Address = AccountId>,
+{
+    type Store = S;
+
+    fn msg_type_urls(&self) -> Vec<&'static str> {
+        vec![
+            MSG_SEND_TYPE_URL,
+            MSG_CREATE_VESTING_ACCOUNT_TYPE_URL,
+            MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL,
+            MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+        ]
     }
 
-    fn init(&mut self, app_state: serde_json::Value) {
+    fn deliver(&mut self, message: Any, signer: &AccountId) -> Result<DeliverResult> {
+        let type_url = message.type_url.clone();
+        let (events, response) = match type_url.as_str() {
+            MSG_SEND_TYPE_URL => (
+                self.send(message, signer)?,
+                proto::cosmos::bank::v1beta1::MsgSendResponse {}.encode_to_vec(),
+            ),
+            MSG_CREATE_VESTING_ACCOUNT_TYPE_URL => (
+                self.create_vesting_account(message, signer)?,
+                MsgCreateVestingAccountResponse {}.encode_to_vec(),
+            ),
+            MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL => (
+                self.create_permanent_locked_account(message, signer)?,
+                MsgCreatePermanentLockedAccountResponse {}.encode_to_vec(),
+            ),
+            MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL => (
+                self.create_periodic_vesting_account(message, signer)?,
+                MsgCreatePeriodicVestingAccountResponse {}.encode_to_vec(),
+            ),
+            _ => return Err(Error::NotHandled.into()),
+        };
+
+        // the response of a `Msg` is the message of the same name with a `Response` suffix
+        Ok(DeliverResult {
+            events,
+            msg_response: Some(Any {
+                type_url: format!("{type_url}Response"),
+                value: response,
+            }),
+        })
+    }
+
+    fn init(&mut self, genesis: serde_json::Value) {
         debug!("Initializing bank module");
 
         // safety - we panic on errors to prevent chain creation with invalid genesis config
-        let accounts: HashMap<String, HashMap<Denom, U256>> =
-            serde_json::from_value(app_state).unwrap();
-        for (account, balances) in accounts {
-            trace!("Adding account ({}) => {:?}", account, balances);
-
-            let account_id = AccountId::from_str(&account).unwrap();
+        let genesis = GenesisState::decode(&genesis).unwrap();
+        for balance in genesis.balances {
+            trace!(
+                "Adding account ({}) => {:?}",
+                balance.address,
+                balance.coins
+            );
+
+            let account_id = balance.address().unwrap();
             self.balance_keeper
-                .mint_coins(
-                    account_id,
-                    balances
-                        .into_iter()
-                        .map(|b| b.into())
-                        .collect::<Vec<Coin>>(),
-                )
+                .add_coins(account_id, balance.coins().unwrap())
                 .unwrap();
         }
     }
 
+    fn validate_genesis(&self, genesis: &serde_json::Value) -> Result<()> {
+        GenesisState::decode(genesis)?.validate()?;
+        Ok(())
+    }
+
     fn query(
         &self,
         data: &[u8],
@@ -300,7 +674,7 @@ where
         _prove: bool,
     ) -> Result<QueryResult> {
         let account_id = match String::from_utf8(data.to_vec()) {
-            Ok(s) if s.starts_with(ACCOUNT_PREFIX) => s, // TODO(hu55a1n1): check if valid identifier
+            Ok(s) if s.starts_with(&account_prefix()) => s, // TODO(hu55a1n1): check if valid identifier
             _ => return Err(Error::NotHandled.into()),
         };
 
@@ -337,7 +711,205 @@ where
         Ok(())
     }
 
-    fn begin_block(&mut self, _header: &tendermint::block::Header) -> Vec<Event> {
+    fn begin_block(
+        &mut self,
+        header: &tendermint::block::Header,
+        _last_commit_info: &CommitInfo,
+    ) -> Vec<Event> {
+        self.balance_keeper
+            .block_time
+            .store(header.time.unix_timestamp(), Ordering::SeqCst);
         vec![]
     }
 }
+
+/// Checks that the address is an account address, i.e. that it has the configured account
+/// prefix and a valid length, the checks of Go's `AccAddressFromBech32`.
+fn validate_address(address: &AccountId) -> Result<(), Error> {
+    Bech32Codec::new(account_prefix())
+        .string_to_bytes(address.as_ref())
+        .map(|_| ())
+        .map_err(|e| Error::InvalidAddress {
+            address: address.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+fn invalid(reason: String) -> Error {
+    Error::MsgValidationFailure { reason }
+}
+
+/// Parses the coins of a message, which must be valid (see `validate_coins`).
+fn parse_coins(coins: Vec<RawCoin>) -> Result<Vec<Coin>, Error> {
+    let coins = coins
+        .into_iter()
+        .map(Coin::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid("invalid coins".to_owned()))?;
+    validate_coins(&coins).map_err(invalid)?;
+    Ok(coins)
+}
+
+/// Checks the coins as the Go SDK's `Coins.Validate` does: the denominations must be valid and
+/// strictly sorted (i.e. unique) and the amounts positive. The vesting amounts are looked up by
+/// denomination, so a duplicate would leave the second entry unlocked.
+fn validate_coins(coins: &[Coin]) -> Result<(), String> {
+    if coins.is_empty() {
+        return Err("invalid coins: empty".to_owned());
+    }
+    for coin in coins {
+        cosmos_x_module_api::genesis::validate_denom(&coin.denom.0)?;
+        if coin.amount.is_zero() {
+            return Err(format!(
+                "coin {}{} amount is not positive",
+                coin.amount, coin.denom.0
+            ));
+        }
+    }
+    for pair in coins.windows(2) {
+        let (previous, denom) = (&pair[0].denom.0, &pair[1].denom.0);
+        if previous == denom {
+            return Err(format!("duplicate denomination {denom}"));
+        }
+        if previous > denom {
+            return Err(format!("denomination {denom} is not sorted"));
+        }
+    }
+    Ok(())
+}
+
+/// Events of a transfer of coins, i.e. the `coin_spent`, `coin_received`, `transfer` and `message`
+/// events Go's `SendCoins` emits. The attributes are indexed, so that transactions can be searched
+/// by sender or recipient, e.g. with `transfer.recipient='cosmos1...'`.
+fn transfer_events(from: &AccountId, to: &AccountId, amount: &[Coin]) -> Vec<Event> {
+    let mut coins: Vec<_> = amount
+        .iter()
+        .map(|coin| (coin.denom.0.as_str(), coin.amount))
+        .collect();
+    coins.sort();
+    let amount = coins
+        .iter()
+        .map(|(denom, amount)| format!("{amount}{denom}"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let event = |kind: &str, attributes: &[(&str, &str)]| Event {
+        r#type: kind.to_owned(),
+        attributes: attributes
+            .iter()
+            .map(|(key, value)| EventAttribute {
+                key: key.to_string(),
+                value: value.to_string(),
+                index: true,
+            })
+            .collect(),
+    };
+    vec![
+        event(
+            "coin_spent",
+            &[("spender", from.as_ref()), ("amount", amount.as_str())],
+        ),
+        event(
+            "coin_received",
+            &[("receiver", to.as_ref()), ("amount", amount.as_str())],
+        ),
+        event(
+            "transfer",
+            &[
+                ("recipient", to.as_ref()),
+                ("sender", from.as_ref()),
+                ("amount", amount.as_str()),
+            ],
+        ),
+        event("message", &[("sender", from.as_ref())]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_events() {
+        let (sender, recipient) = (
+            "cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efws",
+            "cosmos1t2e0nyjhwn3revunvf2uperhftvhzu4euuzva9",
+        );
+        let amount = [
+            Coin {
+                denom: Denom("stake".to_owned()),
+                amount: 10u64.into(),
+            },
+            Coin {
+                denom: Denom("atom".to_owned()),
+                amount: 5u64.into(),
+            },
+        ];
+
+        let events = transfer_events(
+            &sender.parse().unwrap(),
+            &recipient.parse().unwrap(),
+            &amount,
+        );
+        let kinds: Vec<_> = events.iter().map(|event| event.r#type.as_str()).collect();
+        assert_eq!(
+            kinds,
+            ["coin_spent", "coin_received", "transfer", "message"]
+        );
+        assert!(events
+            .iter()
+            .flat_map(|event| &event.attributes)
+            .all(|attr| attr.index));
+
+        let transfer: Vec<_> = events[2]
+            .attributes
+            .iter()
+            .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+            .collect();
+        assert_eq!(
+            transfer,
+            [
+                ("recipient", recipient),
+                ("sender", sender),
+                ("amount", "5atom,10stake"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_address() {
+        let address =
+            |address: &str| validate_address(&address.parse().unwrap()).map_err(|e| e.to_string());
+
+        address("cosmos12xpmzmfpf7tn57xg93rne2hc2q26lcfql5efws").unwrap();
+        assert!(address("osmo12xpmzmfpf7tn57xg93rne2hc2q26lcfqh02ecz")
+            .unwrap_err()
+            .contains("cosmos"));
+        // 10 bytes long
+        assert!(address("cosmos1qqqsyqcyq5rqwzqfys8f67").is_err());
+    }
+
+    #[test]
+    fn test_validate_coins() {
+        let coins = |coins: &[(&str, u64)]| -> Vec<Coin> {
+            coins
+                .iter()
+                .map(|&(denom, amount)| Coin {
+                    denom: Denom(denom.to_owned()),
+                    amount: amount.into(),
+                })
+                .collect()
+        };
+
+        validate_coins(&coins(&[("atom", 5), ("stake", 10)])).unwrap();
+        assert!(validate_coins(&[]).is_err());
+        assert!(validate_coins(&coins(&[("stake", 0)])).is_err());
+        assert!(validate_coins(&coins(&[("1atom", 5)])).is_err());
+        assert!(validate_coins(&coins(&[("stake", 10), ("atom", 5)]))
+            .unwrap_err()
+            .contains("not sorted"));
+        assert!(validate_coins(&coins(&[("stake", 10), ("stake", 5)]))
+            .unwrap_err()
+            .contains("duplicate"));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod context;
 pub mod error;
+pub mod genesis;
 pub mod impls;
 pub mod service;
@@ -13,18 +13,27 @@ use ibc_proto::cosmos::{
 use tonic::{Request, Response, Status};
 
 use cosmos_store::ProvableStore;
+use cosmos_x_auth::context::{Account, AccountKeeper};
+use cosmos_x_auth::impls::AuthAccountKeeper;
 use cosmos_x_bank_type::Denom;
+use cosmrs::AccountId;
 use tracing::debug;
 
 use super::context::BankReader;
-use super::impls::BankBalanceReader;
+use super::impls::{BankBalanceKeeper, BankBalanceReader};
 
-pub struct BankService<S> {
+pub struct BankService<S, AK = AuthAccountKeeper<S>> {
     pub bank_reader: BankBalanceReader<S>,
+    pub bank_keeper: BankBalanceKeeper<S, AK>,
 }
 
 #[tonic::async_trait]
-impl<S: ProvableStore + 'static> Query for BankService<S> {
+impl<S, AK> Query for BankService<S, AK>
+where
+    S: ProvableStore + 'static,
+    AK: AccountKeeper + Send + Sync + 'static,
+    AK::Account: Account<Address = AccountId>,
+{
     async fn balance(
         &self,
         request: Request<QueryBalanceRequest>,
@@ -52,57 +61,81 @@ impl<S: ProvableStore + 'static> Query for BankService<S> {
 
     async fn all_balances(
         &self,
-        _request: Request<QueryAllBalancesRequest>,
+        request: Request<QueryAllBalancesRequest>,
     ) -> Result<Response<QueryAllBalancesResponse>, Status> {
-        unimplemented!()
+        debug!("Got bank all balances request: {:?}", request);
+
+        let account_id: AccountId = request
+            .get_ref()
+            .address
+            .parse()
+            .map_err(|e| Status::invalid_argument(format!("{e}")))?;
+        let balances = self.bank_reader.get_all_balances(account_id);
+
+        Ok(Response::new(QueryAllBalancesResponse {
+            balances: balances.into_iter().map(RawCoin::from).collect(),
+            pagination: None,
+        }))
     }
 
     async fn spendable_balances(
         &self,
-        _request: Request<QuerySpendableBalancesRequest>,
+        request: Request<QuerySpendableBalancesRequest>,
     ) -> Result<Response<QuerySpendableBalancesResponse>, Status> {
-        unimplemented!()
+        debug!("Got bank spendable balances request: {:?}", request);
+
+        let account_id: AccountId = request
+            .get_ref()
+            .address
+            .parse()
+            .map_err(|e| Status::invalid_argument(format!("{e}")))?;
+        let balances = self.bank_keeper.spendable_coins(&account_id);
+
+        Ok(Response::new(QuerySpendableBalancesResponse {
+            balances: balances.into_iter().map(RawCoin::from).collect(),
+            pagination: None,
+        }))
     }
 
     async fn total_supply(
         &self,
         _request: Request<QueryTotalSupplyRequest>,
     ) -> Result<Response<QueryTotalSupplyResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("TotalSupply is not implemented"))
     }
 
     async fn supply_of(
         &self,
         _request: Request<QuerySupplyOfRequest>,
     ) -> Result<Response<QuerySupplyOfResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("SupplyOf is not implemented"))
     }
 
     async fn params(
         &self,
         _request: Request<QueryParamsRequest>,
     ) -> Result<Response<QueryParamsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Params is not implemented"))
     }
 
     async fn denom_metadata(
         &self,
         _request: Request<QueryDenomMetadataRequest>,
     ) -> Result<Response<QueryDenomMetadataResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("DenomMetadata is not implemented"))
     }
 
     async fn denoms_metadata(
         &self,
         _request: Request<QueryDenomsMetadataRequest>,
     ) -> Result<Response<QueryDenomsMetadataResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("DenomsMetadata is not implemented"))
     }
 
     async fn denom_owners(
         &self,
         _request: Request<QueryDenomOwnersRequest>,
     ) -> Result<Response<QueryDenomOwnersResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("DenomOwners is not implemented"))
     }
 }
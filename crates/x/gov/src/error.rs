@@ -1,3 +1,6 @@
+use cosmos_x_module_api::error::{
+    AbciError, RegisteredError, ERR_INVALID_REQUEST, ERR_UNKNOWN_REQUEST,
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -11,9 +14,44 @@ pub enum Error {
     #[error("invalid proposal: `{reason}`")]
     InvalidProposal { reason: String },
 
+    #[error("invalid genesis state: {reason}")]
+    InvalidGenesis { reason: String },
     #[error("data not found")]
     DataNotFound,
 
     #[error("not handled")]
     NotHandled,
 }
+
+/// Codespace of the gov module's errors.
+pub const CODESPACE: &str = "gov";
+
+pub const ERR_UNKNOWN_PROPOSAL: RegisteredError =
+    RegisteredError::new(CODESPACE, 2, "unknown proposal");
+pub const ERR_INVALID_PROPOSAL_CONTENT: RegisteredError =
+    RegisteredError::new(CODESPACE, 5, "invalid proposal content");
+pub const ERR_INVALID_PROPOSAL_TYPE: RegisteredError =
+    RegisteredError::new(CODESPACE, 6, "invalid proposal type");
+pub const ERR_INVALID_GENESIS: RegisteredError =
+    RegisteredError::new(CODESPACE, 8, "invalid genesis state");
+
+/// All the errors registered by the gov module.
+pub const REGISTERED_ERRORS: &[RegisteredError] = &[
+    ERR_UNKNOWN_PROPOSAL,
+    ERR_INVALID_PROPOSAL_CONTENT,
+    ERR_INVALID_PROPOSAL_TYPE,
+    ERR_INVALID_GENESIS,
+];
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::Custom(_) => ERR_INVALID_REQUEST,
+            Error::UnknownTypeUrl(_) => ERR_INVALID_PROPOSAL_TYPE,
+            Error::InvalidProposal { .. } => ERR_INVALID_PROPOSAL_CONTENT,
+            Error::InvalidGenesis { .. } => ERR_INVALID_GENESIS,
+            Error::DataNotFound => ERR_UNKNOWN_PROPOSAL,
+            Error::NotHandled => ERR_UNKNOWN_REQUEST,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use cosmos_x_module_api::genesis::{
+    decode_genesis, ensure_empty, parse_dec, parse_duration, u64_string, validate_denom, DEC_ONE,
+};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Genesis state of the gov module, i.e. `app_state.gov`, as the Go SDK's
+/// `cosmos.gov.v1beta1.GenesisState`.
+///
+/// The module doesn't store its params yet and proposals can't be imported, so the params are
+/// validated only and the proposals, deposits and votes must be empty.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GenesisState {
+    #[serde(with = "u64_string")]
+    pub starting_proposal_id: u64,
+    pub deposits: Vec<Value>,
+    pub votes: Vec<Value>,
+    pub proposals: Vec<Value>,
+    pub deposit_params: DepositParams,
+    pub voting_params: VotingParams,
+    pub tally_params: TallyParams,
+}
+
+impl Default for GenesisState {
+    /// The values of Go's `DefaultGenesisState()`, i.e. proposal ids start at 1.
+    fn default() -> Self {
+        Self {
+            starting_proposal_id: 1,
+            deposits: vec![],
+            votes: vec![],
+            proposals: vec![],
+            deposit_params: DepositParams::default(),
+            voting_params: VotingParams::default(),
+            tally_params: TallyParams::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DepositParams {
+    pub min_deposit: Vec<GenesisCoin>,
+    pub max_deposit_period: String,
+}
+
+impl Default for DepositParams {
+    fn default() -> Self {
+        Self {
+            min_deposit: vec![GenesisCoin {
+                denom: "stake".to_owned(),
+                amount: "10000000".to_owned(),
+            }],
+            max_deposit_period: "172800s".to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VotingParams {
+    pub voting_period: String,
+}
+
+impl Default for VotingParams {
+    fn default() -> Self {
+        Self {
+            voting_period: "172800s".to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TallyParams {
+    pub quorum: String,
+    pub threshold: String,
+    pub veto_threshold: String,
+}
+
+impl Default for TallyParams {
+    fn default() -> Self {
+        Self {
+            quorum: "0.334000000000000000".to_owned(),
+            threshold: "0.500000000000000000".to_owned(),
+            veto_threshold: "0.334000000000000000".to_owned(),
+        }
+    }
+}
+
+/// A proto-JSON encoded coin, i.e. with a decimal amount.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+impl GenesisState {
+    /// Decodes the gov module's section of the genesis `app_state`.
+    pub fn decode(genesis: &Value) -> Result<Self, Error> {
+        decode_genesis(genesis).map_err(invalid_genesis)
+    }
+
+    /// Checks that the tally thresholds are at most one, the minimum deposit is valid coins and
+    /// the periods are positive.
+    pub fn validate(&self) -> Result<(), Error> {
+        ensure_empty("proposals", &self.proposals).map_err(invalid_genesis)?;
+        ensure_empty("deposits", &self.deposits).map_err(invalid_genesis)?;
+        ensure_empty("votes", &self.votes).map_err(invalid_genesis)?;
+
+        for (name, value) in [
+            ("quorum", &self.tally_params.quorum),
+            ("vote threshold", &self.tally_params.threshold),
+            ("vote veto threshold", &self.tally_params.veto_threshold),
+        ] {
+            let dec = parse_dec(value).map_err(invalid_genesis)?;
+            if dec > DEC_ONE {
+                return Err(invalid_genesis(format!(
+                    "governance {name} should be positive and less or equal to one, is {value}"
+                )));
+            }
+        }
+
+        for coin in &self.deposit_params.min_deposit {
+            validate_denom(&coin.denom).map_err(invalid_genesis)?;
+            if !matches!(coin.amount.parse::<u128>(), Ok(amount) if amount > 0) {
+                return Err(invalid_genesis(format!(
+                    "governance deposit amount must be a valid sdk.Coins amount, is {}{}",
+                    coin.amount, coin.denom
+                )));
+            }
+        }
+
+        for (name, value) in [
+            (
+                "maximum deposit period",
+                &self.deposit_params.max_deposit_period,
+            ),
+            ("voting period", &self.voting_params.voting_period),
+        ] {
+            if parse_duration(value).map_err(invalid_genesis)?.is_zero() {
+                return Err(invalid_genesis(format!("{name} must be positive: {value}")));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_genesis(reason: String) -> Error {
+    Error::InvalidGenesis { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_genesis() {
+        let genesis = GenesisState::decode(&Value::Null).unwrap();
+        assert_eq!(genesis, GenesisState::default());
+        genesis.validate().unwrap();
+
+        // `simd init` output
+        let genesis = GenesisState::decode(&json!({
+            "starting_proposal_id": "1",
+            "deposits": [],
+            "votes": [],
+            "proposals": [],
+            "deposit_params": {
+                "min_deposit": [{ "denom": "stake", "amount": "10000000" }],
+                "max_deposit_period": "172800s"
+            },
+            "voting_params": { "voting_period": "172800s" },
+            "tally_params": {
+                "quorum": "0.334000000000000000",
+                "threshold": "0.500000000000000000",
+                "veto_threshold": "0.334000000000000000"
+            }
+        }))
+        .unwrap();
+        assert_eq!(genesis, GenesisState::default());
+    }
+
+    #[test]
+    fn test_validate() {
+        let invalid = |update: fn(&mut GenesisState)| {
+            let mut genesis = GenesisState::default();
+            update(&mut genesis);
+            genesis.validate().unwrap_err().to_string()
+        };
+
+        assert!(invalid(|g| g.tally_params.threshold = "1.1".to_owned()).contains("threshold"));
+        assert!(
+            invalid(|g| g.tally_params.veto_threshold = "-0.1".to_owned())
+                .contains("invalid decimal")
+        );
+        assert!(
+            invalid(|g| g.deposit_params.min_deposit[0].amount = "0".to_owned())
+                .contains("deposit amount")
+        );
+        assert!(
+            invalid(|g| g.deposit_params.min_deposit[0].denom = "1x".to_owned())
+                .contains("invalid denom")
+        );
+        assert!(invalid(|g| g.voting_params.voting_period = "0s".to_owned())
+            .contains("voting period must be positive"));
+        assert!(invalid(|g| g.proposals.push(json!({}))).contains("importing proposals"));
+
+        let mut genesis = GenesisState::default();
+        genesis.tally_params.threshold = "1".to_owned();
+        genesis.deposit_params.min_deposit.clear();
+        genesis.validate().unwrap();
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::Error;
 use anyhow::Result;
+use prost::Message;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::ops::DerefMut;
@@ -10,18 +11,19 @@ use cosmrs::AccountId;
 use ibc::hosts::tendermint::upgrade_proposal::upgrade_client_proposal_handler;
 use ibc::hosts::tendermint::upgrade_proposal::UpgradeProposal;
 use ibc_proto::cosmos::gov::v1beta1::query_server::QueryServer;
+use ibc_proto::cosmos::gov::v1beta1::MsgSubmitProposalResponse;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::protobuf::Protobuf;
 
-use tendermint_proto::abci::Event;
-
 use super::path::ProposalPath;
 use super::proposal::Proposal;
 use super::service::GovernanceService;
-use crate::msg::MsgSubmitProposal;
+use crate::genesis::GenesisState;
+use crate::msg::{MsgSubmitProposal, RESPONSE_TYPE_URL};
 use cosmos_helper::{Height, Path, QueryResult};
 use cosmos_store::{ProtobufStore, SharedRw, SharedStore, Store, TypedStore};
 use cosmos_x_module_api::module::Module;
+use cosmos_x_module_api::types::DeliverResult;
 use cosmos_x_upgrade::impls::Upgrade;
 
 #[derive(Clone)]
@@ -62,7 +64,11 @@ where
 {
     type Store = S;
 
-    fn deliver(&mut self, message: Any, _signer: &AccountId) -> Result<Vec<Event>> {
+    fn msg_type_urls(&self) -> Vec<&'static str> {
+        vec![crate::msg::TYPE_URL]
+    }
+
+    fn deliver(&mut self, message: Any, _signer: &AccountId) -> Result<DeliverResult> {
         if let Ok(message) = MsgSubmitProposal::try_from(message) {
             debug!("Delivering proposal message: {:?}", message);
 
@@ -80,14 +86,28 @@ where
                 .set(ProposalPath::sdk_path(), proposal)
                 .unwrap();
 
+            let response = MsgSubmitProposalResponse {
+                proposal_id: self.proposal_counter,
+            };
             self.proposal_counter += 1;
 
-            Ok(vec![event])
+            Ok(DeliverResult {
+                events: vec![event],
+                msg_response: Some(Any {
+                    type_url: RESPONSE_TYPE_URL.to_owned(),
+                    value: response.encode_to_vec(),
+                }),
+            })
         } else {
             Err(Error::NotHandled.into())
         }
     }
 
+    fn validate_genesis(&self, genesis: &serde_json::Value) -> Result<()> {
+        GenesisState::decode(genesis)?.validate()?;
+        Ok(())
+    }
+
     fn query(
         &self,
         _data: &[u8],
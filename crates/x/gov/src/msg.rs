@@ -9,6 +9,8 @@ use cosmos_x_bank_type::Coin;
 use super::proposal::Proposal;
 
 pub(crate) const TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgSubmitProposal";
+/// Type URL of the response to a `MsgSubmitProposal`
+pub(crate) const RESPONSE_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgSubmitProposalResponse";
 
 #[derive(Clone, Debug)]
 pub struct MsgSubmitProposal {
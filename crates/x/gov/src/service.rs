@@ -29,55 +29,55 @@ impl<S: Store + 'static> GovernanceQuery for GovernanceService<S> {
         &self,
         _request: Request<QueryProposalRequest>,
     ) -> Result<Response<QueryProposalResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Proposal is not implemented"))
     }
 
     async fn proposals(
         &self,
         _request: Request<QueryProposalsRequest>,
     ) -> Result<Response<QueryProposalsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Proposals is not implemented"))
     }
 
     async fn vote(
         &self,
         _request: Request<QueryVoteRequest>,
     ) -> Result<Response<QueryVoteResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Vote is not implemented"))
     }
 
     async fn votes(
         &self,
         _request: Request<QueryVotesRequest>,
     ) -> Result<Response<QueryVotesResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Votes is not implemented"))
     }
 
     async fn params(
         &self,
         _request: Request<QueryParamsRequest>,
     ) -> Result<Response<QueryParamsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Params is not implemented"))
     }
 
     async fn deposit(
         &self,
         _request: Request<QueryDepositRequest>,
     ) -> Result<Response<QueryDepositResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Deposit is not implemented"))
     }
 
     async fn deposits(
         &self,
         _request: Request<QueryDepositsRequest>,
     ) -> Result<Response<QueryDepositsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Deposits is not implemented"))
     }
 
     async fn tally_result(
         &self,
         _request: Request<QueryTallyResultRequest>,
     ) -> Result<Response<QueryTallyResultResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("TallyResult is not implemented"))
     }
 }
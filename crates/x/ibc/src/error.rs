@@ -1,3 +1,7 @@
+use cosmos_x_module_api::error::{
+    AbciError, RegisteredError, ERR_INTERNAL, ERR_INVALID_REQUEST, ERR_NOT_FOUND,
+    ERR_UNKNOWN_REQUEST,
+};
 use ibc::core::ContextError;
 use ibc::core::RouterError;
 use thiserror::Error;
@@ -19,6 +23,9 @@ pub enum Error {
     #[error("Invalid IBC path:({0})")]
     InvalidIbcPath(String),
 
+    #[error("invalid genesis state: {reason}")]
+    InvalidGenesis { reason: String },
+
     #[error("Proof not Found")]
     ProofNotFound,
 
@@ -28,3 +35,17 @@ pub enum Error {
     #[error("not handled")]
     NotHandled,
 }
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::Unknown(_) => ERR_INTERNAL,
+            Error::RouterError(_) => ERR_UNKNOWN_REQUEST,
+            Error::ContextError(_) => ERR_INVALID_REQUEST,
+            Error::InvalidDomainPath(_) | Error::InvalidIbcPath(_) => ERR_INVALID_REQUEST,
+            Error::InvalidGenesis { .. } => ERR_INVALID_REQUEST,
+            Error::ProofNotFound | Error::DataNotFound => ERR_NOT_FOUND,
+            Error::NotHandled => ERR_UNKNOWN_REQUEST,
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use cosmos_x_module_api::genesis::{decode_genesis, ensure_empty, u64_string};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Genesis state of the ibc module, i.e. `app_state.ibc`, as ibc-go's
+/// `ibc.core.types.v1.GenesisState`.
+///
+/// Clients, connections and channels can't be imported yet, so they must be empty and their
+/// sequences must be zero; only the params are validated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GenesisState {
+    pub client_genesis: ClientGenesis,
+    pub connection_genesis: ConnectionGenesis,
+    pub channel_genesis: ChannelGenesis,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ClientGenesis {
+    pub clients: Vec<Value>,
+    pub clients_consensus: Vec<Value>,
+    pub clients_metadata: Vec<Value>,
+    pub params: ClientParams,
+    #[serde(with = "u64_string")]
+    pub next_client_sequence: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClientParams {
+    pub allowed_clients: Vec<String>,
+}
+
+impl Default for ClientParams {
+    /// The clients ibc-go allows by default.
+    fn default() -> Self {
+        Self {
+            allowed_clients: vec![
+                "06-solomachine".to_owned(),
+                "07-tendermint".to_owned(),
+                "09-localhost".to_owned(),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ConnectionGenesis {
+    pub connections: Vec<Value>,
+    pub client_connection_paths: Vec<Value>,
+    #[serde(with = "u64_string")]
+    pub next_connection_sequence: u64,
+    pub params: ConnectionParams,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConnectionParams {
+    /// In nanoseconds.
+    #[serde(with = "u64_string")]
+    pub max_expected_time_per_block: u64,
+}
+
+impl Default for ConnectionParams {
+    /// ibc-go's default of 30 seconds.
+    fn default() -> Self {
+        Self {
+            max_expected_time_per_block: 30_000_000_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChannelGenesis {
+    pub channels: Vec<Value>,
+    pub acknowledgements: Vec<Value>,
+    pub commitments: Vec<Value>,
+    pub receipts: Vec<Value>,
+    pub send_sequences: Vec<Value>,
+    pub recv_sequences: Vec<Value>,
+    pub ack_sequences: Vec<Value>,
+    #[serde(with = "u64_string")]
+    pub next_channel_sequence: u64,
+}
+
+impl GenesisState {
+    /// Decodes the ibc module's section of the genesis `app_state`.
+    pub fn decode(genesis: &Value) -> Result<Self, Error> {
+        decode_genesis(genesis).map_err(invalid_genesis)
+    }
+
+    /// Same param checks as ibc-go's `ValidateGenesis`, i.e. the allowed client types must be
+    /// unique and not blank, and the expected time per block must be positive.
+    pub fn validate(&self) -> Result<(), Error> {
+        let client = &self.client_genesis;
+        let connection = &self.connection_genesis;
+        let channel = &self.channel_genesis;
+        for (name, values) in [
+            ("clients", &client.clients),
+            ("client consensus states", &client.clients_consensus),
+            ("client metadata", &client.clients_metadata),
+            ("connections", &connection.connections),
+            (
+                "client connection paths",
+                &connection.client_connection_paths,
+            ),
+            ("channels", &channel.channels),
+            ("packet acknowledgements", &channel.acknowledgements),
+            ("packet commitments", &channel.commitments),
+            ("packet receipts", &channel.receipts),
+            ("send sequences", &channel.send_sequences),
+            ("recv sequences", &channel.recv_sequences),
+            ("ack sequences", &channel.ack_sequences),
+        ] {
+            ensure_empty(name, values).map_err(invalid_genesis)?;
+        }
+        for (name, sequence) in [
+            ("next client sequence", client.next_client_sequence),
+            (
+                "next connection sequence",
+                connection.next_connection_sequence,
+            ),
+            ("next channel sequence", channel.next_channel_sequence),
+        ] {
+            if sequence != 0 {
+                return Err(invalid_genesis(format!(
+                    "importing a {name} isn't supported, is {sequence}"
+                )));
+            }
+        }
+
+        let allowed_clients = &client.params.allowed_clients;
+        for (i, client_type) in allowed_clients.iter().enumerate() {
+            if client_type.trim().is_empty() {
+                return Err(invalid_genesis(format!("client type {i} cannot be blank")));
+            }
+            if allowed_clients[..i].contains(client_type) {
+                return Err(invalid_genesis(format!(
+                    "duplicate allowed client type: {client_type}"
+                )));
+            }
+        }
+
+        if connection.params.max_expected_time_per_block == 0 {
+            return Err(invalid_genesis(
+                "max expected time per block cannot be zero".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn invalid_genesis(reason: String) -> Error {
+    Error::InvalidGenesis { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_genesis() {
+        let genesis = GenesisState::decode(&Value::Null).unwrap();
+        assert_eq!(genesis, GenesisState::default());
+        genesis.validate().unwrap();
+
+        // `simd init` output
+        let genesis = GenesisState::decode(&json!({
+            "client_genesis": {
+                "clients": [],
+                "clients_consensus": [],
+                "clients_metadata": [],
+                "params": {
+                    "allowed_clients": ["06-solomachine", "07-tendermint", "09-localhost"]
+                },
+                "create_localhost": false,
+                "next_client_sequence": "0"
+            },
+            "connection_genesis": {
+                "connections": [],
+                "client_connection_paths": [],
+                "next_connection_sequence": "0",
+                "params": { "max_expected_time_per_block": "30000000000" }
+            },
+            "channel_genesis": {
+                "channels": [],
+                "acknowledgements": [],
+                "commitments": [],
+                "receipts": [],
+                "send_sequences": [],
+                "recv_sequences": [],
+                "ack_sequences": [],
+                "next_channel_sequence": "0"
+            }
+        }))
+        .unwrap();
+        assert_eq!(genesis, GenesisState::default());
+    }
+
+    #[test]
+    fn test_validate() {
+        let invalid = |update: fn(&mut GenesisState)| {
+            let mut genesis = GenesisState::default();
+            update(&mut genesis);
+            genesis.validate().unwrap_err().to_string()
+        };
+
+        assert!(invalid(|g| g.client_genesis.clients.push(json!({}))).contains("clients"));
+        assert!(invalid(|g| g.channel_genesis.next_channel_sequence = 1)
+            .contains("next channel sequence"));
+        assert!(
+            invalid(|g| g.client_genesis.params.allowed_clients.push(" ".to_owned()))
+                .contains("cannot be blank")
+        );
+        assert!(invalid(|g| g
+            .client_genesis
+            .params
+            .allowed_clients
+            .push("07-tendermint".to_owned()))
+        .contains("duplicate"));
+        assert!(
+            invalid(|g| g.connection_genesis.params.max_expected_time_per_block = 0)
+                .contains("max expected time per block")
+        );
+
+        let mut genesis = GenesisState::default();
+        genesis.client_genesis.params.allowed_clients.clear();
+        genesis.validate().unwrap();
+    }
+}
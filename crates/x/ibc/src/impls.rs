@@ -3,6 +3,7 @@ use super::{
     service::{IbcChannelService, IbcClientService, IbcConnectionService},
 };
 use crate::error::Error;
+use crate::genesis::GenesisState;
 use crate::transfer::IbcTransferModule;
 use anyhow::Result;
 use cosmos_helper::{Height, Path, QueryResult};
@@ -11,6 +12,7 @@ use cosmos_store::{
 };
 use cosmos_x_bank::impls::BankBalanceKeeper;
 use cosmos_x_module_api::module::{Identifiable, Module};
+use cosmos_x_module_api::types::DeliverResult;
 use cosmrs::AccountId;
 use ibc::{
     applications::transfer::msgs::transfer::MsgTransfer,
@@ -74,7 +76,7 @@ use std::{
 };
 use tendermint::{abci::Event as TendermintEvent, block::Header};
 use tendermint_proto::{
-    abci::{Event, EventAttribute},
+    abci::{CommitInfo, Event, EventAttribute},
     crypto::ProofOp,
 };
 use tracing::debug;
@@ -206,6 +208,29 @@ where
     }
 }
 
+/// Type URLs of the IBC core (ICS-02/03/04) and ICS-20 transfer messages
+pub const MSG_TYPE_URLS: [&str; 19] = [
+    "/ibc.core.client.v1.MsgCreateClient",
+    "/ibc.core.client.v1.MsgUpdateClient",
+    "/ibc.core.client.v1.MsgUpgradeClient",
+    "/ibc.core.client.v1.MsgSubmitMisbehaviour",
+    "/ibc.core.connection.v1.MsgConnectionOpenInit",
+    "/ibc.core.connection.v1.MsgConnectionOpenTry",
+    "/ibc.core.connection.v1.MsgConnectionOpenAck",
+    "/ibc.core.connection.v1.MsgConnectionOpenConfirm",
+    "/ibc.core.channel.v1.MsgChannelOpenInit",
+    "/ibc.core.channel.v1.MsgChannelOpenTry",
+    "/ibc.core.channel.v1.MsgChannelOpenAck",
+    "/ibc.core.channel.v1.MsgChannelOpenConfirm",
+    "/ibc.core.channel.v1.MsgChannelCloseInit",
+    "/ibc.core.channel.v1.MsgChannelCloseConfirm",
+    "/ibc.core.channel.v1.MsgRecvPacket",
+    "/ibc.core.channel.v1.MsgAcknowledgement",
+    "/ibc.core.channel.v1.MsgTimeout",
+    "/ibc.core.channel.v1.MsgTimeoutOnClose",
+    "/ibc.applications.transfer.v1.MsgTransfer",
+];
+
 impl<S> Module for Ibc<S>
 where
     S: 'static + ProvableStore + Debug,
@@ -213,17 +238,20 @@ where
 {
     type Store = S;
 
-    fn deliver(&mut self, message: Any, _signer: &AccountId) -> Result<Vec<Event>> {
-        if let Ok(msg) = MsgEnvelope::try_from(message.clone()) {
+    fn msg_type_urls(&self) -> Vec<&'static str> {
+        MSG_TYPE_URLS.to_vec()
+    }
+
+    // note: ibc-rs' handlers don't return the messages' responses, so none are reported
+    fn deliver(&mut self, message: Any, _signer: &AccountId) -> Result<DeliverResult> {
+        let events: Vec<Event> = if let Ok(msg) = MsgEnvelope::try_from(message.clone()) {
             debug!("Dispatching message: {:?}", msg);
 
             dispatch(self, msg)?;
-            let events = self
-                .events
+            self.events
                 .drain(..)
                 .map(|ev| TmEvent(ev.try_into().unwrap()).into())
-                .collect();
-            Ok(events)
+                .collect()
         } else if let Ok(transfer_msg) = MsgTransfer::try_from(message) {
             debug!("Dispatching message: {:?}", transfer_msg);
 
@@ -235,15 +263,25 @@ where
             send_transfer(transfer_module, transfer_msg)
                 .map_err(|e| Error::Unknown(e.to_string()))?;
 
-            Ok(transfer_module
+            transfer_module
                 .events
                 .clone()
                 .into_iter()
                 .map(|ev| TmEvent(ev.try_into().unwrap()).into())
-                .collect())
+                .collect()
         } else {
-            Err(Error::NotHandled.into())
-        }
+            return Err(Error::NotHandled.into());
+        };
+
+        Ok(DeliverResult {
+            events,
+            msg_response: None,
+        })
+    }
+
+    fn validate_genesis(&self, genesis: &serde_json::Value) -> Result<()> {
+        GenesisState::decode(genesis)?.validate()?;
+        Ok(())
     }
 
     fn query(
@@ -289,7 +327,7 @@ where
         Ok(QueryResult { data, proof })
     }
 
-    fn begin_block(&mut self, header: &Header) -> Vec<Event> {
+    fn begin_block(&mut self, header: &Header, _last_commit_info: &CommitInfo) -> Vec<Event> {
         let consensus_state = TmConsensusState::new(
             CommitmentRoot::from_bytes(header.app_hash.as_ref()),
             header.time,
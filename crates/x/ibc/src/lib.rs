@@ -1,4 +1,5 @@
 pub mod error;
+pub mod genesis;
 pub mod impls;
 pub mod router;
 pub mod service;
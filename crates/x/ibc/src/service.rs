@@ -92,7 +92,7 @@ impl<S: ProvableStore + 'static> ClientQuery for IbcClientService<S> {
         &self,
         _request: Request<QueryClientStateRequest>,
     ) -> Result<Response<QueryClientStateResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("ClientState is not implemented"))
     }
 
     async fn client_states(
@@ -138,7 +138,7 @@ impl<S: ProvableStore + 'static> ClientQuery for IbcClientService<S> {
         &self,
         _request: Request<QueryConsensusStateRequest>,
     ) -> Result<Response<QueryConsensusStateResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("ConsensusState is not implemented"))
     }
 
     async fn consensus_states(
@@ -180,35 +180,41 @@ impl<S: ProvableStore + 'static> ClientQuery for IbcClientService<S> {
         &self,
         _request: Request<QueryConsensusStateHeightsRequest>,
     ) -> Result<Response<QueryConsensusStateHeightsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "ConsensusStateHeights is not implemented",
+        ))
     }
 
     async fn client_status(
         &self,
         _request: Request<QueryClientStatusRequest>,
     ) -> Result<Response<QueryClientStatusResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("ClientStatus is not implemented"))
     }
 
     async fn client_params(
         &self,
         _request: Request<QueryClientParamsRequest>,
     ) -> Result<Response<QueryClientParamsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("ClientParams is not implemented"))
     }
 
     async fn upgraded_client_state(
         &self,
         _request: Request<QueryUpgradedClientStateRequest>,
     ) -> Result<Response<QueryUpgradedClientStateResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "UpgradedClientState is not implemented",
+        ))
     }
 
     async fn upgraded_consensus_state(
         &self,
         _request: Request<QueryUpgradedConsensusStateRequest>,
     ) -> Result<Response<QueryUpgradedConsensusStateResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "UpgradedConsensusState is not implemented",
+        ))
     }
 }
 
@@ -309,21 +315,25 @@ impl<S: ProvableStore + 'static> ConnectionQuery for IbcConnectionService<S> {
         &self,
         _request: Request<QueryConnectionClientStateRequest>,
     ) -> Result<Response<QueryConnectionClientStateResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented(
+            "ConnectionClientState is not implemented",
+        ))
     }
 
     async fn connection_consensus_state(
         &self,
         _request: Request<QueryConnectionConsensusStateRequest>,
     ) -> Result<Response<QueryConnectionConsensusStateResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented(
+            "ConnectionConsensusState is not implemented",
+        ))
     }
 
     async fn connection_params(
         &self,
         _request: Request<QueryConnectionParamsRequest>,
     ) -> Result<Response<QueryConnectionParamsResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented("ConnectionParams is not implemented"))
     }
 }
 
@@ -445,7 +455,9 @@ impl<S: ProvableStore + 'static> ChannelQuery for IbcChannelService<S> {
         &self,
         _request: Request<QueryChannelClientStateRequest>,
     ) -> Result<Response<QueryChannelClientStateResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented(
+            "ChannelClientState is not implemented",
+        ))
     }
     /// ChannelConsensusState queries for the consensus state for the channel
     /// associated with the provided channel identifiers.
@@ -453,14 +465,16 @@ impl<S: ProvableStore + 'static> ChannelQuery for IbcChannelService<S> {
         &self,
         _request: Request<QueryChannelConsensusStateRequest>,
     ) -> Result<Response<QueryChannelConsensusStateResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented(
+            "ChannelConsensusState is not implemented",
+        ))
     }
     /// PacketCommitment queries a stored packet commitment hash.
     async fn packet_commitment(
         &self,
         _request: Request<QueryPacketCommitmentRequest>,
     ) -> Result<Response<QueryPacketCommitmentResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented("PacketCommitment is not implemented"))
     }
     /// PacketCommitments returns all the packet commitments hashes associated
     /// with a channel.
@@ -528,7 +542,7 @@ impl<S: ProvableStore + 'static> ChannelQuery for IbcChannelService<S> {
         &self,
         _request: Request<QueryPacketReceiptRequest>,
     ) -> Result<Response<QueryPacketReceiptResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented("PacketReceipt is not implemented"))
     }
 
     /// PacketAcknowledgement queries a stored packet acknowledgement hash.
@@ -536,7 +550,9 @@ impl<S: ProvableStore + 'static> ChannelQuery for IbcChannelService<S> {
         &self,
         _request: Request<QueryPacketAcknowledgementRequest>,
     ) -> Result<Response<QueryPacketAcknowledgementResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented(
+            "PacketAcknowledgement is not implemented",
+        ))
     }
 
     /// PacketAcknowledgements returns all the packet acknowledgements associated
@@ -670,6 +686,8 @@ impl<S: ProvableStore + 'static> ChannelQuery for IbcChannelService<S> {
         &self,
         _request: Request<QueryNextSequenceReceiveRequest>,
     ) -> Result<Response<QueryNextSequenceReceiveResponse>, Status> {
-        todo!()
+        Err(Status::unimplemented(
+            "NextSequenceReceive is not implemented",
+        ))
     }
 }
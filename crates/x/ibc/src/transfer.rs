@@ -1,19 +1,18 @@
 use core::fmt::Debug;
+use core::str::FromStr;
 use cosmos_helper::Height;
 use cosmos_store::{
     SharedStore, Store, {BinStore, JsonStore, ProtobufStore, TypedStore},
 };
-use cosmos_x_auth::account::ACCOUNT_PREFIX;
+use cosmos_types::address::hash::module;
+use cosmos_x_auth::account::{account_prefix, module_address};
 use cosmos_x_bank::context::BankKeeper;
 use cosmos_x_bank_type::{Coin, Denom};
 use cosmrs::AccountId;
 use ibc::{applications::transfer::VERSION, core::ics24_host::path::SeqSendPath};
 use ibc::{
     applications::transfer::{
-        context::{
-            cosmos_adr028_escrow_address, TokenTransferExecutionContext,
-            TokenTransferValidationContext,
-        },
+        context::{TokenTransferExecutionContext, TokenTransferValidationContext},
         error::TokenTransferError,
         PrefixedCoin,
     },
@@ -66,6 +65,10 @@ use ibc::applications::transfer::context::{
     on_timeout_packet_validate,
 };
 
+/// Name of the module account of the transfer module, which needs the `minter` and `burner`
+/// permissions.
+pub const TRANSFER_MODULE_ACCOUNT: &str = "transfer";
+
 #[derive(Clone, Debug)]
 pub struct IbcTransferModule<S, BK>
 where
@@ -367,12 +370,10 @@ where
             .to_string()
             .parse()
             .map_err(|_| TokenTransferError::ParseAccountFailure)?;
-        let coins = vec![Coin {
-            denom: Denom(amt.denom.to_string()),
-            amount: amt.amount.into(),
-        }];
-        self.bank_keeper.send_coins(from, to, coins).unwrap(); // Fixme(hu55a1n1)
-        Ok(())
+        let coins = to_coins(amt);
+        self.bank_keeper
+            .send_coins(from, to, coins)
+            .map_err(bank_error)
     }
 
     fn mint_coins_execute(
@@ -384,12 +385,14 @@ where
             .to_string()
             .parse()
             .map_err(|_| TokenTransferError::ParseAccountFailure)?;
-        let coins = vec![Coin {
-            denom: Denom(amt.denom.to_string()),
-            amount: amt.amount.into(),
-        }];
-        self.bank_keeper.mint_coins(account, coins).unwrap(); // Fixme(hu55a1n1)
-        Ok(())
+        let coins = to_coins(amt);
+        // same as ibc-go - vouchers are minted by the transfer module account, then sent
+        self.bank_keeper
+            .mint_coins(transfer_module_account()?, coins.clone())
+            .map_err(bank_error)?;
+        self.bank_keeper
+            .send_coins(transfer_module_account()?, account, coins)
+            .map_err(bank_error)
     }
 
     fn burn_coins_execute(
@@ -401,15 +404,47 @@ where
             .to_string()
             .parse()
             .map_err(|_| TokenTransferError::ParseAccountFailure)?;
-        let coins = vec![Coin {
-            denom: Denom(amt.denom.to_string()),
-            amount: amt.amount.into(),
-        }];
-        self.bank_keeper.burn_coins(account, coins).unwrap(); // Fixme(hu55a1n1)
-        Ok(())
+        let coins = to_coins(amt);
+        // same as ibc-go - vouchers are sent to the transfer module account, then burnt
+        self.bank_keeper
+            .send_coins(account, transfer_module_account()?, coins.clone())
+            .map_err(bank_error)?;
+        self.bank_keeper
+            .burn_coins(transfer_module_account()?, coins)
+            .map_err(bank_error)
     }
 }
 
+/// Returns the address of the transfer module account, which mints and burns the vouchers.
+fn transfer_module_account<A: FromStr>() -> Result<A, TokenTransferError> {
+    module_address(TRANSFER_MODULE_ACCOUNT)
+        .to_string()
+        .parse()
+        .map_err(|_| TokenTransferError::ParseAccountFailure)
+}
+
+/// Converts an error of the bank keeper, e.g. insufficient funds or a missing permission, to a
+/// `TokenTransferError`.
+fn bank_error(e: impl Debug) -> TokenTransferError {
+    TokenTransferError::ContextError(ContextError::ChannelError(ChannelError::AppModule {
+        description: format!("{e:?}"),
+    }))
+}
+
+/// Returns the coins of the amount, as handled by the bank keeper.
+fn to_coins(amt: &PrefixedCoin) -> Vec<Coin> {
+    vec![Coin {
+        denom: Denom(amt.denom.to_string()),
+        amount: amt.amount.into(),
+    }]
+}
+
+/// Returns the address of the escrow account of the channel, same as ibc-go's `GetEscrowAddress`,
+/// i.e. the (legacy) ADR-028 module address of `{version}\0{port_id}/{channel_id}`.
+fn escrow_address(port_id: &PortId, channel_id: &ChannelId) -> Vec<u8> {
+    module(&format!("{VERSION}\0{port_id}/{channel_id}"), &[])
+}
+
 impl<S, BK> TokenTransferValidationContext for IbcTransferModule<S, BK>
 where
     S: Store + Send + Sync,
@@ -426,26 +461,25 @@ where
         port_id: &PortId,
         channel_id: &ChannelId,
     ) -> Result<Self::AccountId, TokenTransferError> {
-        let account_id = AccountId::new(
-            ACCOUNT_PREFIX,
-            &cosmos_adr028_escrow_address(port_id, channel_id),
-        )
-        .map_err(|_| TokenTransferError::ParseAccountFailure)?;
+        let account_id = AccountId::new(&account_prefix(), &escrow_address(port_id, channel_id))
+            .map_err(|_| TokenTransferError::ParseAccountFailure)?;
 
         Ok(account_id.to_string().into())
     }
 
     fn send_coins_validate(
         &self,
-        _from_account: &Self::AccountId,
+        from_account: &Self::AccountId,
         _to_account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
-        // Architectures that don't use `dispatch()` and care about the
-        // distinction between `validate()` and `execute()` would want to check
-        // that we can also send the coins between the 2 accounts.
-        // However we use `dispatch()` and simply do all our checks in the `execute()` phase.
-        Ok(())
+        let from = from_account
+            .to_string()
+            .parse()
+            .map_err(|_| TokenTransferError::ParseAccountFailure)?;
+        self.bank_keeper
+            .validate_send_coins(&from, &to_coins(coin))
+            .map_err(bank_error)
     }
 
     fn mint_coins_validate(
@@ -453,23 +487,28 @@ where
         _account: &Self::AccountId,
         _coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
-        // Architectures that don't use `dispatch()` and care about the
-        // distinction between `validate()` and `execute()` would want to check
-        // that we can also send the coins between the 2 accounts.
-        // However we use `dispatch()` and simply do all our checks in the `execute()` phase.
-        Ok(())
+        // the minted vouchers are sent to the account by the transfer module account
+        self.bank_keeper
+            .validate_mint_coins(&transfer_module_account()?)
+            .map_err(bank_error)
     }
 
     fn burn_coins_validate(
         &self,
-        _account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        account: &Self::AccountId,
+        coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
-        // Architectures that don't use `dispatch()` and care about the
-        // distinction between `validate()` and `execute()` would want to check
-        // that we can also send the coins between the 2 accounts.
-        // However we use `dispatch()` and simply do all our checks in the `execute()` phase.
-        Ok(())
+        // the vouchers are sent to the transfer module account, which burns them
+        let account = account
+            .to_string()
+            .parse()
+            .map_err(|_| TokenTransferError::ParseAccountFailure)?;
+        self.bank_keeper
+            .validate_send_coins(&account, &to_coins(coin))
+            .map_err(bank_error)?;
+        self.bank_keeper
+            .validate_burn_coins(&transfer_module_account()?)
+            .map_err(bank_error)
     }
 
     fn can_send_coins(&self) -> Result<(), TokenTransferError> {
@@ -1,7 +1,276 @@
+use core::fmt::{Display, Formatter};
+use std::collections::HashSet;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("not handled")]
     NotHandled,
+    #[error("error code {code} is reserved and can't be registered in codespace `{codespace}`")]
+    ReservedErrorCode { codespace: String, code: u32 },
+    #[error("error with code {code} is already registered in codespace `{codespace}`")]
+    DuplicateErrorCode { codespace: String, code: u32 },
+}
+
+/// Code returned to ABCI clients on success.
+pub const SUCCESS_ABCI_CODE: u32 = 0;
+
+/// Codespace of errors that weren't registered.
+pub const UNDEFINED_CODESPACE: &str = "undefined";
+
+/// Codespace of the errors registered by the Go SDK's `types/errors` package.
+pub const ROOT_CODESPACE: &str = "sdk";
+
+/// An error registered under a codespace, i.e. the equivalent of the errors created with the Go
+/// SDK's `errorsmod.Register`. The (codespace, code) pair is what ABCI clients use to identify
+/// the error, so it must never change once released.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RegisteredError {
+    codespace: &'static str,
+    code: u32,
+    description: &'static str,
+}
+
+impl RegisteredError {
+    /// Registers an error under the specified codespace.
+    /// Code `0` is reserved for success and code `1` for internal errors, so they cannot be used by
+    /// modules - see `validate_registered_errors`.
+    pub const fn new(codespace: &'static str, code: u32, description: &'static str) -> Self {
+        Self {
+            codespace,
+            code,
+            description,
+        }
+    }
+
+    pub fn codespace(&self) -> &'static str {
+        self.codespace
+    }
+
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+impl Display for RegisteredError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+/// Checks the registered errors the same way as the Go SDK's `errorsmod.Register`, i.e. that none
+/// of them uses a reserved code (except `ERR_INTERNAL` itself) and that no (codespace, code) pair
+/// is registered twice.
+pub fn validate_registered_errors<'a>(
+    errors: impl IntoIterator<Item = &'a RegisteredError>,
+) -> Result<(), Error> {
+    let mut registered = HashSet::new();
+    for error in errors {
+        if error.code == SUCCESS_ABCI_CODE || (error.code == 1 && *error != ERR_INTERNAL) {
+            return Err(Error::ReservedErrorCode {
+                codespace: error.codespace.to_string(),
+                code: error.code,
+            });
+        }
+        if !registered.insert((error.codespace, error.code)) {
+            return Err(Error::DuplicateErrorCode {
+                codespace: error.codespace.to_string(),
+                code: error.code,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Errors that are reported to ABCI clients as a registered (codespace, code) pair.
+/// Every module's `Error` type is expected to implement this.
+pub trait AbciError: std::error::Error {
+    /// Returns the registered error this error is reported as.
+    fn registered_error(&self) -> RegisteredError;
+}
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::NotHandled => ERR_UNKNOWN_REQUEST,
+            Error::ReservedErrorCode { .. } | Error::DuplicateErrorCode { .. } => ERR_LOGIC,
+        }
+    }
+}
+
+/// Error returned for errors that weren't registered (Go SDK's `errInternal`).
+pub const ERR_INTERNAL: RegisteredError = RegisteredError::new(UNDEFINED_CODESPACE, 1, "internal");
+
+// Errors of the Go SDK's `types/errors` package, registered under the root codespace.
+pub const ERR_TX_DECODE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 2, "tx parse error");
+pub const ERR_INVALID_SEQUENCE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 3, "invalid sequence");
+pub const ERR_UNAUTHORIZED: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 4, "unauthorized");
+pub const ERR_INSUFFICIENT_FUNDS: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 5, "insufficient funds");
+pub const ERR_UNKNOWN_REQUEST: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 6, "unknown request");
+pub const ERR_INVALID_ADDRESS: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 7, "invalid address");
+pub const ERR_INVALID_PUBKEY: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 8, "invalid pubkey");
+pub const ERR_UNKNOWN_ADDRESS: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 9, "unknown address");
+pub const ERR_INVALID_COINS: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 10, "invalid coins");
+pub const ERR_OUT_OF_GAS: RegisteredError = RegisteredError::new(ROOT_CODESPACE, 11, "out of gas");
+pub const ERR_MEMO_TOO_LARGE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 12, "memo too large");
+pub const ERR_INSUFFICIENT_FEE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 13, "insufficient fee");
+pub const ERR_TOO_MANY_SIGNATURES: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 14, "maximum number of signatures exceeded");
+pub const ERR_NO_SIGNATURES: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 15, "no signatures supplied");
+pub const ERR_JSON_MARSHAL: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 16, "failed to marshal JSON bytes");
+pub const ERR_JSON_UNMARSHAL: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 17, "failed to unmarshal JSON bytes");
+pub const ERR_INVALID_REQUEST: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 18, "invalid request");
+pub const ERR_TX_IN_MEMPOOL_CACHE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 19, "tx already in mempool");
+pub const ERR_MEMPOOL_IS_FULL: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 20, "mempool is full");
+pub const ERR_TX_TOO_LARGE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 21, "tx too large");
+pub const ERR_KEY_NOT_FOUND: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 22, "key not found");
+pub const ERR_WRONG_PASSWORD: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 23, "invalid account password");
+pub const ERR_INVALID_SIGNER: RegisteredError = RegisteredError::new(
+    ROOT_CODESPACE,
+    24,
+    "tx intended signer does not match the given signer",
+);
+pub const ERR_INVALID_GAS_ADJUSTMENT: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 25, "invalid gas adjustment");
+pub const ERR_INVALID_HEIGHT: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 26, "invalid height");
+pub const ERR_INVALID_VERSION: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 27, "invalid version");
+pub const ERR_INVALID_CHAIN_ID: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 28, "invalid chain-id");
+pub const ERR_INVALID_TYPE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 29, "invalid type");
+pub const ERR_TX_TIMEOUT_HEIGHT: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 30, "tx timeout height");
+pub const ERR_UNKNOWN_EXTENSION_OPTIONS: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 31, "unknown extension options");
+pub const ERR_WRONG_SEQUENCE: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 32, "incorrect account sequence");
+pub const ERR_PACK_ANY: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 33, "failed packing protobuf message to Any");
+pub const ERR_UNPACK_ANY: RegisteredError = RegisteredError::new(
+    ROOT_CODESPACE,
+    34,
+    "failed unpacking protobuf message from Any",
+);
+pub const ERR_LOGIC: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 35, "internal logic error");
+pub const ERR_CONFLICT: RegisteredError = RegisteredError::new(ROOT_CODESPACE, 36, "conflict");
+pub const ERR_NOT_SUPPORTED: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 37, "feature not supported");
+pub const ERR_NOT_FOUND: RegisteredError = RegisteredError::new(ROOT_CODESPACE, 38, "not found");
+pub const ERR_IO: RegisteredError = RegisteredError::new(ROOT_CODESPACE, 39, "Internal IO error");
+pub const ERR_APP_CONFIG: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 40, "error in app.toml");
+pub const ERR_INVALID_GAS_LIMIT: RegisteredError =
+    RegisteredError::new(ROOT_CODESPACE, 41, "invalid gas limit");
+pub const ERR_PANIC: RegisteredError = RegisteredError::new(UNDEFINED_CODESPACE, 111222, "panic");
+
+/// All the errors registered by this crate.
+pub const REGISTERED_ERRORS: &[RegisteredError] = &[
+    ERR_INTERNAL,
+    ERR_TX_DECODE,
+    ERR_INVALID_SEQUENCE,
+    ERR_UNAUTHORIZED,
+    ERR_INSUFFICIENT_FUNDS,
+    ERR_UNKNOWN_REQUEST,
+    ERR_INVALID_ADDRESS,
+    ERR_INVALID_PUBKEY,
+    ERR_UNKNOWN_ADDRESS,
+    ERR_INVALID_COINS,
+    ERR_OUT_OF_GAS,
+    ERR_MEMO_TOO_LARGE,
+    ERR_INSUFFICIENT_FEE,
+    ERR_TOO_MANY_SIGNATURES,
+    ERR_NO_SIGNATURES,
+    ERR_JSON_MARSHAL,
+    ERR_JSON_UNMARSHAL,
+    ERR_INVALID_REQUEST,
+    ERR_TX_IN_MEMPOOL_CACHE,
+    ERR_MEMPOOL_IS_FULL,
+    ERR_TX_TOO_LARGE,
+    ERR_KEY_NOT_FOUND,
+    ERR_WRONG_PASSWORD,
+    ERR_INVALID_SIGNER,
+    ERR_INVALID_GAS_ADJUSTMENT,
+    ERR_INVALID_HEIGHT,
+    ERR_INVALID_VERSION,
+    ERR_INVALID_CHAIN_ID,
+    ERR_INVALID_TYPE,
+    ERR_TX_TIMEOUT_HEIGHT,
+    ERR_UNKNOWN_EXTENSION_OPTIONS,
+    ERR_WRONG_SEQUENCE,
+    ERR_PACK_ANY,
+    ERR_UNPACK_ANY,
+    ERR_LOGIC,
+    ERR_CONFLICT,
+    ERR_NOT_SUPPORTED,
+    ERR_NOT_FOUND,
+    ERR_IO,
+    ERR_APP_CONFIG,
+    ERR_INVALID_GAS_LIMIT,
+    ERR_PANIC,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_errors() {
+        validate_registered_errors(REGISTERED_ERRORS).unwrap();
+        assert!(REGISTERED_ERRORS
+            .iter()
+            .all(|e| [ROOT_CODESPACE, UNDEFINED_CODESPACE].contains(&e.codespace())));
+    }
+
+    #[test]
+    fn test_invalid_registered_errors() {
+        const ERR_FOO: RegisteredError = RegisteredError::new("foo", 2, "foo");
+
+        for code in [0, 1] {
+            let error = RegisteredError::new("foo", code, "reserved");
+            assert!(matches!(
+                validate_registered_errors(&[ERR_FOO, error]),
+                Err(Error::ReservedErrorCode { code: c, .. }) if c == code
+            ));
+        }
+
+        // codes only have to be unique within their codespace
+        let other = RegisteredError::new("bar", 2, "bar");
+        validate_registered_errors(&[ERR_FOO, other]).unwrap();
+        let duplicate = RegisteredError::new("foo", 2, "other foo");
+        assert!(matches!(
+            validate_registered_errors(&[ERR_FOO, other, duplicate]),
+            Err(Error::DuplicateErrorCode { code: 2, .. })
+        ));
+        assert!(matches!(
+            validate_registered_errors(&[ERR_INTERNAL, ERR_INTERNAL]),
+            Err(Error::DuplicateErrorCode { code: 1, .. })
+        ));
+    }
 }
@@ -0,0 +1,203 @@
+//! Helpers for the modules' sections of the genesis `app_state`, laid out as in the Go SDK, i.e.
+//! `app_state.{module}` holds the proto-JSON encoded `GenesisState` of each module.
+
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Number of decimal places of the Go SDK's `LegacyDec`.
+pub const DEC_PRECISION: usize = 18;
+
+/// `1` as a decimal parsed by [`parse_dec`].
+pub const DEC_ONE: u128 = 10u128.pow(DEC_PRECISION as u32);
+
+/// Decodes a module's section of the genesis `app_state`, or returns the module's default
+/// genesis state if the section is missing (`null`).
+pub fn decode_genesis<T: DeserializeOwned + Default>(genesis: &Value) -> Result<T, String> {
+    if genesis.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(genesis.clone()).map_err(|e| e.to_string())
+}
+
+/// Parses a proto-JSON encoded (non-negative) `LegacyDec`, e.g. `0.334000000000000000`, as an
+/// integer scaled by `10^18`, i.e. `1` is parsed as [`DEC_ONE`].
+pub fn parse_dec(value: &str) -> Result<u128, String> {
+    let invalid = || format!("invalid decimal: {value}");
+    let (int, frac) = match value.split_once('.') {
+        Some((_, "")) => return Err(invalid()),
+        Some((int, frac)) => (int, frac),
+        None => (value, ""),
+    };
+    if int.is_empty()
+        || frac.len() > DEC_PRECISION
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let int: u128 = int.parse().map_err(|_| invalid())?;
+    let frac: u128 = format!("{frac:0<DEC_PRECISION$}")
+        .parse()
+        .map_err(|_| invalid())?;
+    int.checked_mul(DEC_ONE)
+        .and_then(|int| int.checked_add(frac))
+        .ok_or_else(invalid)
+}
+
+/// Parses a proto-JSON encoded (non-negative) `Duration`, e.g. `172800s` or `1.5s`.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration: {value}");
+    let secs = value.strip_suffix('s').ok_or_else(invalid)?;
+    let (secs, nanos) = secs.split_once('.').unwrap_or((secs, ""));
+    if secs.is_empty()
+        || nanos.len() > 9
+        || !secs
+            .chars()
+            .chain(nanos.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let secs = secs.parse().map_err(|_| invalid())?;
+    let nanos = format!("{nanos:0<9}").parse().map_err(|_| invalid())?;
+    Ok(Duration::new(secs, nanos))
+}
+
+/// Checks the denomination against the Go SDK's default regex, i.e.
+/// `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`.
+pub fn validate_denom(denom: &str) -> Result<(), String> {
+    let valid = (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | ':' | '.' | '_' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid denom: {denom}"))
+    }
+}
+
+/// Checks that a list of the genesis state that the module can't import is empty, e.g. the
+/// validators of the staking module, which are set by CometBFT's genesis instead.
+pub fn ensure_empty<T>(name: &str, entries: &[T]) -> Result<(), String> {
+    if entries.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("importing {name} isn't supported"))
+    }
+}
+
+/// (De)serializes a `u64` as a string, as proto-JSON does (numbers are accepted as well).
+pub mod u64_string {
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::Serializer;
+    use std::fmt::Formatter;
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        struct U64Visitor;
+
+        impl<'de> Visitor<'de> for U64Visitor {
+            type Value = u64;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "an unsigned integer or a string holding one")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+                Ok(value)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(U64Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dec() {
+        assert_eq!(parse_dec("1").unwrap(), DEC_ONE);
+        assert_eq!(parse_dec("0.5").unwrap(), DEC_ONE / 2);
+        assert_eq!(
+            parse_dec("0.334000000000000000").unwrap(),
+            334_000_000_000_000_000
+        );
+        assert_eq!(
+            parse_dec("12.000000000000000001").unwrap(),
+            12 * DEC_ONE + 1
+        );
+        for invalid in [
+            "",
+            ".5",
+            "1.",
+            "-0.5",
+            "0.5e1",
+            "1,5",
+            "0.0000000000000000001",
+        ] {
+            assert!(parse_dec(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            parse_duration("172800s").unwrap(),
+            Duration::from_secs(172_800)
+        );
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(
+            parse_duration("0.000000001s").unwrap(),
+            Duration::from_nanos(1)
+        );
+        for invalid in ["", "s", "10", "-1s", "1.0000000001s", "1ms"] {
+            assert!(parse_duration(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_validate_denom() {
+        for valid in ["stake", "ibc/27394FB092D2ECCD56123C74F36E4C1F", "a:b.c_d-e"] {
+            validate_denom(valid).unwrap();
+        }
+        for invalid in ["", "ab", "1stake", "st@ke", &"a".repeat(129)] {
+            assert!(validate_denom(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_decode_genesis() {
+        assert!(decode_genesis::<Vec<u64>>(&Value::Null).unwrap().is_empty());
+        assert_eq!(
+            decode_genesis::<Vec<u64>>(&serde_json::json!([1, 2])).unwrap(),
+            [1, 2]
+        );
+        assert!(decode_genesis::<Vec<u64>>(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_u64_string() {
+        for value in [serde_json::json!("7"), serde_json::json!(7)] {
+            assert_eq!(u64_string::deserialize(value).unwrap(), 7);
+        }
+        assert!(u64_string::deserialize(serde_json::json!("-1")).is_err());
+        assert_eq!(
+            u64_string::serialize(&7, serde_json::value::Serializer).unwrap(),
+            "7"
+        );
+    }
+}
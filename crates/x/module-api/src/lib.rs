@@ -1,5 +1,7 @@
 pub mod error;
+pub mod genesis;
 pub mod module;
+pub mod pagination;
 pub mod types;
 
 pub trait CosmosSdkContext {
@@ -1,11 +1,12 @@
 use crate::error::Error;
+use crate::types::{DeliverResult, EndBlockResult};
 use anyhow::Result;
 use cosmos_helper::{Height, Identifier as StoreIdentifier, Path, QueryResult};
 use cosmos_store::impls::SharedStore;
-use cosmrs::AccountId;
+use cosmrs::{AccountId, Tx};
 use ibc_proto::google::protobuf::Any;
 use tendermint::block::Header;
-use tendermint_proto::abci::Event;
+use tendermint_proto::abci::{CommitInfo, Event};
 
 pub trait Module: Send + Sync {
     /// The module's store type.
@@ -19,21 +20,58 @@ pub trait Module: Send + Sync {
         Ok(())
     }
 
-    /// Execute specified `Message`, modify state accordingly and return resulting `Events`
+    /// Type URLs of the messages handled by this module's `deliver`.
+    /// These are registered with the app's message router when the module is added, a type
+    /// URL can only be handled by a single module.
+    fn msg_type_urls(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Called for every transaction before its messages are delivered, similar to the Go SDK's
+    /// `AnteHandler`. `simulate` is set when the transaction is only simulated (e.g. to estimate
+    /// its gas), in which case its signatures aren't expected to be valid.
+    /// *NOTE* - Implementations MUST be deterministic!
+    ///
+    /// ## Return
+    /// * Errors iff the transaction must be rejected
+    /// * Resulting events on success
+    fn ante_handle(
+        &mut self,
+        _tx: &Tx,
+        _signer: &AccountId,
+        _simulate: bool,
+    ) -> Result<Vec<Event>> {
+        Ok(vec![])
+    }
+
+    /// Execute specified `Message`, modify state accordingly and return resulting `Events` along
+    /// with the message's response
     /// Similar to [ABCI DeliverTx method](https://docs.tendermint.com/master/spec/abci/abci.html#delivertx)
+    /// Only messages whose type URL is returned by `msg_type_urls` are routed to this module.
     /// *NOTE* - Implementations MUST be deterministic!
     ///
     /// ## Return
-    /// * `Error::NotHandled` if message isn't known to OR hasn't been consumed (but possibly intercepted) by this module
+    /// * `Error::NotHandled` if message isn't known to this module
     /// * Other errors iff message was meant to be consumed by module but resulted in an error
-    /// * Resulting events on success
-    fn deliver(&mut self, _message: Any, _signer: &AccountId) -> Result<Vec<Event>> {
+    /// * Resulting events and message response on success
+    fn deliver(&mut self, _message: Any, _signer: &AccountId) -> Result<DeliverResult> {
         Err(Error::NotHandled.into())
     }
 
     /// Similar to [ABCI InitChain method](https://docs.tendermint.com/master/spec/abci/abci.html#initchain)
+    /// Receives the module's section of the genesis `app_state` (i.e. `app_state.{module}`, or
+    /// `null` if it's missing), which was previously checked with `validate_genesis`.
     /// Just as with `InitChain`, implementations are encouraged to panic on error
-    fn init(&mut self, _app_state: serde_json::Value) {}
+    fn init(&mut self, _genesis: serde_json::Value) {}
+
+    /// Checks the module's section of the genesis `app_state` (`null` if it's missing), similar
+    /// to the Go SDK's `ValidateGenesis`.
+    ///
+    /// ## Return
+    /// * Errors iff the genesis state can't be decoded or is invalid
+    fn validate_genesis(&self, _genesis: &serde_json::Value) -> Result<()> {
+        Ok(())
+    }
 
     /// Similar to [ABCI Query method](https://docs.tendermint.com/master/spec/abci/abci.html#query)
     ///
@@ -52,14 +90,24 @@ pub trait Module: Send + Sync {
     }
 
     /// Similar to [ABCI BeginBlock method](https://docs.tendermint.com/master/spec/abci/abci.html#beginblock)
+    /// Receives the block's header and the votes of the validators on the previous block.
     /// *NOTE* - Implementations MUST be deterministic!
     ///
     /// ## Return
     /// * Resulting events if any
-    fn begin_block(&mut self, _header: &Header) -> Vec<Event> {
+    fn begin_block(&mut self, _header: &Header, _last_commit_info: &CommitInfo) -> Vec<Event> {
         vec![]
     }
 
+    /// Similar to [ABCI EndBlock method](https://docs.tendermint.com/master/spec/abci/abci.html#endblock)
+    /// *NOTE* - Implementations MUST be deterministic!
+    ///
+    /// ## Return
+    /// * Resulting events, validator updates and consensus param updates if any
+    fn end_block(&mut self, _height: u64) -> EndBlockResult {
+        EndBlockResult::default()
+    }
+
     /// Return a mutable reference to the module's store
     fn store_mut(&mut self) -> &mut SharedStore<Self::Store>;
 
@@ -0,0 +1,248 @@
+//! Pagination of gRPC query results, following the Go SDK's `types/query` package.
+
+use ibc_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+
+/// Number of items per page when the request doesn't set a limit.
+pub const DEFAULT_LIMIT: u64 = 100;
+
+/// Returns the requested page of the items along with the page response, like Go's
+/// `query.Paginate`: the items are sorted by their (store) key, and the page either starts at
+/// the requested key or after the requested number of items.
+///
+/// ## Return
+/// * Errors iff both a key and an offset are requested
+pub fn paginate<T>(
+    mut items: Vec<(Vec<u8>, T)>,
+    pagination: Option<PageRequest>,
+) -> Result<(Vec<T>, PageResponse), String> {
+    let request = pagination.unwrap_or_default();
+    if request.offset > 0 && !request.key.is_empty() {
+        return Err("invalid request, either offset or key is expected, got both".to_owned());
+    }
+    let (limit, count_total) = match request.limit {
+        0 => (DEFAULT_LIMIT, true),
+        limit => (limit, request.count_total),
+    };
+
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if request.reverse {
+        items.reverse();
+    }
+    let total = items.len() as u64;
+
+    let start = if request.key.is_empty() {
+        request.offset.min(total) as usize
+    } else {
+        items
+            .iter()
+            .position(|(key, _)| match request.reverse {
+                false => key >= &request.key,
+                true => key <= &request.key,
+            })
+            .unwrap_or(items.len())
+    };
+
+    let mut items = items.into_iter().skip(start);
+    let page = items
+        .by_ref()
+        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        .map(|(_, item)| item)
+        .collect();
+    let next_key = items.next().map(|(key, _)| key).unwrap_or_default();
+
+    // the total is only counted for offset-based pagination
+    let total = if count_total && request.key.is_empty() {
+        total
+    } else {
+        0
+    };
+    Ok((page, PageResponse { next_key, total }))
+}
+
+/// Returns the offset and limit of the requested page, for results that can only be paged
+/// through by offset (e.g. the transactions of a block).
+///
+/// ## Return
+/// * Errors iff reverse pagination is requested
+pub fn page_bounds(pagination: Option<PageRequest>) -> Result<(u64, u64), String> {
+    let request = pagination.unwrap_or_default();
+    if request.reverse {
+        return Err("reverse pagination is not supported".to_owned());
+    }
+    let limit = match request.limit {
+        0 => DEFAULT_LIMIT,
+        limit => limit,
+    };
+    Ok((request.offset, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of items, i.e. the number of balances in the Go pagination tests.
+    const NUM_ITEMS: u64 = 235;
+
+    fn key(i: u64) -> Vec<u8> {
+        format!("denom{i:03}").into_bytes()
+    }
+
+    /// Items that aren't sorted by key, as listed from a store that isn't ordered.
+    fn items() -> Vec<(Vec<u8>, u64)> {
+        (0..NUM_ITEMS).rev().map(|i| (key(i), i)).collect()
+    }
+
+    fn page(request: PageRequest) -> (Vec<u64>, PageResponse) {
+        paginate(items(), Some(request)).unwrap()
+    }
+
+    #[test]
+    fn test_default_limit() {
+        let (items, response) = paginate(items(), None).unwrap();
+        assert_eq!(items, (0..DEFAULT_LIMIT).collect::<Vec<_>>());
+        assert_eq!(response.next_key, key(DEFAULT_LIMIT));
+        // the total is always counted with the default limit
+        assert_eq!(response.total, NUM_ITEMS);
+    }
+
+    #[test]
+    fn test_offset_pagination() {
+        let (items, response) = page(PageRequest {
+            limit: 2,
+            count_total: true,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [0, 1]);
+        assert_eq!(response.next_key, key(2));
+        assert_eq!(response.total, NUM_ITEMS);
+
+        let (items, response) = page(PageRequest {
+            offset: 100,
+            limit: 3,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [100, 101, 102]);
+        assert_eq!(response.next_key, key(103));
+        assert_eq!(response.total, 0);
+
+        // the last page has no next key
+        let (items, response) = page(PageRequest {
+            offset: 230,
+            limit: 10,
+            count_total: true,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [230, 231, 232, 233, 234]);
+        assert!(response.next_key.is_empty());
+        assert_eq!(response.total, NUM_ITEMS);
+
+        let (items, response) = page(PageRequest {
+            offset: NUM_ITEMS + 1,
+            limit: 10,
+            ..PageRequest::default()
+        });
+        assert!(items.is_empty());
+        assert!(response.next_key.is_empty());
+    }
+
+    #[test]
+    fn test_key_pagination() {
+        // each page starts at the next key of the previous one
+        let mut request = PageRequest {
+            limit: 100,
+            count_total: true,
+            ..PageRequest::default()
+        };
+        let mut all = vec![];
+        loop {
+            let (items, response) = page(request.clone());
+            all.extend(items);
+            if response.next_key.is_empty() {
+                break;
+            }
+            request = PageRequest {
+                key: response.next_key,
+                limit: 100,
+                // the total is only counted for offset-based pagination
+                count_total: true,
+                ..PageRequest::default()
+            };
+            assert_eq!(page(request.clone()).1.total, 0);
+        }
+        assert_eq!(all, (0..NUM_ITEMS).collect::<Vec<_>>());
+
+        // a key that isn't stored starts the page at the next stored key
+        let (items, _) = page(PageRequest {
+            key: b"denom0995".to_vec(),
+            limit: 2,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [100, 101]);
+    }
+
+    #[test]
+    fn test_reverse_pagination() {
+        let (items, response) = page(PageRequest {
+            limit: 3,
+            count_total: true,
+            reverse: true,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [234, 233, 232]);
+        assert_eq!(response.next_key, key(231));
+        assert_eq!(response.total, NUM_ITEMS);
+
+        let (items, response) = page(PageRequest {
+            key: response.next_key,
+            limit: 3,
+            reverse: true,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [231, 230, 229]);
+        assert_eq!(response.next_key, key(228));
+
+        let (items, response) = page(PageRequest {
+            offset: 233,
+            limit: 3,
+            reverse: true,
+            ..PageRequest::default()
+        });
+        assert_eq!(items, [1, 0]);
+        assert!(response.next_key.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        let result = paginate(
+            items(),
+            Some(PageRequest {
+                key: key(1),
+                offset: 1,
+                ..PageRequest::default()
+            }),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            "invalid request, either offset or key is expected, got both"
+        );
+    }
+
+    #[test]
+    fn test_page_bounds() {
+        assert_eq!(page_bounds(None).unwrap(), (0, DEFAULT_LIMIT));
+        let request = |offset, limit, reverse| {
+            Some(PageRequest {
+                offset,
+                limit,
+                reverse,
+                ..PageRequest::default()
+            })
+        };
+        assert_eq!(
+            page_bounds(request(5, 0, false)).unwrap(),
+            (5, DEFAULT_LIMIT)
+        );
+        assert_eq!(page_bounds(request(5, 10, false)).unwrap(), (5, 10));
+        assert!(page_bounds(request(0, 10, true)).is_err());
+    }
+}
@@ -1,11 +1,40 @@
 use crate::module::Module;
 use cosmos_helper::Identifier;
-use cosmos_store::RevertibleStore;
+use cosmos_store::{GasStore, RevertibleStore};
+use ibc_proto::google::protobuf::Any;
+use tendermint_proto::abci::{Event, ValidatorUpdate};
+use tendermint_proto::types::ConsensusParams;
 
 pub type ModuleList<S> = Vec<IdentifiedModule<S>>;
-pub type ModuleStore<S> = RevertibleStore<S>;
+/// Store of a module, metered and revertible so that the changes of failed transactions can be
+/// discarded.
+pub type ModuleStore<S> = RevertibleStore<GasStore<S>>;
 
 pub struct IdentifiedModule<S> {
+    /// Prefix of the module's store.
     pub id: Identifier,
+    /// Name of the module, which keys its section of the genesis `app_state`.
+    pub name: String,
     pub module: Box<dyn Module<Store = ModuleStore<S>>>,
 }
+
+/// Result of a module's `deliver`, aggregated by the app into the transaction's `TxMsgData`
+#[derive(Clone, Debug, Default)]
+pub struct DeliverResult {
+    /// Resulting events if any
+    pub events: Vec<Event>,
+    /// The message's response packed into an `Any` (e.g. a `MsgSendResponse`), if the module
+    /// returns one
+    pub msg_response: Option<Any>,
+}
+
+/// Result of a module's `end_block`, aggregated by the app into `ResponseEndBlock`
+#[derive(Clone, Debug, Default)]
+pub struct EndBlockResult {
+    /// Resulting events if any
+    pub events: Vec<Event>,
+    /// Changes to the validator set, at most one module may return these per block
+    pub validator_updates: Vec<ValidatorUpdate>,
+    /// Changes to the consensus params, at most one module may return these per block
+    pub consensus_param_updates: Option<ConsensusParams>,
+}
@@ -0,0 +1,16 @@
+use cosmos_x_module_api::error::{AbciError, RegisteredError, ERR_INVALID_REQUEST};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid genesis state: {reason}")]
+    InvalidGenesis { reason: String },
+}
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::InvalidGenesis { .. } => ERR_INVALID_REQUEST,
+        }
+    }
+}
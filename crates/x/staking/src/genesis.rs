@@ -0,0 +1,176 @@
+use cosmos_x_module_api::genesis::{
+    decode_genesis, ensure_empty, parse_dec, parse_duration, validate_denom, DEC_ONE,
+};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Genesis state of the staking module, i.e. `app_state.staking`, as the Go SDK's
+/// `cosmos.staking.v1beta1.GenesisState`.
+///
+/// The validator set is taken from CometBFT's genesis instead, so the validators, delegations
+/// and their powers must be empty. The params are validated only.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GenesisState {
+    pub params: Params,
+    pub last_total_power: String,
+    pub last_validator_powers: Vec<Value>,
+    pub validators: Vec<Value>,
+    pub delegations: Vec<Value>,
+    pub unbonding_delegations: Vec<Value>,
+    pub redelegations: Vec<Value>,
+    pub exported: bool,
+}
+
+/// Params of the staking module, as the Go SDK's `cosmos.staking.v1beta1.Params`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Params {
+    pub unbonding_time: String,
+    pub max_validators: u32,
+    pub max_entries: u32,
+    pub historical_entries: u32,
+    pub bond_denom: String,
+    #[serde(default = "default_min_commission_rate")]
+    pub min_commission_rate: String,
+}
+
+impl Default for Params {
+    /// The values of Go's `DefaultParams()`, i.e. a 21 day unbonding time and `stake` bonds.
+    fn default() -> Self {
+        Self {
+            unbonding_time: "1814400s".to_owned(),
+            max_validators: 100,
+            max_entries: 7,
+            historical_entries: 10000,
+            bond_denom: "stake".to_owned(),
+            min_commission_rate: default_min_commission_rate(),
+        }
+    }
+}
+
+fn default_min_commission_rate() -> String {
+    "0.000000000000000000".to_owned()
+}
+
+impl GenesisState {
+    /// Decodes the staking module's section of the genesis `app_state`.
+    pub fn decode(genesis: &Value) -> Result<Self, Error> {
+        decode_genesis(genesis).map_err(invalid_genesis)
+    }
+
+    /// Checks that no validator or delegation is imported, and that the params are valid.
+    pub fn validate(&self) -> Result<(), Error> {
+        for (name, entries) in [
+            ("validators", &self.validators),
+            ("validator powers", &self.last_validator_powers),
+            ("delegations", &self.delegations),
+            ("unbonding delegations", &self.unbonding_delegations),
+            ("redelegations", &self.redelegations),
+        ] {
+            ensure_empty(name, entries).map_err(invalid_genesis)?;
+        }
+        if !matches!(self.last_total_power.as_str(), "" | "0") {
+            return Err(invalid_genesis(format!(
+                "importing the last total power ({}) isn't supported",
+                self.last_total_power
+            )));
+        }
+        self.params.validate()
+    }
+}
+
+impl Params {
+    pub fn validate(&self) -> Result<(), Error> {
+        if parse_duration(&self.unbonding_time)
+            .map_err(invalid_genesis)?
+            .is_zero()
+        {
+            return Err(invalid_genesis(format!(
+                "unbonding time must be positive: {}",
+                self.unbonding_time
+            )));
+        }
+        if self.max_validators == 0 {
+            return Err(invalid_genesis(
+                "max validators must be positive".to_owned(),
+            ));
+        }
+        if self.max_entries == 0 {
+            return Err(invalid_genesis(
+                "max entries must be positive: 0".to_owned(),
+            ));
+        }
+        if self.bond_denom.trim().is_empty() {
+            return Err(invalid_genesis("bond denom cannot be blank".to_owned()));
+        }
+        validate_denom(&self.bond_denom).map_err(invalid_genesis)?;
+        if parse_dec(&self.min_commission_rate).map_err(invalid_genesis)? > DEC_ONE {
+            return Err(invalid_genesis(format!(
+                "minimum commission rate cannot be greater than 100%: {}",
+                self.min_commission_rate
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn invalid_genesis(reason: String) -> Error {
+    Error::InvalidGenesis { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_default_genesis() {
+        let genesis = GenesisState::decode(&Value::Null).unwrap();
+        genesis.validate().unwrap();
+
+        // `simd init` output
+        let genesis = GenesisState::decode(&json!({
+            "params": {
+                "unbonding_time": "1814400s",
+                "max_validators": 100,
+                "max_entries": 7,
+                "historical_entries": 10000,
+                "bond_denom": "stake",
+                "min_commission_rate": "0.000000000000000000"
+            },
+            "last_total_power": "0",
+            "last_validator_powers": [],
+            "validators": [],
+            "delegations": [],
+            "unbonding_delegations": [],
+            "redelegations": [],
+            "exported": false
+        }))
+        .unwrap();
+        assert_eq!(genesis.params, Params::default());
+        genesis.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        let invalid = |update: fn(&mut GenesisState)| {
+            let mut genesis = GenesisState::default();
+            update(&mut genesis);
+            genesis.validate().unwrap_err().to_string()
+        };
+
+        assert!(invalid(|g| g.params.unbonding_time = "0s".to_owned())
+            .contains("unbonding time must be positive"));
+        assert!(invalid(|g| g.params.max_validators = 0).contains("max validators"));
+        assert!(invalid(|g| g.params.max_entries = 0).contains("max entries"));
+        assert!(invalid(|g| g.params.bond_denom = " ".to_owned()).contains("cannot be blank"));
+        assert!(
+            invalid(|g| g.params.min_commission_rate = "1.01".to_owned())
+                .contains("greater than 100%")
+        );
+        assert!(invalid(|g| g.validators.push(json!({}))).contains("importing validators"));
+        assert!(invalid(|g| g.last_total_power = "10".to_owned()).contains("total power"));
+    }
+}
@@ -1,19 +1,43 @@
 use std::marker::PhantomData;
 
+use anyhow::Result;
 use ibc_proto::cosmos::staking::v1beta1::query_server::QueryServer;
 
-use cosmos_store::{ProvableStore, SharedStore};
+use cosmos_store::{ProvableStore, SharedStore, Store};
+use cosmos_x_module_api::module::Module;
 
+use super::genesis::GenesisState;
 use super::service::StakingService;
 
-pub struct Staking<S>(PhantomData<S>);
+/// The staking module, which only validates its genesis state and serves (stubbed) queries - the
+/// validator set is managed by CometBFT.
+pub struct Staking<S> {
+    store: SharedStore<S>,
+}
 
 impl<S: 'static + ProvableStore> Staking<S> {
-    pub fn new(_store: SharedStore<S>) -> Self {
-        Self(PhantomData)
+    pub fn new(store: SharedStore<S>) -> Self {
+        Self { store }
     }
 
     pub fn service(&self) -> QueryServer<StakingService<S>> {
         QueryServer::new(StakingService(PhantomData))
     }
 }
+
+impl<S: Store> Module for Staking<S> {
+    type Store = S;
+
+    fn validate_genesis(&self, genesis: &serde_json::Value) -> Result<()> {
+        GenesisState::decode(genesis)?.validate()?;
+        Ok(())
+    }
+
+    fn store_mut(&mut self) -> &mut SharedStore<S> {
+        &mut self.store
+    }
+
+    fn store(&self) -> &SharedStore<S> {
+        &self.store
+    }
+}
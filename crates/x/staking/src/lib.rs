@@ -1,2 +1,4 @@
+pub mod error;
+pub mod genesis;
 pub mod impls;
 pub mod service;
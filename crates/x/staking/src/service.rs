@@ -31,91 +31,105 @@ impl<S: ProvableStore + 'static> Query for StakingService<S> {
         &self,
         _request: Request<QueryValidatorsRequest>,
     ) -> Result<Response<QueryValidatorsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Validators is not implemented"))
     }
 
     async fn validator(
         &self,
         _request: Request<QueryValidatorRequest>,
     ) -> Result<Response<QueryValidatorResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Validator is not implemented"))
     }
 
     async fn validator_delegations(
         &self,
         _request: Request<QueryValidatorDelegationsRequest>,
     ) -> Result<Response<QueryValidatorDelegationsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "ValidatorDelegations is not implemented",
+        ))
     }
 
     async fn validator_unbonding_delegations(
         &self,
         _request: Request<QueryValidatorUnbondingDelegationsRequest>,
     ) -> Result<Response<QueryValidatorUnbondingDelegationsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "ValidatorUnbondingDelegations is not implemented",
+        ))
     }
 
     async fn delegation(
         &self,
         _request: Request<QueryDelegationRequest>,
     ) -> Result<Response<QueryDelegationResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Delegation is not implemented"))
     }
 
     async fn unbonding_delegation(
         &self,
         _request: Request<QueryUnbondingDelegationRequest>,
     ) -> Result<Response<QueryUnbondingDelegationResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "UnbondingDelegation is not implemented",
+        ))
     }
 
     async fn delegator_delegations(
         &self,
         _request: Request<QueryDelegatorDelegationsRequest>,
     ) -> Result<Response<QueryDelegatorDelegationsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "DelegatorDelegations is not implemented",
+        ))
     }
 
     async fn delegator_unbonding_delegations(
         &self,
         _request: Request<QueryDelegatorUnbondingDelegationsRequest>,
     ) -> Result<Response<QueryDelegatorUnbondingDelegationsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "DelegatorUnbondingDelegations is not implemented",
+        ))
     }
 
     async fn redelegations(
         &self,
         _request: Request<QueryRedelegationsRequest>,
     ) -> Result<Response<QueryRedelegationsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Redelegations is not implemented"))
     }
 
     async fn delegator_validators(
         &self,
         _request: Request<QueryDelegatorValidatorsRequest>,
     ) -> Result<Response<QueryDelegatorValidatorsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "DelegatorValidators is not implemented",
+        ))
     }
 
     async fn delegator_validator(
         &self,
         _request: Request<QueryDelegatorValidatorRequest>,
     ) -> Result<Response<QueryDelegatorValidatorResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented(
+            "DelegatorValidator is not implemented",
+        ))
     }
 
     async fn historical_info(
         &self,
         _request: Request<QueryHistoricalInfoRequest>,
     ) -> Result<Response<QueryHistoricalInfoResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("HistoricalInfo is not implemented"))
     }
 
     async fn pool(
         &self,
         _request: Request<QueryPoolRequest>,
     ) -> Result<Response<QueryPoolResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Pool is not implemented"))
     }
 
     async fn params(
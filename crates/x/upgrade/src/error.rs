@@ -1,3 +1,7 @@
+use cosmos_x_module_api::error::{
+    AbciError, RegisteredError, ERR_INTERNAL, ERR_INVALID_REQUEST, ERR_NOT_FOUND,
+    ERR_UNKNOWN_REQUEST,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +12,9 @@ pub enum Error {
     #[error("Invalid path")]
     InvalidPath,
 
+    #[error("invalid genesis state: {reason}")]
+    InvalidGenesis { reason: String },
+
     #[error("Proof not Found")]
     ProofNotFound,
 
@@ -17,3 +24,14 @@ pub enum Error {
     #[error("not handled")]
     NotHandled,
 }
+
+impl AbciError for Error {
+    fn registered_error(&self) -> RegisteredError {
+        match self {
+            Error::Unknown(_) => ERR_INTERNAL,
+            Error::InvalidPath | Error::InvalidGenesis { .. } => ERR_INVALID_REQUEST,
+            Error::ProofNotFound | Error::DataNotFound => ERR_NOT_FOUND,
+            Error::NotHandled => ERR_UNKNOWN_REQUEST,
+        }
+    }
+}
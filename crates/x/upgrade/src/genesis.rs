@@ -0,0 +1,33 @@
+use cosmos_x_module_api::genesis::decode_genesis;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Genesis state of the upgrade module, i.e. `app_state.upgrade`, which is empty as in the Go
+/// SDK - upgrade plans are scheduled by governance proposals only.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisState {}
+
+impl GenesisState {
+    /// Decodes the upgrade module's section of the genesis `app_state`, which can't have any
+    /// field.
+    pub fn decode(genesis: &Value) -> Result<Self, Error> {
+        decode_genesis(genesis).map_err(|reason| Error::InvalidGenesis { reason })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(GenesisState::decode(&Value::Null).unwrap(), GenesisState {});
+        assert_eq!(GenesisState::decode(&json!({})).unwrap(), GenesisState {});
+        assert!(GenesisState::decode(&json!({ "plan": {} })).is_err());
+        assert!(GenesisState::decode(&json!("")).is_err());
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use tracing::debug;
 
 use crate::error::Error;
+use crate::genesis::GenesisState;
 use anyhow::Result;
 use cosmrs::AccountId;
 use ibc_proto::cosmos::upgrade::v1beta1::query_server::QueryServer;
@@ -21,7 +22,7 @@ use ibc::hosts::tendermint::upgrade_proposal::UpgradeValidationContext;
 use ibc::hosts::tendermint::upgrade_proposal::{Plan, UpgradeChain};
 use ibc::hosts::tendermint::SDK_UPGRADE_QUERY_PATH;
 
-use tendermint_proto::abci::Event;
+use tendermint_proto::abci::{CommitInfo, Event};
 use tendermint_proto::crypto::ProofOp;
 
 use super::path::UpgradePlanPath;
@@ -30,6 +31,7 @@ use crate::query::UPGRADE_PLAN_QUERY_PATH;
 use cosmos_helper::{Height, Path, QueryResult};
 use cosmos_store::{ProtobufStore, ProvableStore, SharedStore, Store, TypedStore};
 use cosmos_x_module_api::module::Module;
+use cosmos_x_module_api::types::DeliverResult;
 
 #[derive(Clone)]
 pub struct Upgrade<S>
@@ -74,10 +76,15 @@ where
 {
     type Store = S;
 
-    fn deliver(&mut self, _message: Any, _signer: &AccountId) -> Result<Vec<Event>> {
+    fn deliver(&mut self, _message: Any, _signer: &AccountId) -> Result<DeliverResult> {
         Err(Error::NotHandled.into())
     }
 
+    fn validate_genesis(&self, genesis: &serde_json::Value) -> Result<()> {
+        GenesisState::decode(genesis)?;
+        Ok(())
+    }
+
     fn query(
         &self,
         data: &[u8],
@@ -129,7 +136,11 @@ where
         Err(Error::NotHandled.into())
     }
 
-    fn begin_block(&mut self, header: &tendermint::block::Header) -> Vec<Event> {
+    fn begin_block(
+        &mut self,
+        header: &tendermint::block::Header,
+        _last_commit_info: &CommitInfo,
+    ) -> Vec<Event> {
         if let Ok(plan) = self.upgrade_plan() {
             debug!("Upgrade plan found: {:?}", plan);
 
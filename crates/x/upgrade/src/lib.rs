@@ -1,4 +1,5 @@
 pub mod error;
+pub mod genesis;
 pub mod impls;
 pub mod path;
 pub mod query;
@@ -45,21 +45,21 @@ impl<S: Store + 'static> UpgradeQuery for UpgradeService<S> {
         &self,
         _request: Request<QueryCurrentPlanRequest>,
     ) -> Result<Response<QueryCurrentPlanResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("CurrentPlan is not implemented"))
     }
 
     async fn applied_plan(
         &self,
         _request: Request<QueryAppliedPlanRequest>,
     ) -> Result<Response<QueryAppliedPlanResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("AppliedPlan is not implemented"))
     }
 
     async fn authority(
         &self,
         _request: Request<QueryAuthorityRequest>,
     ) -> Result<Response<QueryAuthorityResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("Authority is not implemented"))
     }
 
     async fn upgraded_consensus_state(
@@ -93,6 +93,6 @@ impl<S: Store + 'static> UpgradeQuery for UpgradeService<S> {
         &self,
         _request: Request<QueryModuleVersionsRequest>,
     ) -> Result<Response<QueryModuleVersionsResponse>, Status> {
-        unimplemented!()
+        Err(Status::unimplemented("ModuleVersions is not implemented"))
     }
 }